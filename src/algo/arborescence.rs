@@ -0,0 +1,167 @@
+use std::ops::Sub;
+
+use crate::algo::Measure;
+use crate::graph::{DiGraph, NodeIndex};
+use crate::visit::{EdgeRef, IntoEdgeReferences, IntoNodeReferences, NodeIndexable, NodeRef};
+
+/// \[Generic\] Compute a *minimum spanning arborescence* rooted at `root`
+/// using [Edmonds' algorithm][1].
+///
+/// [1]: https://en.wikipedia.org/wiki/Edmonds%27_algorithm
+///
+/// An arborescence is the directed analogue of a spanning tree: every node
+/// except `root` has exactly one incoming edge, and every node can be
+/// reached from `root`. The function `edge_cost` should return the cost for
+/// a particular edge; the returned arborescence has the minimum total cost.
+///
+/// Edges are used in their `source → target` orientation, so the input is
+/// expected to be a directed graph. Self loops are ignored.
+///
+/// Return `None` if some node is unreachable from `root`.
+///
+/// The resulting graph has the nodes of the input graph in the order of
+/// `node_references` (for a `Graph` this means identical node indices),
+/// and **|V| - 1** edges.
+///
+/// Computes in **O(|V| |E|)** time.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::min_spanning_arborescence;
+/// use petgraph::visit::EdgeRef;
+///
+/// let mut g = Graph::<&str, u32>::new();
+/// let r = g.add_node("r");
+/// let a = g.add_node("a");
+/// let b = g.add_node("b");
+/// g.extend_with_edges(&[(r, a, 5), (r, b, 1), (b, a, 1), (a, b, 1)]);
+///
+/// let tree = min_spanning_arborescence(&g, r, |e| *e.weight()).unwrap();
+/// assert_eq!(tree.edge_count(), 2);
+/// assert!(tree.find_edge(r, b).is_some());
+/// assert!(tree.find_edge(b, a).is_some());
+/// ```
+pub fn min_spanning_arborescence<G, F, K>(
+    g: G,
+    root: G::NodeId,
+    mut edge_cost: F,
+) -> Option<DiGraph<G::NodeWeight, G::EdgeWeight>>
+where
+    G: IntoNodeReferences + IntoEdgeReferences + NodeIndexable,
+    G::NodeWeight: Clone,
+    G::EdgeWeight: Clone,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy + Sub<K, Output = K>,
+{
+    // Compact node numbering in the order of `node_references`.
+    let mut order = vec![std::usize::MAX; g.node_bound()];
+    let mut tree = DiGraph::new();
+    for node in g.node_references() {
+        order[g.to_index(node.id())] = tree.add_node(node.weight().clone()).index();
+    }
+    let n = tree.node_count();
+
+    let edges: Vec<G::EdgeRef> = g.edge_references().collect();
+    let candidates = edges
+        .iter()
+        .enumerate()
+        .map(|(i, e)| {
+            let (s, t) = (order[g.to_index(e.source())], order[g.to_index(e.target())]);
+            (s, t, edge_cost(*e), i)
+        })
+        .filter(|&(s, t, _, _)| s != t)
+        .collect::<Vec<_>>();
+
+    let root = order[g.to_index(root)];
+    let chosen = edmonds(n, root, &candidates)?;
+    for i in chosen {
+        let e = edges[candidates[i].3];
+        let (s, t) = (order[g.to_index(e.source())], order[g.to_index(e.target())]);
+        tree.add_edge(NodeIndex::new(s), NodeIndex::new(t), e.weight().clone());
+    }
+    Some(tree)
+}
+
+/// Solve the arborescence problem on nodes `0..n` for the edge list
+/// `(source, target, cost, _)`, returning the positions of the chosen
+/// edges in `edges`.
+fn edmonds<K, T>(n: usize, root: usize, edges: &[(usize, usize, K, T)]) -> Option<Vec<usize>>
+where
+    K: Measure + Copy + Sub<K, Output = K>,
+{
+    // Cheapest incoming edge of every node.
+    let mut incoming: Vec<Option<usize>> = vec![None; n];
+    for (i, &(_, t, cost, _)) in edges.iter().enumerate() {
+        if t == root {
+            continue;
+        }
+        match incoming[t] {
+            Some(j) if edges[j].2 <= cost => {}
+            _ => incoming[t] = Some(i),
+        }
+    }
+    if (0..n).any(|v| v != root && incoming[v].is_none()) {
+        return None;
+    }
+
+    // Find the cycles formed by the cheapest incoming edges, and give every
+    // node its (possibly contracted) component.
+    const UNSEEN: usize = std::usize::MAX;
+    let mut comp = vec![UNSEEN; n];
+    let mut stamp = vec![UNSEEN; n];
+    let mut in_cycle = vec![false; n];
+    let mut ncomp = 0;
+    for start in 0..n {
+        let mut v = start;
+        while v != root && stamp[v] == UNSEEN && comp[v] == UNSEEN {
+            stamp[v] = start;
+            v = edges[incoming[v].unwrap()].0;
+        }
+        if v != root && stamp[v] == start && comp[v] == UNSEEN {
+            // Walked back into the current path: `v` lies on a new cycle.
+            let mut u = v;
+            loop {
+                comp[u] = ncomp;
+                in_cycle[u] = true;
+                u = edges[incoming[u].unwrap()].0;
+                if u == v {
+                    break;
+                }
+            }
+            ncomp += 1;
+        }
+    }
+    if ncomp == 0 {
+        return Some((0..n).filter_map(|v| incoming[v]).collect());
+    }
+    for c in comp.iter_mut() {
+        if *c == UNSEEN {
+            *c = ncomp;
+            ncomp += 1;
+        }
+    }
+
+    // Contract the cycles, reducing the cost of edges entering a cycle by
+    // the cost of the cycle edge they would replace.
+    let contracted = edges
+        .iter()
+        .enumerate()
+        .filter(|&(_, &(s, t, _, _))| comp[s] != comp[t])
+        .map(|(i, &(s, t, cost, _))| {
+            let cost = if in_cycle[t] {
+                cost - edges[incoming[t].unwrap()].2
+            } else {
+                cost
+            };
+            (comp[s], comp[t], cost, i)
+        })
+        .collect::<Vec<_>>();
+
+    let chosen = edmonds(ncomp, comp[root], &contracted)?;
+    for c in chosen {
+        let i = contracted[c].3;
+        incoming[edges[i].1] = Some(i);
+    }
+    Some((0..n).filter_map(|v| incoming[v]).collect())
+}
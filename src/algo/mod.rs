@@ -4,29 +4,50 @@
 //! so that they are generally applicable. For now, some of these still require
 //! the `Graph` type.
 
+mod arborescence;
 pub mod dominators;
+mod parallel_bfs;
+mod reachability;
+mod statistics;
 pub mod tred;
 
-use std::cmp::min;
-use std::collections::{BinaryHeap, HashMap};
+use std::cmp::{max, min, Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
 
+use fixedbitset::FixedBitSet;
+
+use crate::adj::List;
 use crate::prelude::*;
 
-use super::graph::IndexType;
+use super::graph::{EdgeReference, IndexType};
 use super::unionfind::UnionFind;
 use super::visit::{
-    GraphBase, GraphRef, IntoEdgeReferences, IntoEdges, IntoNeighbors, IntoNeighborsDirected,
-    IntoNodeIdentifiers, NodeCompactIndexable, NodeCount, NodeIndexable, Reversed, VisitMap,
-    Visitable,
+    GraphBase, GraphProp, GraphRef, IntoEdgeReferences, IntoEdges, IntoNeighbors,
+    IntoNeighborsDirected, IntoNodeIdentifiers, NodeCompactIndexable, NodeCount, NodeIndexable,
+    Reversed, VisitMap, Visitable,
 };
 use super::EdgeType;
 use crate::data::Element;
 use crate::scored::MinScored;
+use crate::util::random_index;
 use crate::visit::Walker;
-use crate::visit::{Data, IntoNodeReferences, NodeRef};
+use crate::visit::{Data, EdgeFiltered, IntoNodeReferences, NodeRef};
 
-pub use super::astar::astar;
-pub use super::dijkstra::dijkstra;
+pub use self::arborescence::min_spanning_arborescence;
+pub use self::parallel_bfs::parallel_bfs_distances;
+pub use self::reachability::{
+    reachability_matrix, transitive_closure_fast, IncrementalReachability, ReachabilityOracle,
+};
+pub use self::statistics::{
+    degree_signature, edge_weight_histogram, edge_weight_quantile, group_by_signature,
+    in_weight_map, modularity, out_weight_map, random_neighbor, reciprocity, sample_edge,
+    sample_neighbors, sample_neighbors_weighted,
+};
+pub use super::astar::{astar, greedy_best_first};
+pub use super::dijkstra::{
+    all_shortest_paths, dijkstra, dijkstra_avoiding, reachable_within_budget, spfa,
+};
 pub use super::k_shortest_path::k_shortest_path;
 
 pub use super::isomorphism::{
@@ -89,6 +110,142 @@ where
     labels.len()
 }
 
+/// \[Generic\] Compute the weakly connected components of the subgraph made of
+/// the edges whose cost is at least `threshold`.
+///
+/// The function `edge_cost` should return the cost for a particular edge;
+/// edges below the threshold are ignored, so raising the threshold can only
+/// split components, never join them. Sweeping the threshold therefore gives
+/// the levels of a single-linkage clustering.
+///
+/// Return one vector of nodes per component, every node of the graph being in
+/// exactly one of them. Both the components and the nodes within a component
+/// are in the order of `node_identifiers`.
+///
+/// Computes in **O(|V| + |E|)** time, up to the inverse Ackermann function.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::components_above_threshold;
+///
+/// let g = Graph::<(), u32>::from_edges(&[(0, 1, 5), (1, 2, 1), (2, 3, 5)]);
+/// assert_eq!(components_above_threshold(&g, 1, |e| *e.weight()).len(), 1);
+/// assert_eq!(
+///     components_above_threshold(&g, 2, |e| *e.weight()),
+///     vec![vec![0.into(), 1.into()], vec![2.into(), 3.into()]]
+/// );
+/// ```
+pub fn components_above_threshold<G, F, K>(
+    g: G,
+    threshold: K,
+    mut edge_cost: F,
+) -> Vec<Vec<G::NodeId>>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+    F: FnMut(G::EdgeRef) -> K,
+    K: PartialOrd,
+{
+    let mut vertex_sets = UnionFind::new(g.node_bound());
+    for edge in g.edge_references() {
+        let (a, b) = (edge.source(), edge.target());
+        if edge_cost(edge) >= threshold {
+            vertex_sets.union(g.to_index(a), g.to_index(b));
+        }
+    }
+    let labels = vertex_sets.into_labeling();
+    let mut component = HashMap::new();
+    let mut components: Vec<Vec<G::NodeId>> = Vec::new();
+    for node in g.node_identifiers() {
+        let next = components.len();
+        let i = *component.entry(labels[g.to_index(node)]).or_insert(next);
+        if i == next {
+            components.push(Vec::new());
+        }
+        components[i].push(node);
+    }
+    components
+}
+
+/// \[Generic\] Label every node with the id of its weakly connected
+/// component.
+///
+/// Return a map from each node to its component id. The ids are
+/// `0..connected_components(g)`, numbered in the order in which the
+/// components are first seen in `node_identifiers`, so the labeling is
+/// deterministic.
+///
+/// Computes in **O(|V| + |E|)** time, up to the inverse Ackermann function.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::connected_component_ids;
+///
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (2, 3), (3, 1), (4, 5)]);
+/// let ids = connected_component_ids(&g);
+/// assert_eq!(ids[&0.into()], 0);
+/// assert_eq!(ids[&2.into()], 0);
+/// assert_eq!(ids[&5.into()], 1);
+/// ```
+pub fn connected_component_ids<G>(g: G) -> HashMap<G::NodeId, usize>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+    G::NodeId: Eq + Hash,
+{
+    let mut vertex_sets = UnionFind::new(g.node_bound());
+    for edge in g.edge_references() {
+        let (a, b) = (edge.source(), edge.target());
+        vertex_sets.union(g.to_index(a), g.to_index(b));
+    }
+    let labels = vertex_sets.into_labeling();
+    let mut component = HashMap::new();
+    g.node_identifiers()
+        .map(|node| {
+            let next = component.len();
+            let id = *component.entry(labels[g.to_index(node)]).or_insert(next);
+            (node, id)
+        })
+        .collect()
+}
+
+/// \[Generic\] Compute the sizes of the weakly connected components.
+///
+/// Return the number of nodes of every component, largest first. Only the
+/// sizes are counted, the members of the components are not collected, which
+/// makes this cheaper than `components_above_threshold` when only the size
+/// distribution is needed.
+///
+/// Computes in **O(|V| log |V| + |E|)** time, up to the inverse Ackermann
+/// function.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::weak_component_sizes;
+///
+/// let mut g = Graph::<(), ()>::from_edges(&[(0, 1), (2, 1), (3, 4)]);
+/// g.add_node(());
+/// assert_eq!(weak_component_sizes(&g), vec![3, 2, 1]);
+/// ```
+pub fn weak_component_sizes<G>(g: G) -> Vec<usize>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+{
+    let mut vertex_sets = UnionFind::new(g.node_bound());
+    for edge in g.edge_references() {
+        let (a, b) = (edge.source(), edge.target());
+        vertex_sets.union(g.to_index(a), g.to_index(b));
+    }
+    let mut counts = vec![0; g.node_bound()];
+    for node in g.node_identifiers() {
+        counts[vertex_sets.find_mut(g.to_index(node))] += 1;
+    }
+    counts.retain(|&count| count > 0);
+    counts.sort_by(|a, b| b.cmp(a));
+    counts
+}
+
 /// \[Generic\] Return `true` if the input graph contains a cycle.
 ///
 /// Always treats the input graph as if undirected.
@@ -177,6 +334,415 @@ where
     })
 }
 
+/// \[Generic\] Perform a topological sort of a directed graph, pairing each
+/// node with its depth.
+///
+/// The depth of a node is the length of the longest path reaching it from
+/// any source, so nodes without incoming edges have depth `0`. Nodes are
+/// returned in the same order as by `toposort`; the depths can be used as
+/// layer indices for a layered layout.
+///
+/// If the graph has a cycle, return a `Cycle` error. Self loops are also cycles.
+///
+/// Computes in **O(|V| + |E|)** time.
+#[allow(clippy::type_complexity)]
+pub fn toposort_with_depth<G>(g: G) -> Result<Vec<(G::NodeId, usize)>, Cycle<G::NodeId>>
+where
+    G: IntoNeighborsDirected + IntoNodeIdentifiers + Visitable + NodeIndexable,
+{
+    let order = toposort(g, None)?;
+    let mut depth = vec![0; g.node_bound()];
+    for &nx in &order {
+        let d = depth[g.to_index(nx)] + 1;
+        for succ in g.neighbors(nx) {
+            let succ = &mut depth[g.to_index(succ)];
+            *succ = max(*succ, d);
+        }
+    }
+    Ok(order
+        .into_iter()
+        .map(|nx| (nx, depth[g.to_index(nx)]))
+        .collect())
+}
+
+/// \[Generic\] Assign every node of a directed acyclic graph to a layer, so
+/// that every edge goes from a lower to a higher layer, keeping the edges
+/// short.
+///
+/// This is the layer assignment step of a layered (Sugiyama style) drawing.
+/// Nodes are first put on their depth, as given by `toposort_with_depth`,
+/// which makes the layering as flat as possible. Then, in reverse
+/// topological order, every node with more outgoing than incoming edges is
+/// pulled up to just below its lowest successor, which shortens the total
+/// span of the edges, the sum over all edges of the difference of the
+/// layers of their endpoints. Finally, layers are shifted to start at `0`.
+///
+/// **Note:** This is a heuristic: the total span is often, but not always,
+/// the smallest possible.
+///
+/// If the graph has a cycle, return one of its nodes as the error. Self
+/// loops are also cycles.
+///
+/// Computes in **O(|V| + |E|)** time.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::ordered_layout;
+///
+/// // 0 -> 1 -> 2, and 3 -> 2
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (3, 2)]);
+/// let layers = ordered_layout(&g).unwrap();
+/// assert_eq!(layers[&0.into()], 0);
+/// // 3 is pulled up next to 2
+/// assert_eq!(layers[&3.into()], 1);
+/// assert_eq!(layers[&2.into()], 2);
+/// ```
+pub fn ordered_layout<G>(g: G) -> Result<HashMap<G::NodeId, usize>, G::NodeId>
+where
+    G: IntoNeighborsDirected + IntoNodeIdentifiers + Visitable + NodeIndexable,
+    G::NodeId: Eq + Hash,
+{
+    let order = toposort_with_depth(g).map_err(|cycle| cycle.node_id())?;
+    let mut layer = vec![0; g.node_bound()];
+    for &(nx, depth) in &order {
+        layer[g.to_index(nx)] = depth;
+    }
+    for &(nx, _) in order.iter().rev() {
+        let incoming = g.neighbors_directed(nx, Incoming).count();
+        let outgoing = g.neighbors_directed(nx, Outgoing).count();
+        if outgoing > incoming {
+            let lowest = g
+                .neighbors_directed(nx, Outgoing)
+                .map(|succ| layer[g.to_index(succ)])
+                .min()
+                .unwrap();
+            let i = g.to_index(nx);
+            layer[i] = max(layer[i], lowest - 1);
+        }
+    }
+    let base = order
+        .iter()
+        .map(|&(nx, _)| layer[g.to_index(nx)])
+        .min()
+        .unwrap_or(0);
+    Ok(order
+        .into_iter()
+        .map(|(nx, _)| (nx, layer[g.to_index(nx)] - base))
+        .collect())
+}
+
+/// \[Generic\] Order the nodes of `subset` consistently with the
+/// reachability in the whole graph.
+///
+/// Return the nodes of `subset`, without duplicates, ordered so that if a
+/// node can reach another one, through any path and also via nodes outside
+/// of the subset, it comes first. Nodes that can not reach each other are in
+/// no particular order.
+///
+/// Cycles are only a problem if they make two nodes of the subset reach each
+/// other: then a `Cycle` error with one of them is returned. Other cycles,
+/// including self loops, are allowed.
+///
+/// This uses the strongly connected components of the whole graph, and
+/// computes in **O(|V| + |E|)** time.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::toposort_subset;
+///
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 1), (3, 0)]);
+/// let order = toposort_subset(&g, vec![2.into(), 3.into()]);
+/// assert_eq!(order, Ok(vec![3.into(), 2.into()]));
+/// // 1 and 2 reach each other
+/// assert!(toposort_subset(&g, vec![1.into(), 2.into()]).is_err());
+/// ```
+pub fn toposort_subset<G, I>(g: G, subset: I) -> Result<Vec<G::NodeId>, Cycle<G::NodeId>>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+    I: IntoIterator<Item = G::NodeId>,
+{
+    let mut selected = vec![false; g.node_bound()];
+    for nx in subset {
+        selected[g.to_index(nx)] = true;
+    }
+    // `tarjan_scc` returns the components in reverse topological order.
+    let mut order = Vec::new();
+    for scc in tarjan_scc(g).into_iter().rev() {
+        let mut members = scc.into_iter().filter(|&nx| selected[g.to_index(nx)]);
+        if let Some(nx) = members.next() {
+            if let Some(other) = members.next() {
+                return Err(Cycle(other));
+            }
+            order.push(nx);
+        }
+    }
+    Ok(order)
+}
+
+/// \[Generic\] Perform a topological sort of a directed graph that, among
+/// the nodes that are ready, always emits the one with the largest `key`
+/// first.
+///
+/// This is Kahn's algorithm with a max-heap of ready nodes: a node is ready
+/// once all its predecessors are emitted. The result is a valid topological
+/// order that puts high priority work, for example the tasks on a critical
+/// path, as early as the dependencies allow. Ready nodes with equal keys are
+/// emitted in the order of `node_identifiers`. `key` is called once for
+/// every node that becomes ready.
+///
+/// If the graph has a cycle, return a `Cycle` error with a node on the
+/// cycle. Self loops are cycles.
+///
+/// Computes in **O(|V| log |V| + |E|)** time.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::toposort_by_key;
+///
+/// let mut g = Graph::<u32, ()>::new();
+/// let a = g.add_node(1);
+/// let b = g.add_node(5);
+/// let c = g.add_node(3);
+/// g.add_edge(a, c, ());
+/// // b has the largest key, but c waits for a
+/// assert_eq!(toposort_by_key(&g, |n| g[n]), Ok(vec![b, a, c]));
+/// ```
+pub fn toposort_by_key<G, F, K>(g: G, mut key: F) -> Result<Vec<G::NodeId>, Cycle<G::NodeId>>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+    F: FnMut(G::NodeId) -> K,
+    K: Ord,
+{
+    let mut in_degree = vec![0usize; g.node_bound()];
+    for nx in g.node_identifiers() {
+        for succ in g.neighbors(nx) {
+            in_degree[g.to_index(succ)] += 1;
+        }
+    }
+    // order by key, then by the smaller index
+    let mut ready = BinaryHeap::new();
+    for nx in g.node_identifiers() {
+        let ix = g.to_index(nx);
+        if in_degree[ix] == 0 {
+            ready.push((key(nx), Reverse(ix)));
+        }
+    }
+    let mut order = Vec::with_capacity(in_degree.len());
+    while let Some((_, Reverse(ix))) = ready.pop() {
+        let nx = g.from_index(ix);
+        order.push(nx);
+        for succ in g.neighbors(nx) {
+            let succ_ix = g.to_index(succ);
+            in_degree[succ_ix] -= 1;
+            if in_degree[succ_ix] == 0 {
+                ready.push((key(succ), Reverse(succ_ix)));
+            }
+        }
+    }
+    if order.len() < g.node_identifiers().count() {
+        // the nodes that were never ready all lie on or behind a cycle
+        let on_cycle = tarjan_scc(g)
+            .into_iter()
+            .find(|scc| scc.len() > 1 || g.neighbors(scc[0]).any(|succ| succ == scc[0]))
+            .unwrap();
+        return Err(Cycle(on_cycle[0]));
+    }
+    Ok(order)
+}
+
+/// \[Generic\] Find a small *feedback arc set*: a set of edges whose removal
+/// makes the graph acyclic, using the greedy heuristic of Eades, Lin and
+/// Smyth.
+///
+/// The nodes are put in a sequence by repeatedly taking out sinks, which go
+/// to the back, sources, which go to the front, and otherwise the node with
+/// the largest difference of out-degree and in-degree, which goes to the
+/// front too. The edges pointing backwards in that sequence, and all self
+/// loops, form the returned set, in the order of `edge_references`; parallel
+/// edges are listed once each.
+///
+/// The result is empty for an acyclic graph. Finding a minimum feedback arc
+/// set is NP-hard, so the returned set is not always the smallest one.
+///
+/// Computes in **O((|V| + |E|) log |V|)** time.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::feedback_arc_set;
+///
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 0), (2, 3)]);
+/// assert_eq!(feedback_arc_set(&g), vec![(2.into(), 0.into())]);
+/// ```
+pub fn feedback_arc_set<G>(g: G) -> Vec<(G::NodeId, G::NodeId)>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+{
+    let n = g.node_bound();
+    let mut succ = vec![Vec::new(); n];
+    let mut pred = vec![Vec::new(); n];
+    for edge in g.edge_references() {
+        let (a, b) = (g.to_index(edge.source()), g.to_index(edge.target()));
+        if a != b {
+            succ[a].push(b);
+            pred[b].push(a);
+        }
+    }
+    let mut out_degree: Vec<_> = succ.iter().map(Vec::len).collect();
+    let mut in_degree: Vec<_> = pred.iter().map(Vec::len).collect();
+    let delta = |v: usize, out_degree: &[usize], in_degree: &[usize]| {
+        out_degree[v] as isize - in_degree[v] as isize
+    };
+
+    let mut alive = vec![false; n];
+    let mut remaining = 0;
+    let mut sinks = Vec::new();
+    let mut sources = Vec::new();
+    // candidates by delta; outdated entries are skipped when popped
+    let mut candidates = BinaryHeap::new();
+    for node in g.node_identifiers() {
+        let v = g.to_index(node);
+        alive[v] = true;
+        remaining += 1;
+        candidates.push((delta(v, &out_degree, &in_degree), Reverse(v)));
+    }
+    for v in (0..n).rev() {
+        if alive[v] && out_degree[v] == 0 {
+            sinks.push(v);
+        } else if alive[v] && in_degree[v] == 0 {
+            sources.push(v);
+        }
+    }
+
+    let mut front = Vec::with_capacity(remaining);
+    let mut back = Vec::new();
+    while remaining > 0 {
+        let v = if let Some(v) = sinks.pop() {
+            if !alive[v] {
+                continue;
+            }
+            back.push(v);
+            v
+        } else if let Some(v) = sources.pop() {
+            if !alive[v] {
+                continue;
+            }
+            front.push(v);
+            v
+        } else {
+            let (d, Reverse(v)) = candidates.pop().unwrap();
+            if !alive[v] || d != delta(v, &out_degree, &in_degree) {
+                continue;
+            }
+            front.push(v);
+            v
+        };
+        alive[v] = false;
+        remaining -= 1;
+        for &w in &succ[v] {
+            if alive[w] {
+                in_degree[w] -= 1;
+                if in_degree[w] == 0 {
+                    sources.push(w);
+                }
+                candidates.push((delta(w, &out_degree, &in_degree), Reverse(w)));
+            }
+        }
+        for &w in &pred[v] {
+            if alive[w] {
+                out_degree[w] -= 1;
+                if out_degree[w] == 0 {
+                    sinks.push(w);
+                }
+                candidates.push((delta(w, &out_degree, &in_degree), Reverse(w)));
+            }
+        }
+    }
+
+    let mut position = vec![0; n];
+    for (i, &v) in front.iter().chain(back.iter().rev()).enumerate() {
+        position[v] = i;
+    }
+    g.edge_references()
+        .map(|edge| (edge.source(), edge.target()))
+        .filter(|&(a, b)| position[g.to_index(a)] >= position[g.to_index(b)])
+        .collect()
+}
+
+/// \[Generic\] Order the nodes with the reverse Cuthill-McKee algorithm, to
+/// reduce the bandwidth of the adjacency matrix.
+///
+/// Edge directions are ignored. Every connected component is traversed
+/// breadth-first, starting from one of its nodes of the smallest degree and
+/// visiting the unvisited neighbors of every node in order of increasing
+/// degree; the concatenation of these orders, reversed, is returned. Nodes
+/// that are adjacent thus get close positions, which clusters the nonzero
+/// entries of the adjacency matrix near its diagonal. Ties are broken by
+/// the node index.
+///
+/// Computes in **O(|V| + |E| log |V|)** time.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::cuthill_mckee_order;
+///
+/// // a star around 0
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (0, 2), (0, 3)]);
+/// let order = cuthill_mckee_order(&g);
+/// assert_eq!(order, vec![3.into(), 2.into(), 0.into(), 1.into()]);
+/// ```
+pub fn cuthill_mckee_order<G>(g: G) -> Vec<G::NodeId>
+where
+    G: IntoNeighborsDirected + IntoNodeIdentifiers + NodeIndexable,
+{
+    let n = g.node_bound();
+    // the distinct neighbors of every node in the undirected view
+    let mut adjacent = vec![Vec::new(); n];
+    for node in g.node_identifiers() {
+        let v = g.to_index(node);
+        let neighbors = &mut adjacent[v];
+        neighbors.extend(
+            g.neighbors_directed(node, Outgoing)
+                .chain(g.neighbors_directed(node, Incoming))
+                .map(|w| g.to_index(w))
+                .filter(|&w| w != v),
+        );
+        neighbors.sort_unstable();
+        neighbors.dedup();
+    }
+    let degree: Vec<_> = adjacent.iter().map(Vec::len).collect();
+    for neighbors in &mut adjacent {
+        neighbors.sort_by_key(|&w| (degree[w], w));
+    }
+
+    let mut starts: Vec<_> = g.node_identifiers().map(|node| g.to_index(node)).collect();
+    starts.sort_by_key(|&v| (degree[v], v));
+    let mut visited = vec![false; n];
+    let mut order = Vec::with_capacity(starts.len());
+    for start in starts {
+        if visited[start] {
+            continue;
+        }
+        visited[start] = true;
+        let mut i = order.len();
+        order.push(start);
+        while i < order.len() {
+            for &w in &adjacent[order[i]] {
+                if !visited[w] {
+                    visited[w] = true;
+                    order.push(w);
+                }
+            }
+            i += 1;
+        }
+    }
+    order.into_iter().rev().map(|v| g.from_index(v)).collect()
+}
+
 /// \[Generic\] Return `true` if the input directed graph contains a cycle.
 ///
 /// This implementation is recursive; use `toposort` if an alternative is
@@ -267,6 +833,130 @@ where
     })
 }
 
+/// \[Generic\] Check if the node `a` lies on a cycle, that is, if `a` can
+/// reach itself by following one or more edges.
+///
+/// A self loop counts as a cycle. For an undirected graph every edge can be
+/// walked back, so any node with a neighbor is reported to be on a cycle.
+///
+/// Unlike enumerating all cycles, this only runs one depth-first search from
+/// the successors of `a`.
+///
+/// Computes in **O(|V| + |E|)** time.
+///
+/// ```
+/// use petgraph::algo::is_on_cycle;
+/// use petgraph::graph::{DiGraph, NodeIndex};
+///
+/// let g = DiGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 0), (2, 3)]);
+/// assert!(is_on_cycle(&g, NodeIndex::new(1)));
+/// assert!(!is_on_cycle(&g, NodeIndex::new(3)));
+/// ```
+pub fn is_on_cycle<G>(g: G, a: G::NodeId) -> bool
+where
+    G: IntoNeighbors + Visitable,
+{
+    let mut dfs = Dfs::empty(g);
+    dfs.stack.extend(g.neighbors(a));
+    dfs.iter(g).any(|x| x == a)
+}
+
+/// \[Generic\] Find a cycle of minimum mean edge cost, using Karp's
+/// algorithm.
+///
+/// The function `edge_cost` should return the cost for a particular edge;
+/// costs may be negative. The mean cost of a cycle is the sum of the costs
+/// of its edges divided by their number.
+///
+/// Return the mean cost and the nodes of the cycle, in the order they are
+/// visited and without repeating the first one, or `None` if the graph is
+/// acyclic. A self loop is a cycle of one node. For an undirected graph
+/// every edge can be walked back, so it forms a cycle with itself.
+///
+/// Computes in **O(|V| |E|)** time and **O(|V|²)** space.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::min_mean_cycle;
+///
+/// // 0 -> 1 -> 0 has mean 2, 1 -> 2 -> 3 -> 1 has mean 1
+/// let g = Graph::<(), f64>::from_edges(&[
+///     (0, 1, 1.), (1, 0, 3.), (1, 2, 1.), (2, 3, 0.), (3, 1, 2.),
+/// ]);
+/// let (mean, cycle) = min_mean_cycle(&g, |e| *e.weight()).unwrap();
+/// assert_eq!(mean, 1.);
+/// assert_eq!(cycle.len(), 3);
+/// ```
+pub fn min_mean_cycle<G, F>(g: G, mut edge_cost: F) -> Option<(f64, Vec<G::NodeId>)>
+where
+    G: IntoEdgeReferences + NodeIndexable + GraphProp,
+    F: FnMut(G::EdgeRef) -> f64,
+{
+    let n = g.node_bound();
+    let mut arcs = Vec::new();
+    for edge in g.edge_references() {
+        let a = g.to_index(edge.source());
+        let b = g.to_index(edge.target());
+        let cost = edge_cost(edge);
+        arcs.push((a, b, cost));
+        if !g.is_directed() && a != b {
+            arcs.push((b, a, cost));
+        }
+    }
+    // walk_cost[k][v] is the least cost of a walk of exactly k edges that
+    // ends at v and starts anywhere, and walk_pred[k][v] the node before v.
+    let mut walk_cost = vec![vec![std::f64::INFINITY; n]; n + 1];
+    let mut walk_pred = vec![vec![!0; n]; n + 1];
+    for cost in &mut walk_cost[0] {
+        *cost = 0.;
+    }
+    for k in 1..=n {
+        for &(a, b, cost) in &arcs {
+            let next = walk_cost[k - 1][a] + cost;
+            if next < walk_cost[k][b] {
+                walk_cost[k][b] = next;
+                walk_pred[k][b] = a;
+            }
+        }
+    }
+
+    // The minimum mean is min over v of max over k of
+    // (walk_cost[n][v] - walk_cost[k][v]) / (n - k).
+    let mut best: Option<(f64, usize)> = None;
+    for (v, &end_cost) in walk_cost[n].iter().enumerate() {
+        if end_cost == std::f64::INFINITY {
+            continue;
+        }
+        let mean = (0..n)
+            .filter(|&k| walk_cost[k][v] < std::f64::INFINITY)
+            .map(|k| (end_cost - walk_cost[k][v]) / (n - k) as f64)
+            .fold(std::f64::NEG_INFINITY, f64::max);
+        match best {
+            Some((best_mean, _)) if best_mean <= mean => {}
+            _ => best = Some((mean, v)),
+        }
+    }
+    let (_, end) = best?;
+
+    // The walk of n edges to `end` repeats a node, and every cycle on it has
+    // the minimum mean.
+    let mut walk = vec![end; n + 1];
+    for k in (1..=n).rev() {
+        walk[k - 1] = walk_pred[k][walk[k]];
+    }
+    let mut seen: Vec<Option<usize>> = vec![None; n];
+    for (k, &v) in walk.iter().enumerate() {
+        if let Some(j) = seen[v] {
+            let mean = (walk_cost[k][v] - walk_cost[j][v]) / (k - j) as f64;
+            let cycle = walk[j..k].iter().map(|&v| g.from_index(v)).collect();
+            return Some((mean, cycle));
+        }
+        seen[v] = Some(k);
+    }
+    unreachable!("min_mean_cycle: a walk of |V| edges must repeat a node")
+}
+
 /// Renamed to `kosaraju_scc`.
 #[deprecated(note = "renamed to kosaraju_scc")]
 pub fn scc<G>(g: G) -> Vec<Vec<G::NodeId>>
@@ -480,6 +1170,82 @@ where
     sccs
 }
 
+/// \[Generic\] Label every node with the id of its strongly connected
+/// component.
+///
+/// Return a map from each node to its component id. The ids are
+/// `0..tarjan_scc(g).len()`, numbered in topological order of the
+/// condensation: if a node can reach a node of another component, its own
+/// component has the smaller id.
+///
+/// For an undirected graph, the components are the connected components.
+///
+/// Computes in **O(|V| + |E|)** time, with Tarjan's algorithm.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::scc_membership;
+///
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 0), (1, 2)]);
+/// let ids = scc_membership(&g);
+/// assert_eq!(ids[&0.into()], ids[&1.into()]);
+/// assert!(ids[&1.into()] < ids[&2.into()]);
+/// ```
+pub fn scc_membership<G>(g: G) -> HashMap<G::NodeId, usize>
+where
+    G: IntoNodeIdentifiers + IntoNeighbors + NodeIndexable,
+    G::NodeId: Eq + Hash,
+{
+    let sccs = tarjan_scc(g);
+    let count = sccs.len();
+    sccs.into_iter()
+        .enumerate()
+        // the sccs come in reverse topological order
+        .flat_map(|(i, scc)| scc.into_iter().map(move |node| (node, count - 1 - i)))
+        .collect()
+}
+
+/// \[Generic\] Compute the *strong bridges* of a directed graph: the edges
+/// whose removal increases the number of strongly connected components.
+///
+/// Return the endpoints `(source, target)` of every strong bridge, in the
+/// order of `edge_references`. An edge that has a parallel edge is never a
+/// strong bridge.
+///
+/// This is the straightforward algorithm: it removes each edge in turn and
+/// recomputes the strongly connected components with `tarjan_scc`, so it
+/// takes **O(|E| (|V| + |E|))** time. It is meant for small graphs and as a
+/// correctness baseline for faster implementations.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::strong_bridges;
+///
+/// // a cycle 0 -> 1 -> 2 -> 0, with the chord 0 -> 2
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 0), (0, 2)]);
+/// assert_eq!(
+///     strong_bridges(&g),
+///     vec![(0.into(), 1.into()), (1.into(), 2.into()), (2.into(), 0.into())]
+/// );
+/// ```
+pub fn strong_bridges<G>(g: G) -> Vec<(G::NodeId, G::NodeId)>
+where
+    G: IntoEdges + IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+    G::EdgeId: PartialEq,
+{
+    let components = tarjan_scc(g).len();
+    g.edge_references()
+        .filter(|removed| {
+            let removed = removed.id();
+            let without = EdgeFiltered::from_fn(g, |edge: G::EdgeRef| edge.id() != removed);
+            tarjan_scc(&without).len() > components
+        })
+        .map(|edge| (edge.source(), edge.target()))
+        .collect()
+}
+
 /// [Graph] Condense every strongly connected component into a single node and return the result.
 ///
 /// If `make_acyclic` is true, self-loops and multi edges are ignored, guaranteeing that
@@ -599,6 +1365,2378 @@ where
     condensed
 }
 
+/// [Graph] Return a graph with the fewest edges that has the same
+/// reachability relation as `g`, which may have cycles.
+///
+/// The strongly connected components are condensed into a DAG, whose
+/// transitive reduction is computed with
+/// [`tred::dag_transitive_reduction_closure`][1]. Each component is then
+/// expanded again into a simple cycle through its nodes, in index order,
+/// and each edge of the reduced DAG becomes an edge between the first
+/// nodes of the two components. A single node component keeps a self loop
+/// if it had one.
+///
+/// The result has the nodes of `g`, with the same indices and weights. Its
+/// edges are not necessarily edges of `g`: within a component, the cycle
+/// may use edges that `g` does not have.
+///
+/// [1]: tred/fn.dag_transitive_reduction_closure.html
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::{has_path_connecting, transitive_reduction_general};
+///
+/// // a cycle 0 -> 1 -> 0, both reaching 2 directly
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 0), (0, 2), (1, 2)]);
+/// let reduced = transitive_reduction_general(&g);
+/// assert_eq!(reduced.edge_count(), 3);
+/// assert!(has_path_connecting(&reduced, 1.into(), 2.into(), None));
+/// ```
+pub fn transitive_reduction_general<N, E, Ix>(g: &DiGraph<N, E, Ix>) -> DiGraph<N, (), Ix>
+where
+    N: Clone,
+    Ix: IndexType,
+{
+    // tarjan_scc returns the components in reverse topological order
+    let mut sccs = tarjan_scc(g);
+    sccs.reverse();
+    let mut component = vec![0; g.node_count()];
+    for (i, nodes) in sccs.iter_mut().enumerate() {
+        nodes.sort();
+        for nx in nodes.iter() {
+            component[nx.index()] = i;
+        }
+    }
+    let mut successors = vec![Vec::new(); sccs.len()];
+    let mut self_loop = vec![false; sccs.len()];
+    for edge in g.raw_edges() {
+        let (a, b) = (
+            component[edge.source().index()],
+            component[edge.target().index()],
+        );
+        if a != b {
+            successors[a].push(b);
+        } else if edge.source() == edge.target() {
+            self_loop[a] = true;
+        }
+    }
+    // the node indices of the condensed DAG are a topological order, and
+    // its neighbor lists are sorted so that they are too
+    let mut dag = List::<(), usize>::with_capacity(sccs.len());
+    for _ in 0..sccs.len() {
+        dag.add_node();
+    }
+    for (a, succ) in successors.iter_mut().enumerate() {
+        succ.sort_unstable();
+        succ.dedup();
+        for &b in succ.iter() {
+            dag.add_edge(a, b, ());
+        }
+    }
+    let (reduction, _) = tred::dag_transitive_reduction_closure(&dag);
+
+    let mut result = Graph::with_capacity(g.node_count(), g.node_count() + reduction.edge_count());
+    for node in g.raw_nodes() {
+        result.add_node(node.weight.clone());
+    }
+    for (i, nodes) in sccs.iter().enumerate() {
+        if nodes.len() > 1 {
+            for (j, &a) in nodes.iter().enumerate() {
+                result.add_edge(a, nodes[(j + 1) % nodes.len()], ());
+            }
+        } else if self_loop[i] {
+            result.add_edge(nodes[0], nodes[0], ());
+        }
+    }
+    for a in reduction.node_indices() {
+        for b in reduction.neighbors(a) {
+            result.add_edge(sccs[a][0], sccs[b][0], ());
+        }
+    }
+    result
+}
+
+/// \[Generic\] Find the critical chain of `g`: the longest path through its
+/// strongly connected components, each of which counts as a single step.
+///
+/// The graph is condensed into the DAG of its strongly connected
+/// components. A component costs the largest `edge_cost` of the edges inside
+/// it, or `K::default()` (zero) if it has none, and an edge between two
+/// components costs its `edge_cost`. The length of a chain of components is
+/// the sum of the costs of its components and of the edges joining them,
+/// taking the most costly edge between consecutive components. Edge costs
+/// must be non-negative.
+///
+/// Return the length of a longest chain and its components, in the order of
+/// the chain, or `K::default()` and no components for an empty graph. The
+/// nodes of every component are in the order `tarjan_scc` returns them.
+///
+/// Computes in **O(|V| + |E|)** time.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::critical_chain;
+///
+/// // 0 -> (1 <-> 2) -> 3, with a direct but cheaper 0 -> 3
+/// let g = Graph::<(), u32>::from_edges(&[(0, 1, 1), (1, 2, 4), (2, 1, 2), (2, 3, 1), (0, 3, 5)]);
+/// let (length, chain) = critical_chain(&g, |e| *e.weight());
+/// assert_eq!(length, 6);
+/// assert_eq!(chain.len(), 3);
+/// assert_eq!(chain[1].len(), 2);
+/// ```
+pub fn critical_chain<G, F, K>(g: G, mut edge_cost: F) -> (K, Vec<Vec<G::NodeId>>)
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + IntoNeighbors + NodeIndexable,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    // tarjan_scc returns the components in reverse topological order
+    let mut sccs = tarjan_scc(g);
+    sccs.reverse();
+    let mut component = vec![0; g.node_bound()];
+    for (i, nodes) in sccs.iter().enumerate() {
+        for &a in nodes {
+            component[g.to_index(a)] = i;
+        }
+    }
+    let mut cost = vec![K::default(); sccs.len()];
+    let mut successors = vec![Vec::new(); sccs.len()];
+    for edge in g.edge_references() {
+        let a = component[g.to_index(edge.source())];
+        let b = component[g.to_index(edge.target())];
+        let k = edge_cost(edge);
+        if a == b {
+            if k > cost[a] {
+                cost[a] = k;
+            }
+        } else {
+            successors[a].push((b, k));
+        }
+    }
+
+    // the longest chain ending at every component, and its previous component
+    let mut longest: Vec<_> = cost.iter().map(|&k| (k, None)).collect();
+    for a in 0..sccs.len() {
+        let length = longest[a].0;
+        for &(b, k) in &successors[a] {
+            let through = length + k + cost[b];
+            if through > longest[b].0 {
+                longest[b] = (through, Some(a));
+            }
+        }
+    }
+    if sccs.is_empty() {
+        return (K::default(), Vec::new());
+    }
+    let mut end = 0;
+    for a in 1..sccs.len() {
+        if longest[a].0 > longest[end].0 {
+            end = a;
+        }
+    }
+    let length = longest[end].0;
+    let mut chain = vec![end];
+    while let Some(prev) = longest[end].1 {
+        chain.push(prev);
+        end = prev;
+    }
+    let mut sccs: Vec<_> = sccs.into_iter().map(Some).collect();
+    let chain = chain
+        .into_iter()
+        .rev()
+        .map(|a| sccs[a].take().unwrap())
+        .collect();
+    (length, chain)
+}
+
+/// \[Generic\] Compute the shortest path closure of `g`: a graph with an
+/// edge `a → b`, weighted by the length of a shortest path from `a` to `b`,
+/// for every node `b` reachable from another node `a`.
+///
+/// The length of a path is the sum of `edge_cost` over its edges, which must
+/// be non-negative. The node weights of the closure are the nodes of `g`, in
+/// the order of `node_identifiers`, and the edges of every node are sorted by
+/// their targets in that order. A node has no edge to itself.
+///
+/// This runs [`dijkstra`](fn.dijkstra.html) from every node, in
+/// **O(|V| (|E| + |V|) log |V|)** time, which is faster than the **O(|V|³)**
+/// of Floyd-Warshall unless the graph is dense. The closure can have up to
+/// **|V|²** edges.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::shortest_path_closure;
+///
+/// let g = Graph::<(), u32>::from_edges(&[(0, 1, 2), (1, 2, 3)]);
+/// let closure = shortest_path_closure(&g, |e| *e.weight());
+/// let edge = closure.find_edge(0.into(), 2.into()).unwrap();
+/// assert_eq!(closure[edge], 5);
+/// assert_eq!(closure.edge_count(), 3);
+/// ```
+pub fn shortest_path_closure<G, F, K>(g: G, mut edge_cost: F) -> DiGraph<G::NodeId, K>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable + Visitable,
+    G::NodeId: Eq + Hash,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    let nodes: Vec<_> = g.node_identifiers().collect();
+    let mut compact = vec![0; g.node_bound()];
+    for (i, &a) in nodes.iter().enumerate() {
+        compact[g.to_index(a)] = i;
+    }
+    let mut closure = Graph::with_capacity(nodes.len(), 0);
+    for &a in &nodes {
+        closure.add_node(a);
+    }
+    for (i, &a) in nodes.iter().enumerate() {
+        let mut distances: Vec<_> = dijkstra(g, a, None, &mut edge_cost)
+            .into_iter()
+            .map(|(b, d)| (compact[g.to_index(b)], d))
+            .filter(|&(j, _)| j != i)
+            .collect();
+        distances.sort_by_key(|&(j, _)| j);
+        for (j, d) in distances {
+            closure.add_edge(NodeIndex::new(i), NodeIndex::new(j), d);
+        }
+    }
+    closure
+}
+
+/// \[Generic\] Compute the `k`-th power of `g`: a graph with an edge
+/// `a → b` whenever `b` can be reached from `a` in at least one and at most
+/// `k` steps.
+///
+/// Steps follow the edges in the direction of `neighbors`, so for an
+/// undirected graph every edge of the power appears in both directions. A
+/// node gets a self loop if a walk of at most `k` steps leads back to it.
+/// The node weights of the power are the nodes of `g`, in the order of
+/// `node_identifiers`, and the edges of every node are sorted by their
+/// targets in that order. With `k = 1` this is `g` with parallel edges
+/// merged, and with `k = 0` it has no edges.
+///
+/// Computes in **O(|V| (|V| + |E|))** time in the worst case, with a
+/// breadth-first search of depth `k` from every node; the power can have up
+/// to **|V|²** edges.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::graph_power;
+///
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3)]);
+/// let square = graph_power(&g, 2);
+/// assert_eq!(square.edge_count(), 5);
+/// assert!(square.contains_edge(0.into(), 2.into()));
+/// assert!(!square.contains_edge(0.into(), 3.into()));
+/// ```
+pub fn graph_power<G>(g: G, k: usize) -> DiGraph<G::NodeId, ()>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable + Visitable,
+{
+    let nodes: Vec<_> = g.node_identifiers().collect();
+    let mut compact = vec![0; g.node_bound()];
+    for (i, &a) in nodes.iter().enumerate() {
+        compact[g.to_index(a)] = i;
+    }
+    let mut power = Graph::with_capacity(nodes.len(), 0);
+    for &a in &nodes {
+        power.add_node(a);
+    }
+    let mut discovered = g.visit_map();
+    for (i, &a) in nodes.iter().enumerate() {
+        g.reset_map(&mut discovered);
+        discovered.visit(a);
+        let mut targets = Vec::new();
+        let mut self_loop = false;
+        let mut frontier = vec![a];
+        for _ in 0..k {
+            let mut next = Vec::new();
+            for &node in &frontier {
+                for succ in g.neighbors(node) {
+                    if succ == a {
+                        self_loop = true;
+                    } else if discovered.visit(succ) {
+                        next.push(succ);
+                        targets.push(compact[g.to_index(succ)]);
+                    }
+                }
+            }
+            if next.is_empty() {
+                break;
+            }
+            frontier = next;
+        }
+        if self_loop {
+            targets.push(i);
+        }
+        targets.sort_unstable();
+        for j in targets {
+            power.add_edge(NodeIndex::new(i), NodeIndex::new(j), ());
+        }
+    }
+    power
+}
+
+/// \[Generic\] Compute the metric closure of `terminals`: a complete graph
+/// on the terminals, with an edge `a → b` weighted by the length of a
+/// shortest path from `a` to `b` in `g`.
+///
+/// The length of a path is the sum of `edge_cost` over its edges, which must
+/// be non-negative. Node `i` of the closure has the weight `terminals[i]`,
+/// and its edges are sorted by their targets in the same order. Pairs of
+/// terminals with no path between them have no edge, and neither does a
+/// terminal and itself.
+///
+/// This runs [`dijkstra`](fn.dijkstra.html) from every terminal, in
+/// **O(|T| (|E| + |V|) log |V|)** time for |T| terminals.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::metric_closure;
+///
+/// let g = Graph::<(), u32>::from_edges(&[(0, 1, 2), (1, 2, 3), (2, 3, 1)]);
+/// let closure = metric_closure(&g, &[3.into(), 0.into()], |e| *e.weight());
+/// assert_eq!(closure.node_count(), 2);
+/// // 0 reaches 3, but not the other way around
+/// assert_eq!(closure.edge_count(), 1);
+/// let edge = closure.find_edge(1.into(), 0.into()).unwrap();
+/// assert_eq!(closure[edge], 6);
+/// ```
+pub fn metric_closure<G, F, K>(
+    g: G,
+    terminals: &[G::NodeId],
+    mut edge_cost: F,
+) -> DiGraph<G::NodeId, K>
+where
+    G: IntoEdges + Visitable,
+    G::NodeId: Eq + Hash,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    let mut closure = Graph::with_capacity(terminals.len(), 0);
+    for &a in terminals {
+        closure.add_node(a);
+    }
+    for (i, &a) in terminals.iter().enumerate() {
+        let distances = dijkstra(g, a, None, &mut edge_cost);
+        for (j, b) in terminals.iter().enumerate() {
+            if i != j {
+                if let Some(&d) = distances.get(b) {
+                    closure.add_edge(NodeIndex::new(i), NodeIndex::new(j), d);
+                }
+            }
+        }
+    }
+    closure
+}
+
+/// \[Generic\] Compute an approximate minimum Steiner tree: a tree of edges
+/// of `g` that connects all of the `terminals`, and that may pass through
+/// other nodes.
+///
+/// This is the classic 2-approximation: a minimum spanning tree of the
+/// metric closure of the terminals (see `metric_closure`) is expanded into
+/// the shortest paths it stands for, a minimum spanning tree of these paths
+/// is taken, and non-terminal leaves are pruned. The total cost of the tree
+/// is at most `2 (1 - 1/|T|)` times that of a minimum Steiner tree, for |T|
+/// terminals. Edge costs, given by `edge_cost`, must be non-negative.
+///
+/// The guarantee is for undirected graphs. Paths follow the edges in the
+/// direction of `edges`, so for a directed graph every terminal is only
+/// connected from terminals listed before it, when that is possible.
+/// Terminals that can not be connected end up in separate trees.
+///
+/// The node weights of the result are nodes of `g`: the terminals first, in
+/// the order of `terminals`, then the other nodes of the tree. Every edge
+/// keeps its weight, and points in the direction a shortest path took it,
+/// from the `source` to the `target` of the reference given by `edges`. For
+/// a directed graph this is its direction in `g`; for an undirected graph it
+/// points away from the terminal the path started at, whichever way the
+/// edge was added.
+///
+/// Computes in **O(|T| (|E| + |V|) log |V| + |T|² log |T|)** time.
+///
+/// # Example
+/// ```rust
+/// use petgraph::graph::UnGraph;
+/// use petgraph::algo::steiner_tree;
+///
+/// // the terminals 0, 1 and 2 are joined through 3 more cheaply than directly
+/// let g = UnGraph::<(), u32>::from_edges(&[
+///     (0, 1, 3), (1, 2, 3), (0, 3, 1), (1, 3, 1), (2, 3, 1)
+/// ]);
+/// let tree = steiner_tree(&g, &[0.into(), 1.into(), 2.into()], |e| *e.weight());
+/// assert_eq!(tree.node_count(), 4);
+/// assert!(tree.edge_count() == 3 && tree.raw_edges().iter().all(|e| e.weight == 1));
+/// ```
+pub fn steiner_tree<G, F, K>(
+    g: G,
+    terminals: &[G::NodeId],
+    mut edge_cost: F,
+) -> DiGraph<G::NodeId, G::EdgeWeight>
+where
+    G: IntoEdges + Visitable,
+    G::NodeId: Eq + Hash,
+    G::EdgeId: Eq + Hash,
+    G::EdgeWeight: Clone,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    let trees: Vec<_> = terminals
+        .iter()
+        .map(|&t| shortest_path_tree(g, t, &mut edge_cost))
+        .collect();
+    // a minimum spanning tree of the metric closure, by Kruskal's algorithm
+    let mut closure_edges = Vec::new();
+    for (i, (distances, _)) in trees.iter().enumerate() {
+        for (j, b) in terminals.iter().enumerate().skip(i + 1) {
+            if let Some(&d) = distances.get(b) {
+                closure_edges.push((d, i, j));
+            }
+        }
+    }
+    closure_edges.sort_by(|x, y| x.0.partial_cmp(&y.0).unwrap_or(Ordering::Equal));
+    let mut sets = UnionFind::new(terminals.len());
+    let mut seen = HashSet::new();
+    let mut path_edges = Vec::new();
+    for (_, i, j) in closure_edges {
+        if sets.union(i, j) {
+            let predecessor = &trees[i].1;
+            let mut b = terminals[j];
+            while let Some(&edge) = predecessor.get(&b) {
+                if seen.insert(edge.id()) {
+                    path_edges.push(edge);
+                }
+                b = edge.source();
+            }
+        }
+    }
+
+    // a minimum spanning tree of the union of the paths
+    let mut nodes = Vec::new();
+    let mut compact = HashMap::new();
+    {
+        let mut add = |a| {
+            compact.entry(a).or_insert_with(|| {
+                nodes.push(a);
+                nodes.len() - 1
+            });
+        };
+        for &a in terminals {
+            add(a);
+        }
+        for edge in &path_edges {
+            add(edge.source());
+            add(edge.target());
+        }
+    }
+    let mut path_edges: Vec<_> = path_edges
+        .into_iter()
+        .map(|edge| (edge_cost(edge), edge))
+        .collect();
+    path_edges.sort_by(|x, y| x.0.partial_cmp(&y.0).unwrap_or(Ordering::Equal));
+    let mut sets = UnionFind::new(nodes.len());
+    let mut tree_edges: Vec<_> = path_edges
+        .into_iter()
+        .map(|(_, edge)| (compact[&edge.source()], compact[&edge.target()], edge))
+        .filter(|&(a, b, _)| sets.union(a, b))
+        .collect();
+
+    // prune the leaves that are not terminals, until there are none
+    let mut adjacent = vec![Vec::new(); nodes.len()];
+    for &(a, b, _) in &tree_edges {
+        adjacent[a].push(b);
+        adjacent[b].push(a);
+    }
+    let mut degree: Vec<_> = adjacent.iter().map(Vec::len).collect();
+    // the distinct terminals are the first nodes
+    let terminal_count = terminals.iter().collect::<HashSet<_>>().len();
+    let is_terminal = |i: usize| i < terminal_count;
+    let mut keep = vec![true; nodes.len()];
+    let mut leaves: Vec<_> = (0..nodes.len())
+        .filter(|&i| !is_terminal(i) && degree[i] <= 1)
+        .collect();
+    while let Some(leaf) = leaves.pop() {
+        keep[leaf] = false;
+        for &other in &adjacent[leaf] {
+            if keep[other] {
+                degree[other] -= 1;
+                if degree[other] == 1 && !is_terminal(other) {
+                    leaves.push(other);
+                }
+            }
+        }
+    }
+    tree_edges.retain(|&(a, b, _)| keep[a] && keep[b]);
+
+    let mut tree = Graph::with_capacity(nodes.len(), tree_edges.len());
+    let mut index = vec![NodeIndex::end(); nodes.len()];
+    for (i, &a) in nodes.iter().enumerate() {
+        if keep[i] {
+            index[i] = tree.add_node(a);
+        }
+    }
+    for (a, b, edge) in tree_edges {
+        tree.add_edge(index[a], index[b], edge.weight().clone());
+    }
+    tree
+}
+
+/// The distances from a node, and the edge every other node is reached
+/// through.
+type ShortestPathTree<G, K> = (
+    HashMap<<G as GraphBase>::NodeId, K>,
+    HashMap<<G as GraphBase>::NodeId, <G as IntoEdgeReferences>::EdgeRef>,
+);
+
+/// Run Dijkstra's algorithm from `start`, and return the distances of the
+/// reachable nodes together with the edge each of them is reached through.
+fn shortest_path_tree<G, F, K>(g: G, start: G::NodeId, edge_cost: &mut F) -> ShortestPathTree<G, K>
+where
+    G: IntoEdges + Visitable,
+    G::NodeId: Eq + Hash,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    let mut visited = g.visit_map();
+    let mut scores = HashMap::new();
+    let mut predecessor = HashMap::new();
+    let mut visit_next = BinaryHeap::new();
+    scores.insert(start, K::default());
+    visit_next.push(MinScored(K::default(), start));
+    while let Some(MinScored(score, node)) = visit_next.pop() {
+        if !visited.visit(node) {
+            continue;
+        }
+        for edge in g.edges(node) {
+            let next = edge.target();
+            if visited.is_visited(&next) {
+                continue;
+            }
+            let next_score = score + edge_cost(edge);
+            let better = match scores.get(&next) {
+                Some(&old) => next_score < old,
+                None => true,
+            };
+            if better {
+                scores.insert(next, next_score);
+                predecessor.insert(next, edge);
+                visit_next.push(MinScored(next_score, next));
+            }
+        }
+    }
+    (scores, predecessor)
+}
+
+/// [Graph] Return a new graph with `start`, every node reachable from `start`,
+/// and all the edges among those nodes.
+///
+/// Nodes and edges keep their relative order, but their indices are
+/// compacted like with `filter_map`. For an undirected graph, this is the
+/// connected component of `start`.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::reachable_subgraph;
+///
+/// let mut g = Graph::<&str, u32>::new();
+/// let a = g.add_node("a");
+/// let b = g.add_node("b");
+/// let c = g.add_node("c");
+/// g.extend_with_edges(&[(a, b, 1), (b, a, 2), (c, a, 3)]);
+///
+/// let sub = reachable_subgraph(&g, a);
+/// assert_eq!(sub.node_count(), 2);
+/// assert_eq!(sub.edge_count(), 2);
+/// ```
+pub fn reachable_subgraph<N, E, Ty, Ix>(
+    g: &Graph<N, E, Ty, Ix>,
+    start: NodeIndex<Ix>,
+) -> Graph<N, E, Ty, Ix>
+where
+    N: Clone,
+    E: Clone,
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    let mut dfs = Dfs::new(g, start);
+    while dfs.next(g).is_some() {}
+    let reachable = dfs.discovered;
+    g.filter_map(
+        |nx, weight| {
+            if reachable.is_visited(&nx) {
+                Some(weight.clone())
+            } else {
+                None
+            }
+        },
+        |_, weight| Some(weight.clone()),
+    )
+}
+
+/// [Graph] Return the part of `g` reachable from `start`, with every edge
+/// reversed.
+///
+/// This is `reachable_subgraph` followed by `Graph::reverse`, so only the
+/// reachable region is copied and reversed. In the result, `start` can be
+/// reached from every other node.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::reversed_reachable_from;
+///
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (3, 0)]);
+/// let rev = reversed_reachable_from(&g, 0.into());
+/// assert_eq!(rev.node_count(), 3);
+/// assert!(rev.contains_edge(2.into(), 1.into()));
+/// ```
+pub fn reversed_reachable_from<N, E, Ix>(
+    g: &DiGraph<N, E, Ix>,
+    start: NodeIndex<Ix>,
+) -> DiGraph<N, E, Ix>
+where
+    N: Clone,
+    E: Clone,
+    Ix: IndexType,
+{
+    let mut sub = reachable_subgraph(g, start);
+    sub.reverse();
+    sub
+}
+
+/// [Graph] Return the part of `g` within `radius` hops of any of the `seeds`,
+/// where a hop must follow an edge whose cost is at least `min_weight`.
+///
+/// Edges with a lower cost, as given by `edge_cost`, are too weak to extend
+/// the region; they are still part of the result if both of their endpoints
+/// are reached by other means. The result is the subgraph induced by the
+/// region: its nodes and all the edges among them. Hops follow the direction
+/// of the edges in a directed graph.
+///
+/// Nodes and edges keep their relative order, but their indices are
+/// compacted like with `filter_map`.
+///
+/// Computes in **O(|V| + |E|)** time, with a breadth-first search started
+/// from all seeds at once.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::seeded_subgraph;
+///
+/// let g = Graph::<(), f32>::from_edges(&[(0, 1, 0.9), (1, 2, 0.8), (0, 3, 0.1)]);
+/// let region = seeded_subgraph(&g, vec![0.into()], 1, 0.5, |e| *e.weight());
+/// assert_eq!(region.node_count(), 2);
+/// assert_eq!(region.edge_count(), 1);
+/// ```
+pub fn seeded_subgraph<N, E, Ty, Ix, I, F, K>(
+    g: &Graph<N, E, Ty, Ix>,
+    seeds: I,
+    radius: usize,
+    min_weight: K,
+    mut edge_cost: F,
+) -> Graph<N, E, Ty, Ix>
+where
+    N: Clone,
+    E: Clone,
+    Ty: EdgeType,
+    Ix: IndexType,
+    I: IntoIterator<Item = NodeIndex<Ix>>,
+    F: FnMut(EdgeReference<E, Ix>) -> K,
+    K: PartialOrd,
+{
+    let mut region = g.visit_map();
+    let mut frontier: Vec<_> = seeds
+        .into_iter()
+        .filter(|&seed| region.visit(seed))
+        .collect();
+    for _ in 0..radius {
+        let mut next = Vec::new();
+        for &node in &frontier {
+            for edge in g.edges(node) {
+                if edge_cost(edge) < min_weight {
+                    continue;
+                }
+                let other = if edge.source() == node {
+                    edge.target()
+                } else {
+                    edge.source()
+                };
+                if region.visit(other) {
+                    next.push(other);
+                }
+            }
+        }
+        if next.is_empty() {
+            break;
+        }
+        frontier = next;
+    }
+    g.filter_map(
+        |nx, weight| {
+            if region.is_visited(&nx) {
+                Some(weight.clone())
+            } else {
+                None
+            }
+        },
+        |_, weight| Some(weight.clone()),
+    )
+}
+
+/// The traversal used by [`spanning_tree_from`](fn.spanning_tree_from.html).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TreeStrategy {
+    /// Breadth-first: every node hangs below a parent on a shortest path
+    /// from the root, counting edges.
+    Bfs,
+    /// Depth-first: every node hangs below the node from which a
+    /// depth-first search first reached it.
+    Dfs,
+}
+
+/// [Graph] Return a spanning tree of the part of `g` reachable from `root`,
+/// built by a breadth-first or depth-first search, as selected by
+/// `strategy`.
+///
+/// The tree has the nodes reachable from `root`, and an edge from the
+/// parent of each node other than `root` to the node itself, so it has one
+/// edge less than nodes. Nodes keep their relative order, but their indices
+/// are compacted like with `filter_map`. Edges are added in the order the
+/// search discovered their nodes.
+///
+/// Computes in **O(|V| + |E|)** time.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::{spanning_tree_from, TreeStrategy};
+///
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (0, 2), (1, 2), (3, 0)]);
+/// let tree = spanning_tree_from(&g, 0.into(), TreeStrategy::Bfs);
+/// assert_eq!(tree.node_count(), 3);
+/// assert!(tree.contains_edge(0.into(), 2.into()));
+/// assert!(!tree.contains_edge(1.into(), 2.into()));
+/// ```
+pub fn spanning_tree_from<N, E, Ty, Ix>(
+    g: &Graph<N, E, Ty, Ix>,
+    root: NodeIndex<Ix>,
+    strategy: TreeStrategy,
+) -> DiGraph<N, (), Ix>
+where
+    N: Clone,
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    let mut discovered = g.visit_map();
+    // tree edges (parent, child), in order of discovery
+    let mut tree_edges = Vec::new();
+    discovered.visit(root);
+    match strategy {
+        TreeStrategy::Bfs => {
+            let mut queue = VecDeque::new();
+            queue.push_back(root);
+            while let Some(node) = queue.pop_front() {
+                for succ in g.neighbors(node) {
+                    if discovered.visit(succ) {
+                        tree_edges.push((node, succ));
+                        queue.push_back(succ);
+                    }
+                }
+            }
+        }
+        TreeStrategy::Dfs => {
+            let mut stack = vec![(root, g.neighbors(root))];
+            while let Some(&mut (node, ref mut neighbors)) = stack.last_mut() {
+                match neighbors.find(|&succ| discovered.visit(succ)) {
+                    Some(succ) => {
+                        tree_edges.push((node, succ));
+                        stack.push((succ, g.neighbors(succ)));
+                    }
+                    None => {
+                        stack.pop();
+                    }
+                }
+            }
+        }
+    }
+    let mut tree = Graph::with_capacity(tree_edges.len() + 1, tree_edges.len());
+    let mut node_map = vec![NodeIndex::end(); g.node_count()];
+    for nx in g.node_indices() {
+        if discovered.is_visited(&nx) {
+            node_map[nx.index()] = tree.add_node(g[nx].clone());
+        }
+    }
+    for (parent, child) in tree_edges {
+        tree.add_edge(node_map[parent.index()], node_map[child.index()], ());
+    }
+    tree
+}
+
+/// [Graph] Return a uniformly random spanning tree of the component of `g`
+/// that contains its first node, using Wilson's algorithm.
+///
+/// Edge directions are ignored: the tree spans the nodes connected to the
+/// node with index `0` in the undirected view of `g`, and is rooted there.
+/// Every edge of the tree points from a parent to its child, away from the
+/// root, and carries a clone of the weight of the edge of `g` it stems
+/// from. Self loops are ignored; with parallel edges, a tree is picked more
+/// often the more ways there are to choose its edges. Nodes keep their
+/// relative order, but their indices are compacted like with `filter_map`.
+/// Return an empty graph if `g` has no nodes.
+///
+/// `rng` is called to draw numbers uniformly distributed in `[0, 1)`, like
+/// for `sample_edge`, once for every step of the loop-erased random walks.
+///
+/// Computes in expected time proportional to the mean hitting time of the
+/// random walk on `g`, which is **O(|V| |E|)** in the worst case.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::random_spanning_tree;
+///
+/// let g = Graph::<(), u32>::from_edges(&[(0, 1, 1), (1, 2, 2), (2, 0, 3), (3, 4, 4)]);
+/// // a small linear congruential generator
+/// let mut state = 7u64;
+/// let mut rng = || {
+///     state = state * 48271 % 0x7fff_ffff;
+///     state as f64 / 0x7fff_ffff as f64
+/// };
+/// let tree = random_spanning_tree(&g, &mut rng);
+/// assert_eq!(tree.node_count(), 3);
+/// assert_eq!(tree.edge_count(), 2);
+/// ```
+pub fn random_spanning_tree<N, E, Ty, Ix, R>(
+    g: &Graph<N, E, Ty, Ix>,
+    rng: &mut R,
+) -> DiGraph<N, E, Ix>
+where
+    N: Clone,
+    E: Clone,
+    Ty: EdgeType,
+    Ix: IndexType,
+    R: FnMut() -> f64,
+{
+    let mut tree = Graph::default();
+    if g.node_count() == 0 {
+        return tree;
+    }
+    let root = NodeIndex::new(0);
+    // the incident edges of every node, in the undirected view
+    let mut incident = vec![Vec::new(); g.node_count()];
+    for edge in g.edge_references() {
+        let (a, b) = (edge.source(), edge.target());
+        if a != b {
+            incident[a.index()].push((edge.id(), b));
+            incident[b.index()].push((edge.id(), a));
+        }
+    }
+    let mut component = vec![root];
+    let mut discovered = g.visit_map();
+    discovered.visit(root);
+    let mut i = 0;
+    while i < component.len() {
+        for &(_, succ) in &incident[component[i].index()] {
+            if discovered.visit(succ) {
+                component.push(succ);
+            }
+        }
+        i += 1;
+    }
+
+    let mut in_tree = vec![false; g.node_count()];
+    in_tree[root.index()] = true;
+    // the step taken when the walk last left each node; revisiting a node
+    // overwrites it, which erases the loop
+    let mut next = vec![None; g.node_count()];
+    for &start in &component {
+        let mut node = start;
+        while !in_tree[node.index()] {
+            let edges = &incident[node.index()];
+            let step = edges[random_index(rng, edges.len())];
+            next[node.index()] = Some(step);
+            node = step.1;
+        }
+        let mut node = start;
+        while !in_tree[node.index()] {
+            in_tree[node.index()] = true;
+            node = next[node.index()].unwrap().1;
+        }
+    }
+
+    let mut node_map = vec![NodeIndex::end(); g.node_count()];
+    for nx in g.node_indices() {
+        if discovered.is_visited(&nx) {
+            node_map[nx.index()] = tree.add_node(g[nx].clone());
+        }
+    }
+    for &child in &component[1..] {
+        let (edge, parent) = next[child.index()].unwrap();
+        tree.add_edge(
+            node_map[parent.index()],
+            node_map[child.index()],
+            g[edge].clone(),
+        );
+    }
+    tree
+}
+
+/// \[Generic\] Compute a Gomory-Hu tree of `g`: a tree on its nodes such
+/// that, for any two nodes, the smallest weight on the tree path between
+/// them is the value of a minimum cut separating them in `g`.
+///
+/// Edge directions are ignored, and the capacity of every edge is given by
+/// `capacity`, which must be non-negative; parallel edges add up and self
+/// loops are ignored. The node weights of the tree are the nodes of `g`, in
+/// the order of `node_identifiers`, and the tree has an edge from every node
+/// but the first to its parent, weighted by the cut between them. Nodes in
+/// different connected components are joined by edges of weight
+/// `K::default()`.
+///
+/// This uses Gusfield's algorithm, with one maximum flow computation
+/// (Edmonds-Karp) for each of the |V| - 1 tree edges, in
+/// **O(|V|² |E|²)** time in total.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::gomory_hu_tree;
+/// use petgraph::visit::EdgeRef;
+///
+/// // a triangle 0, 1, 2 with a pendant node 3
+/// let g = Graph::<(), u32>::from_edges(&[(0, 1, 3), (1, 2, 2), (2, 0, 4), (2, 3, 1)]);
+/// let tree = gomory_hu_tree(&g, |e| *e.weight());
+/// assert_eq!(tree.edge_count(), 3);
+/// let mut weights: Vec<_> = tree.raw_edges().iter().map(|e| e.weight).collect();
+/// weights.sort();
+/// // cutting off 3, then 1, then separating 0 from 2
+/// assert_eq!(weights, vec![1, 5, 6]);
+/// ```
+pub fn gomory_hu_tree<G, F, K>(g: G, mut capacity: F) -> DiGraph<G::NodeId, K>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy + Sub<K, Output = K>,
+{
+    let nodes: Vec<_> = g.node_identifiers().collect();
+    let n = nodes.len();
+    let mut compact = vec![0; g.node_bound()];
+    for (i, &a) in nodes.iter().enumerate() {
+        compact[g.to_index(a)] = i;
+    }
+    // the arcs `2 * k` and `2 * k + 1` are the two directions of edge `k`
+    let mut network = FlowNetwork {
+        arcs: vec![Vec::new(); n],
+        head: Vec::new(),
+        capacity: Vec::new(),
+    };
+    for edge in g.edge_references() {
+        let a = compact[g.to_index(edge.source())];
+        let b = compact[g.to_index(edge.target())];
+        let c = capacity(edge);
+        if a != b {
+            for &(from, to) in &[(a, b), (b, a)] {
+                network.arcs[from].push(network.head.len());
+                network.head.push(to);
+                network.capacity.push(c);
+            }
+        }
+    }
+
+    let mut tree = Graph::with_capacity(n, n.saturating_sub(1));
+    for &a in &nodes {
+        tree.add_node(a);
+    }
+    let mut parent = vec![0; n];
+    for s in 1..n {
+        let t = parent[s];
+        let (value, source_side) = network.min_cut(s, t);
+        for i in s + 1..n {
+            if source_side[i] && parent[i] == t {
+                parent[i] = s;
+            }
+        }
+        tree.add_edge(NodeIndex::new(s), NodeIndex::new(t), value);
+    }
+    tree
+}
+
+/// An undirected flow network, for `gomory_hu_tree`.
+struct FlowNetwork<K> {
+    /// The arcs leaving every node
+    arcs: Vec<Vec<usize>>,
+    /// The target node of every arc
+    head: Vec<usize>,
+    capacity: Vec<K>,
+}
+
+impl<K> FlowNetwork<K>
+where
+    K: Measure + Copy + Sub<K, Output = K>,
+{
+    /// Return the value of a minimum cut between `s` and `t`, and the nodes
+    /// on the side of `s`, with the Edmonds-Karp algorithm.
+    fn min_cut(&self, s: usize, t: usize) -> (K, Vec<bool>) {
+        let zero = K::default();
+        let mut residual = self.capacity.clone();
+        let mut flow = zero;
+        loop {
+            // the arc each node was discovered through
+            let mut pred = vec![None; self.arcs.len()];
+            let mut discovered = vec![false; self.arcs.len()];
+            discovered[s] = true;
+            let mut queue = VecDeque::new();
+            queue.push_back(s);
+            while let Some(a) = queue.pop_front() {
+                for &arc in &self.arcs[a] {
+                    let b = self.head[arc];
+                    if !discovered[b] && residual[arc] > zero {
+                        discovered[b] = true;
+                        pred[b] = Some(arc);
+                        queue.push_back(b);
+                    }
+                }
+            }
+            if !discovered[t] {
+                return (flow, discovered);
+            }
+            let mut bottleneck = None;
+            let mut b = t;
+            while let Some(arc) = pred[b] {
+                bottleneck = match bottleneck {
+                    Some(c) if c <= residual[arc] => Some(c),
+                    _ => Some(residual[arc]),
+                };
+                b = self.head[arc ^ 1];
+            }
+            let bottleneck = bottleneck.unwrap();
+            let mut b = t;
+            while let Some(arc) = pred[b] {
+                residual[arc] = residual[arc] - bottleneck;
+                residual[arc ^ 1] = residual[arc ^ 1] + bottleneck;
+                b = self.head[arc ^ 1];
+            }
+            flow = flow + bottleneck;
+        }
+    }
+}
+
+/// [Graph] Return a canonical form of `g`: a copy of `g` with its nodes
+/// renumbered `0..|V|` in an order derived from the structure of the graph
+/// only, such that isomorphic graphs usually give the same canonical form,
+/// together with the nodes of `g` in that order.
+///
+/// The node weight of canonical node `i` is its label `i`, and the `i`th
+/// node of the returned vector is the node of `g` it stands for, to map
+/// results computed on the canonical form back to `g`. The edges are sorted
+/// by their canonical endpoints, and keep the weights of `g`; undirected
+/// edges point from the smaller to the larger canonical index. Two
+/// canonical forms are thus equal when their edge lists are.
+///
+/// The order is computed by color refinement (the 1-dimensional
+/// Weisfeiler-Leman algorithm): nodes are repeatedly split by the colors of
+/// their successors and predecessors, and when that no longer splits any
+/// class, the first node of the smallest class with several nodes, by
+/// index in `g`, is singled out and the refinement continues.
+///
+/// **Note:** This is a heuristic. The canonical form is always isomorphic to
+/// `g`, so different canonical forms mean the graphs are not isomorphic, and
+/// they are exact for graphs the refinement tells apart fully, like most
+/// trees, and for graphs whose singled out nodes are symmetric to the rest
+/// of their class, like cycles. Some regular graphs, however, may have
+/// several canonical forms. Edge weights do not affect the order.
+///
+/// Computes in **O(|V|² (|V| + |E|) log |V|)** time in the worst case.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::canonical_form;
+/// use petgraph::graph::NodeIndex;
+///
+/// let a = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (1, 3)]);
+/// let b = Graph::<(), ()>::from_edges(&[(3, 0), (0, 2), (0, 1)]);
+///
+/// let edges = |g: &Graph<_, _>| {
+///     g.raw_edges().iter().map(|e| (e.source(), e.target())).collect::<Vec<_>>()
+/// };
+/// let (ca, a_nodes) = canonical_form(&a);
+/// let (cb, b_nodes) = canonical_form(&b);
+/// assert_eq!(edges(&ca), edges(&cb));
+/// // the center of the star gets the same label in both forms
+/// let label = |nodes: &[NodeIndex], v| nodes.iter().position(|u| u.index() == v);
+/// assert_eq!(label(&a_nodes, 1), label(&b_nodes, 0));
+/// ```
+pub fn canonical_form<N, E, Ty, Ix>(
+    g: &Graph<N, E, Ty, Ix>,
+) -> (DiGraph<usize, E, Ix>, Vec<NodeIndex<Ix>>)
+where
+    E: Clone,
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    let n = g.node_count();
+    let mut succ = vec![Vec::new(); n];
+    let mut pred = vec![Vec::new(); n];
+    for edge in g.edge_references() {
+        let (a, b) = (edge.source().index(), edge.target().index());
+        succ[a].push(b);
+        pred[b].push(a);
+        if !g.is_directed() {
+            succ[b].push(a);
+            pred[a].push(b);
+        }
+    }
+
+    let mut colors = vec![0; n];
+    loop {
+        refine_colors(&succ, &pred, &mut colors);
+        // the colors are 0..classes now
+        let mut class_size = vec![0; n];
+        for &c in &colors {
+            class_size[c] += 1;
+        }
+        // of equally small classes, `min_by_key` picks the lowest color
+        let tied = match (0..n)
+            .filter(|&c| class_size[c] > 1)
+            .min_by_key(|&c| class_size[c])
+        {
+            Some(c) => c,
+            None => break,
+        };
+        let single = colors.iter().position(|&c| c == tied).unwrap();
+        for (u, c) in colors.iter_mut().enumerate() {
+            *c = 2 * *c + (*c == tied && u != single) as usize;
+        }
+    }
+
+    let mut order = vec![NodeIndex::end(); n];
+    for (u, &c) in colors.iter().enumerate() {
+        order[c] = NodeIndex::new(u);
+    }
+    let mut edges: Vec<_> = g
+        .edge_references()
+        .map(|edge| {
+            let a = colors[edge.source().index()];
+            let b = colors[edge.target().index()];
+            if g.is_directed() {
+                (a, b, edge.weight())
+            } else {
+                (min(a, b), max(a, b), edge.weight())
+            }
+        })
+        .collect();
+    edges.sort_by_key(|&(a, b, _)| (a, b));
+
+    let mut canonical = Graph::with_capacity(n, edges.len());
+    for i in 0..n {
+        canonical.add_node(i);
+    }
+    for (a, b, weight) in edges {
+        canonical.add_edge(NodeIndex::new(a), NodeIndex::new(b), weight.clone());
+    }
+    (canonical, order)
+}
+
+/// Refine `colors` until the colors of the successors and predecessors of
+/// the nodes no longer split any color class, and renumber them `0..classes`
+/// keeping their relative order.
+fn refine_colors(succ: &[Vec<usize>], pred: &[Vec<usize>], colors: &mut Vec<usize>) {
+    let mut classes = {
+        let mut distinct = colors.clone();
+        distinct.sort_unstable();
+        distinct.dedup();
+        distinct.len()
+    };
+    loop {
+        let neighbor_colors = |adj: &[usize]| {
+            let mut c: Vec<_> = adj.iter().map(|&v| colors[v]).collect();
+            c.sort_unstable();
+            c
+        };
+        let signatures: Vec<_> = (0..colors.len())
+            .map(|u| {
+                (
+                    colors[u],
+                    neighbor_colors(&succ[u]),
+                    neighbor_colors(&pred[u]),
+                )
+            })
+            .collect();
+        let mut distinct: Vec<_> = signatures.iter().collect();
+        distinct.sort();
+        distinct.dedup();
+        let refined = signatures
+            .iter()
+            .map(|s| distinct.binary_search(&s).unwrap())
+            .collect();
+        let stable = distinct.len() == classes;
+        classes = distinct.len();
+        *colors = refined;
+        if stable {
+            break;
+        }
+    }
+}
+
+/// \[Generic\] Return the nodes within `radius` hops of any node on `path`.
+///
+/// This is the corridor around a route: the nodes of `path` themselves
+/// (radius `0`), their neighbors (radius `1`), and so on. Hops follow the
+/// edges in the direction of `neighbors`, so in a directed graph they lead
+/// away from the path only.
+///
+/// Computes in **O(|V| + |E|)** time at most, with a breadth-first search
+/// started from all nodes of the path at once.
+///
+/// # Example
+/// ```rust
+/// use petgraph::graph::UnGraph;
+/// use petgraph::algo::path_neighborhood;
+///
+/// let g = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 4), (1, 5)]);
+/// let corridor = path_neighborhood(&g, &[1.into(), 2.into()], 1);
+/// let mut corridor: Vec<_> = corridor.into_iter().map(|n| n.index()).collect();
+/// corridor.sort();
+/// assert_eq!(corridor, vec![0, 1, 2, 3, 5]);
+/// ```
+pub fn path_neighborhood<G>(g: G, path: &[G::NodeId], radius: usize) -> HashSet<G::NodeId>
+where
+    G: IntoNeighbors,
+    G::NodeId: Eq + Hash,
+{
+    let mut seen: HashSet<_> = path.iter().cloned().collect();
+    let mut frontier: Vec<_> = seen.iter().cloned().collect();
+    for _ in 0..radius {
+        let mut next = Vec::new();
+        for &node in &frontier {
+            for succ in g.neighbors(node) {
+                if seen.insert(succ) {
+                    next.push(succ);
+                }
+            }
+        }
+        if next.is_empty() {
+            break;
+        }
+        frontier = next;
+    }
+    seen
+}
+
+/// \[Generic\] Return the region grown from `start` by a breadth-first
+/// search that only crosses an edge if `expand` allows it.
+///
+/// `expand` is called with the target node and the weight of every edge
+/// starting at a node of the region (as given by `edges`), whose target is
+/// not in the region yet; the target joins the region if it returns `true`.
+/// A node blocked through one edge can still be reached through another.
+/// `start` is always part of the region.
+///
+/// Computes in **O(|V| + |E|)** time (average), counting the nodes and edges
+/// of the region.
+///
+/// # Example
+/// ```rust
+/// use petgraph::graph::UnGraph;
+/// use petgraph::algo::flood_fill;
+///
+/// // heights along a ridge; only climb by at most 2 per step
+/// let g = UnGraph::<(), u32>::from_edges(&[(0, 1, 1), (1, 2, 5), (0, 3, 2)]);
+/// let region = flood_fill(&g, 0.into(), |_, &climb| climb <= 2);
+/// let mut region: Vec<_> = region.into_iter().map(|n| n.index()).collect();
+/// region.sort();
+/// assert_eq!(region, vec![0, 1, 3]);
+/// ```
+pub fn flood_fill<G, F>(g: G, start: G::NodeId, mut expand: F) -> HashSet<G::NodeId>
+where
+    G: IntoEdges,
+    G::NodeId: Eq + Hash,
+    F: FnMut(&G::NodeId, &G::EdgeWeight) -> bool,
+{
+    let mut region = HashSet::new();
+    region.insert(start);
+    let mut queue = VecDeque::new();
+    queue.push_back(start);
+    while let Some(node) = queue.pop_front() {
+        for edge in g.edges(node) {
+            let next = edge.target();
+            if !region.contains(&next) && expand(&next, edge.weight()) {
+                region.insert(next);
+                queue.push_back(next);
+            }
+        }
+    }
+    region
+}
+
+/// \[Generic\] Return the *eccentricity* of `a`: the largest number of edges
+/// on a shortest path from `a` to any node reachable from it.
+///
+/// Paths follow the edges in the direction of `neighbors`. Nodes that can
+/// not be reached from `a` are ignored, so in a disconnected graph this is
+/// the eccentricity within the component of `a`, and an isolated node has
+/// eccentricity `0`.
+///
+/// Computes in **O(|V| + |E|)** time, with a breadth-first search.
+///
+/// # Example
+/// ```rust
+/// use petgraph::graph::UnGraph;
+/// use petgraph::algo::eccentricity;
+///
+/// let g = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3)]);
+/// assert_eq!(eccentricity(&g, 0.into()), 3);
+/// assert_eq!(eccentricity(&g, 1.into()), 2);
+/// ```
+pub fn eccentricity<G>(g: G, a: G::NodeId) -> usize
+where
+    G: IntoNeighbors + Visitable,
+{
+    let mut discovered = g.visit_map();
+    discovered.visit(a);
+    let mut frontier = vec![a];
+    let mut depth = 0;
+    loop {
+        let mut next = Vec::new();
+        for &node in &frontier {
+            for succ in g.neighbors(node) {
+                if discovered.visit(succ) {
+                    next.push(succ);
+                }
+            }
+        }
+        if next.is_empty() {
+            return depth;
+        }
+        depth += 1;
+        frontier = next;
+    }
+}
+
+/// \[Generic\] Return the *center* of the graph: the nodes of minimum
+/// eccentricity.
+///
+/// The eccentricity of every node is computed like with `eccentricity`,
+/// counting only the nodes reachable from it. In a disconnected graph, that
+/// makes nodes of small components central; apply this to one component at
+/// a time to get the center of each. The nodes are returned in the order of
+/// `node_identifiers`, and the result is empty only for an empty graph.
+///
+/// Computes in **O(|V| (|V| + |E|))** time.
+///
+/// # Example
+/// ```rust
+/// use petgraph::graph::{NodeIndex, UnGraph};
+/// use petgraph::algo::center;
+///
+/// let g = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 4)]);
+/// assert_eq!(center(&g), vec![NodeIndex::new(2)]);
+/// ```
+pub fn center<G>(g: G) -> Vec<G::NodeId>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + Visitable,
+{
+    extreme_eccentricity(g, |e, best| e < best)
+}
+
+/// \[Generic\] Return the *periphery* of the graph: the nodes of maximum
+/// eccentricity.
+///
+/// The eccentricity of every node is computed like with `eccentricity`,
+/// counting only the nodes reachable from it; see `center` for the
+/// consequences in a disconnected graph. The nodes are returned in the order
+/// of `node_identifiers`, and the result is empty only for an empty graph.
+///
+/// Computes in **O(|V| (|V| + |E|))** time.
+///
+/// # Example
+/// ```rust
+/// use petgraph::graph::{NodeIndex, UnGraph};
+/// use petgraph::algo::periphery;
+///
+/// let g = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 4)]);
+/// assert_eq!(periphery(&g), vec![NodeIndex::new(0), NodeIndex::new(4)]);
+/// ```
+pub fn periphery<G>(g: G) -> Vec<G::NodeId>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + Visitable,
+{
+    extreme_eccentricity(g, |e, best| e > best)
+}
+
+/// Return the nodes whose eccentricity no other node's is `better` than.
+fn extreme_eccentricity<G, F>(g: G, mut better: F) -> Vec<G::NodeId>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + Visitable,
+    F: FnMut(usize, usize) -> bool,
+{
+    let mut best = None;
+    let mut nodes = Vec::new();
+    for a in g.node_identifiers() {
+        let e = eccentricity(g, a);
+        match best {
+            Some(b) if e == b => nodes.push(a),
+            Some(b) if !better(e, b) => {}
+            _ => {
+                best = Some(e);
+                nodes.clear();
+                nodes.push(a);
+            }
+        }
+    }
+    nodes
+}
+
+/// \[Generic\] Estimate the diameter of the graph from the eccentricities
+/// of `samples` randomly chosen nodes.
+///
+/// The nodes are chosen without repetition, and their eccentricity is
+/// computed like with `eccentricity`, counting only the nodes reachable from
+/// them. The largest one is returned; this is a lower bound of the diameter,
+/// which is exact when `samples` is at least the number of nodes. Return `0`
+/// for an empty graph.
+///
+/// `rng` must return numbers uniformly distributed in `[0, 1)`.
+///
+/// Computes in **O(samples · (|V| + |E|))** time, with a breadth-first
+/// search from every chosen node.
+///
+/// # Example
+/// ```rust
+/// use petgraph::graph::UnGraph;
+/// use petgraph::algo::approximate_diameter;
+///
+/// let g = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3)]);
+/// let mut draws = [0.7, 0.2, 0.95, 0.4].iter().cycle();
+/// let mut rng = || *draws.next().unwrap();
+/// assert!(approximate_diameter(&g, 1, &mut rng) >= 2);
+/// assert_eq!(approximate_diameter(&g, 4, &mut rng), 3);
+/// ```
+pub fn approximate_diameter<G, R>(g: G, samples: usize, rng: &mut R) -> usize
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + Visitable,
+    R: FnMut() -> f64,
+{
+    let mut nodes: Vec<_> = g.node_identifiers().collect();
+    let samples = samples.min(nodes.len());
+    // a partial Fisher-Yates shuffle puts the chosen nodes first
+    for i in 0..samples {
+        let j = i + random_index(rng, nodes.len() - i);
+        nodes.swap(i, j);
+    }
+    nodes[..samples]
+        .iter()
+        .map(|&a| eccentricity(g, a))
+        .max()
+        .unwrap_or(0)
+}
+
+/// \[Generic\] Compute the *harmonic centrality* of every node: the sum of
+/// the reciprocals of the distances from the node to every other node.
+///
+/// Distances count the edges on a shortest path, following the edges in the
+/// direction of `neighbors`. Nodes that can not be reached contribute `0`,
+/// so unlike closeness centrality, this is finite and meaningful for
+/// disconnected graphs: an isolated node has centrality `0`.
+///
+/// Computes in **O(|V| (|V| + |E|))** time, with a breadth-first search
+/// from every node.
+///
+/// # Example
+/// ```rust
+/// use petgraph::graph::UnGraph;
+/// use petgraph::algo::harmonic_centrality;
+///
+/// let g = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2)]);
+/// let centrality = harmonic_centrality(&g);
+/// assert_eq!(centrality[&0.into()], 1.5);
+/// assert_eq!(centrality[&1.into()], 2.);
+/// ```
+pub fn harmonic_centrality<G>(g: G) -> HashMap<G::NodeId, f64>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + Visitable,
+    G::NodeId: Eq + Hash,
+{
+    let mut discovered = g.visit_map();
+    g.node_identifiers()
+        .map(|a| {
+            g.reset_map(&mut discovered);
+            discovered.visit(a);
+            let mut frontier = vec![a];
+            let mut depth = 0;
+            let mut centrality = 0.;
+            while !frontier.is_empty() {
+                depth += 1;
+                let mut next = Vec::new();
+                for &node in &frontier {
+                    for succ in g.neighbors(node) {
+                        if discovered.visit(succ) {
+                            next.push(succ);
+                        }
+                    }
+                }
+                centrality += next.len() as f64 / depth as f64;
+                frontier = next;
+            }
+            (a, centrality)
+        })
+        .collect()
+}
+
+/// \[Generic\] Count, for every node, the distinct nodes it can reach.
+///
+/// A node is counted among its own descendants only if it lies on a cycle
+/// (a self loop included), that is, if a path of at least one edge leads
+/// back to it. The edges of an undirected graph can be followed both ways,
+/// so there every node with an edge reaches itself.
+///
+/// If the graph is acyclic, the reachable sets are merged in reverse
+/// topological order, in **O(|V| (|V| + |E|) / w)** time for a word size
+/// `w`. Otherwise a depth-first search is run from every node, in
+/// **O(|V| (|V| + |E|))** time.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::descendant_counts;
+///
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (0, 2), (1, 2)]);
+/// let counts = descendant_counts(&g);
+/// assert_eq!(counts[&0.into()], 2);
+/// assert_eq!(counts[&1.into()], 1);
+/// assert_eq!(counts[&2.into()], 0);
+/// ```
+pub fn descendant_counts<G>(g: G) -> HashMap<G::NodeId, usize>
+where
+    G: IntoNeighborsDirected + IntoNodeIdentifiers + NodeIndexable + Visitable,
+    G::NodeId: Eq + Hash,
+{
+    if let Ok(order) = toposort(g, None) {
+        let mut reach = vec![FixedBitSet::default(); g.node_bound()];
+        let mut counts = HashMap::with_capacity(order.len());
+        for &a in order.iter().rev() {
+            let mut set = FixedBitSet::with_capacity(g.node_bound());
+            for succ in g.neighbors(a) {
+                let i = g.to_index(succ);
+                set.insert(i);
+                set.union_with(&reach[i]);
+            }
+            counts.insert(a, set.count_ones(..));
+            reach[g.to_index(a)] = set;
+        }
+        return counts;
+    }
+
+    let mut discovered = g.visit_map();
+    g.node_identifiers()
+        .map(|a| {
+            g.reset_map(&mut discovered);
+            let mut stack: Vec<_> = g.neighbors(a).collect();
+            let mut count = 0;
+            while let Some(node) = stack.pop() {
+                if discovered.visit(node) {
+                    count += 1;
+                    stack.extend(g.neighbors(node));
+                }
+            }
+            (a, count)
+        })
+        .collect()
+}
+
+/// \[Generic\] Compute the SimRank similarity of every pair of nodes.
+///
+/// Two nodes are similar if their in-neighbors are similar: the similarity
+/// of `a` and `b` is `decay` times the average similarity of the pairs made
+/// of an in-neighbor of `a` and an in-neighbor of `b`, and the similarity of
+/// a node with itself is `1`. Nodes without in-neighbors are similar only
+/// to themselves. For an undirected graph, the in-neighbors are all the
+/// neighbors.
+///
+/// Starting from the identity, the similarities are updated `iterations`
+/// times, or until no similarity changes by more than `1e-10`. The result
+/// has an entry for every ordered pair of nodes, and is symmetric.
+///
+/// Uses **O(|V|²)** memory, and computes in **O(iterations · |E|²)** time.
+///
+/// **Panics** if `decay` is not in `[0, 1]`.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::simrank;
+///
+/// // 1 and 2 are both pointed to by 0
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (0, 2)]);
+/// let similarity = simrank(&g, 0.8, 10);
+/// assert_eq!(similarity[&(1.into(), 2.into())], 0.8);
+/// assert_eq!(similarity[&(0.into(), 1.into())], 0.);
+/// assert_eq!(similarity[&(2.into(), 2.into())], 1.);
+/// ```
+pub fn simrank<G>(g: G, decay: f64, iterations: usize) -> HashMap<(G::NodeId, G::NodeId), f64>
+where
+    G: IntoNeighborsDirected + IntoNodeIdentifiers + NodeIndexable,
+    G::NodeId: Eq + Hash,
+{
+    assert!(
+        (0. ..=1.).contains(&decay),
+        "simrank: decay must be in [0, 1]"
+    );
+    let nodes: Vec<_> = g.node_identifiers().collect();
+    let n = nodes.len();
+    let mut compact = vec![0; g.node_bound()];
+    for (i, &a) in nodes.iter().enumerate() {
+        compact[g.to_index(a)] = i;
+    }
+    let in_neighbors: Vec<Vec<usize>> = nodes
+        .iter()
+        .map(|&a| {
+            let mut pred: Vec<_> = g
+                .neighbors_directed(a, Incoming)
+                .map(|p| compact[g.to_index(p)])
+                .collect();
+            pred.sort_unstable();
+            pred.dedup();
+            pred
+        })
+        .collect();
+
+    let mut sim = vec![0.; n * n];
+    for i in 0..n {
+        sim[i * n + i] = 1.;
+    }
+    let mut next = sim.clone();
+    for _ in 0..iterations {
+        let mut change: f64 = 0.;
+        for a in 0..n {
+            for b in a + 1..n {
+                let (pa, pb) = (&in_neighbors[a], &in_neighbors[b]);
+                let value = if pa.is_empty() || pb.is_empty() {
+                    0.
+                } else {
+                    let total: f64 = pa
+                        .iter()
+                        .flat_map(|&i| pb.iter().map(move |&j| (i, j)))
+                        .map(|(i, j)| sim[i * n + j])
+                        .sum();
+                    decay * total / (pa.len() * pb.len()) as f64
+                };
+                change = change.max((value - sim[a * n + b]).abs());
+                next[a * n + b] = value;
+                next[b * n + a] = value;
+            }
+        }
+        std::mem::swap(&mut sim, &mut next);
+        if change <= 1e-10 {
+            break;
+        }
+    }
+
+    let mut result = HashMap::with_capacity(n * n);
+    for (i, &a) in nodes.iter().enumerate() {
+        for (j, &b) in nodes.iter().enumerate() {
+            result.insert((a, b), sim[i * n + j]);
+        }
+    }
+    result
+}
+
+/// \[Generic\] Collect the edge weights into a map keyed by endpoint pair.
+///
+/// Each edge is stored under `(source, target)`, as given by
+/// `edge_references`; for undirected graphs, where an edge has no
+/// preferred orientation, look up both `(a, b)` and `(b, a)`. If the graph
+/// has parallel edges, the weight of the one seen last is kept.
+///
+/// Computes in **O(|E|)** time (average).
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::edge_map;
+///
+/// let g = Graph::<(), u32>::from_edges(&[(0, 1, 3), (1, 2, 4)]);
+/// let weights = edge_map(&g);
+/// assert_eq!(weights[&(1.into(), 2.into())], 4);
+/// assert!(!weights.contains_key(&(2.into(), 1.into())));
+/// ```
+pub fn edge_map<G>(g: G) -> HashMap<(G::NodeId, G::NodeId), G::EdgeWeight>
+where
+    G: IntoEdgeReferences,
+    G::NodeId: Eq + Hash,
+    G::EdgeWeight: Clone,
+{
+    g.edge_references()
+        .map(|edge| ((edge.source(), edge.target()), edge.weight().clone()))
+        .collect()
+}
+
+/// \[Generic\] Color the edges of a graph so that edges sharing an endpoint
+/// have different colors.
+///
+/// Edges are treated as undirected. Colors are numbered from `0`, and each
+/// edge gets the smallest color not yet used at either of its endpoints, in
+/// the order of `edge_references`. A proper edge coloring needs at least
+/// **Δ** colors, for the maximum degree **Δ**, and **Δ + 1** are always
+/// enough; this greedy strategy is simpler and may use up to **2Δ - 1**.
+///
+/// Return a map from the endpoints `(source, target)` of every edge to its
+/// color. Parallel edges, also in opposite directions, share one entry.
+///
+/// Computes in **O(|V| + |E| Δ)** time.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::edge_coloring;
+///
+/// // a star: all edges meet at 0
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (0, 2), (3, 0)]);
+/// let colors = edge_coloring(&g);
+/// assert_eq!(colors[&(0.into(), 1.into())], 0);
+/// assert_eq!(colors[&(0.into(), 2.into())], 1);
+/// assert_eq!(colors[&(3.into(), 0.into())], 2);
+/// ```
+pub fn edge_coloring<G>(g: G) -> HashMap<(G::NodeId, G::NodeId), usize>
+where
+    G: IntoEdgeReferences + NodeIndexable,
+    G::NodeId: Eq + Hash,
+{
+    let mut used = vec![FixedBitSet::with_capacity(0); g.node_bound()];
+    let mut colors = HashMap::new();
+    for edge in g.edge_references() {
+        let (a, b) = (edge.source(), edge.target());
+        if colors.contains_key(&(a, b)) || colors.contains_key(&(b, a)) {
+            continue;
+        }
+        let (ai, bi) = (g.to_index(a), g.to_index(b));
+        let color = (0..)
+            .find(|&c| !used[ai].contains(c) && !used[bi].contains(c))
+            .unwrap();
+        for &i in &[ai, bi] {
+            if used[i].len() <= color {
+                used[i].grow(color + 1);
+            }
+            used[i].insert(color);
+        }
+        colors.insert((a, b), color);
+    }
+    colors
+}
+
+/// \[Generic\] Compute a maximal independent set of `g` with Luby's
+/// randomized algorithm.
+///
+/// Edges are treated as undirected. The set has no two adjacent nodes, and
+/// is maximal: every node outside of it has a neighbor in it, or a self
+/// loop, which keeps a node out of any independent set. It is not
+/// necessarily a largest independent set.
+///
+/// In every round, each remaining node draws a random number, and the nodes
+/// that drew less than all of their remaining neighbors join the set; they
+/// and their neighbors are then removed. `rng` must return numbers uniformly
+/// distributed in `[0, 1)`; ties are broken by the node index.
+///
+/// Computes in **O(|V| + |E|)** time per round, with **O(log |V|)** rounds
+/// expected.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::maximal_independent_set;
+///
+/// // a path 0 - 1 - 2
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2)]);
+/// // fixed draws for the nodes 0, 1 and 2, in place of random ones
+/// let mut draws = [0.1, 0.6, 0.35].iter().cycle();
+/// let mut rng = || *draws.next().unwrap();
+/// let set = maximal_independent_set(&g, &mut rng);
+/// // 1 drew more than both of its neighbors
+/// assert!(set.contains(&0.into()) && set.contains(&2.into()));
+/// assert_eq!(set.len(), 2);
+/// ```
+pub fn maximal_independent_set<G, R>(g: G, rng: &mut R) -> HashSet<G::NodeId>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+    G::NodeId: Eq + Hash,
+    R: FnMut() -> f64,
+{
+    let mut adjacent = vec![Vec::new(); g.node_bound()];
+    let mut active = FixedBitSet::with_capacity(g.node_bound());
+    for a in g.node_identifiers() {
+        active.insert(g.to_index(a));
+    }
+    for edge in g.edge_references() {
+        let (a, b) = (g.to_index(edge.source()), g.to_index(edge.target()));
+        if a == b {
+            active.set(a, false);
+        } else {
+            adjacent[a].push(b);
+            adjacent[b].push(a);
+        }
+    }
+
+    let mut set = HashSet::new();
+    let mut draw = vec![0.; g.node_bound()];
+    let mut remaining: Vec<usize> = active.ones().collect();
+    while !remaining.is_empty() {
+        for &a in &remaining {
+            draw[a] = rng();
+        }
+        let wins = |a: usize, b: usize| (draw[a], a) < (draw[b], b);
+        let chosen: Vec<usize> = remaining
+            .iter()
+            .cloned()
+            .filter(|&a| {
+                adjacent[a]
+                    .iter()
+                    .all(|&b| !active.contains(b) || wins(a, b))
+            })
+            .collect();
+        for &a in &chosen {
+            set.insert(g.from_index(a));
+            active.set(a, false);
+            for &b in &adjacent[a] {
+                active.set(b, false);
+            }
+        }
+        remaining.retain(|&a| active.contains(a));
+    }
+    set
+}
+
+/// \[Generic\] Split the nodes of `g` into two halves with few edges
+/// between them, with the Kernighan-Lin heuristic.
+///
+/// Edges are treated as undirected, parallel edges count once each and self
+/// loops are ignored. Starting from the first and the second half of the
+/// nodes in the order of `node_identifiers`, every pass tentatively swaps
+/// pairs of nodes between the halves, each time the pair that removes the
+/// most crossing edges, and keeps the best prefix of these swaps. Passes are
+/// repeated until one no longer improves the cut.
+///
+/// The first half has `⌈|V| / 2⌉` nodes and the second `⌊|V| / 2⌋`.
+///
+/// **Note:** This is a heuristic: the partition can not be improved by
+/// swapping nodes one pair at a time, but is not always a minimum bisection.
+///
+/// Computes in **O(|V|³)** time per pass.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::bisect;
+///
+/// // the triangles 0, 2, 4 and 1, 3, 5, joined by the edge 4 - 5
+/// let g = Graph::<(), ()>::from_edges(&[
+///     (0, 2), (2, 4), (4, 0), (1, 3), (3, 5), (5, 1), (4, 5),
+/// ]);
+/// let (a, b) = bisect(&g);
+/// assert_eq!(a.len(), 3);
+/// assert_eq!(a.contains(&0.into()), a.contains(&2.into()));
+/// assert_ne!(a.contains(&0.into()), b.contains(&4.into()));
+/// ```
+pub fn bisect<G>(g: G) -> (HashSet<G::NodeId>, HashSet<G::NodeId>)
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+    G::NodeId: Eq + Hash,
+{
+    let nodes: Vec<_> = g.node_identifiers().collect();
+    let n = nodes.len();
+    let mut compact = vec![0; g.node_bound()];
+    for (i, &a) in nodes.iter().enumerate() {
+        compact[g.to_index(a)] = i;
+    }
+    // the number of edges between every pair of nodes
+    let mut multiplicity = HashMap::new();
+    let mut adjacent = vec![Vec::new(); n];
+    for edge in g.edge_references() {
+        let a = compact[g.to_index(edge.source())];
+        let b = compact[g.to_index(edge.target())];
+        if a != b {
+            let count = multiplicity.entry((a.min(b), a.max(b))).or_insert(0);
+            if *count == 0 {
+                adjacent[a].push(b);
+                adjacent[b].push(a);
+            }
+            *count += 1;
+        }
+    }
+    let weight = |a: usize, b: usize| -> i64 {
+        multiplicity
+            .get(&(a.min(b), a.max(b)))
+            .cloned()
+            .unwrap_or(0)
+    };
+
+    // `true` for the nodes of the second half
+    let mut side: Vec<bool> = (0..n).map(|i| i >= n - n / 2).collect();
+    loop {
+        // how many more crossing edges than uncut edges every node has
+        let mut gain: Vec<i64> = (0..n)
+            .map(|a| {
+                adjacent[a]
+                    .iter()
+                    .map(|&b| {
+                        if side[a] != side[b] {
+                            weight(a, b)
+                        } else {
+                            -weight(a, b)
+                        }
+                    })
+                    .sum()
+            })
+            .collect();
+        let mut locked = vec![false; n];
+        let mut swaps = Vec::new();
+        let mut total = 0;
+        let mut best = (0, 0);
+        for _ in 0..n / 2 {
+            let mut pair = None;
+            for a in (0..n).filter(|&a| !locked[a] && !side[a]) {
+                for b in (0..n).filter(|&b| !locked[b] && side[b]) {
+                    let swap_gain = gain[a] + gain[b] - 2 * weight(a, b);
+                    match pair {
+                        Some((_, _, best)) if best >= swap_gain => {}
+                        _ => pair = Some((a, b, swap_gain)),
+                    }
+                }
+            }
+            let (a, b, swap_gain) = match pair {
+                Some(pair) => pair,
+                None => break,
+            };
+            locked[a] = true;
+            locked[b] = true;
+            // update the gains as if `a` and `b` had changed sides
+            for &moved in &[a, b] {
+                for &x in &adjacent[moved] {
+                    let w = weight(moved, x);
+                    if side[x] == side[moved] {
+                        gain[x] += 2 * w;
+                    } else {
+                        gain[x] -= 2 * w;
+                    }
+                }
+            }
+            swaps.push((a, b));
+            total += swap_gain;
+            if total > best.0 {
+                best = (total, swaps.len());
+            }
+        }
+        if best.0 <= 0 {
+            break;
+        }
+        for &(a, b) in &swaps[..best.1] {
+            side[a] = true;
+            side[b] = false;
+        }
+    }
+
+    let mut halves = (HashSet::new(), HashSet::new());
+    for (i, &a) in nodes.iter().enumerate() {
+        if side[i] {
+            halves.1.insert(a);
+        } else {
+            halves.0.insert(a);
+        }
+    }
+    halves
+}
+
+/// \[Generic\] Detect communities with the Louvain method, and return the
+/// community id of every node.
+///
+/// The graph is treated as undirected, with edge weights given by
+/// `edge_cost`, which must be non-negative. Every node starts in a community
+/// of its own. In the first phase, nodes are visited in a random order and
+/// moved to the neighboring community that increases the `modularity` the
+/// most, until no move increases it. In the second phase, every community is
+/// aggregated into a single node, and the two phases are repeated on the
+/// aggregated graph until no node moves anymore.
+///
+/// `rng` must return numbers uniformly distributed in `[0, 1)`; it decides
+/// the order the nodes are visited in, which can lead to different
+/// communities. The ids are numbered from `0`, in the order of
+/// `node_identifiers`.
+///
+/// **Note:** This is a heuristic: the partition is a local optimum of the
+/// modularity only.
+///
+/// Computes in **O(|V| + |E|)** time per sweep over the nodes; the number of
+/// sweeps is small in practice.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::louvain_communities;
+///
+/// // two triangles joined by an edge
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3), (2, 3)]);
+/// let mut draws = [0.3, 0.8, 0.55, 0.05].iter().cycle();
+/// let mut rng = || *draws.next().unwrap();
+/// let community = louvain_communities(&g, |_| 1., &mut rng);
+/// assert_eq!(community[&0.into()], community[&2.into()]);
+/// assert_eq!(community[&3.into()], community[&5.into()]);
+/// assert_ne!(community[&2.into()], community[&3.into()]);
+/// ```
+pub fn louvain_communities<G, F, R>(
+    g: G,
+    mut edge_cost: F,
+    rng: &mut R,
+) -> HashMap<G::NodeId, usize>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+    G::NodeId: Eq + Hash,
+    F: FnMut(G::EdgeRef) -> f64,
+    R: FnMut() -> f64,
+{
+    let nodes: Vec<_> = g.node_identifiers().collect();
+    let mut compact = vec![0; g.node_bound()];
+    for (i, &a) in nodes.iter().enumerate() {
+        compact[g.to_index(a)] = i;
+    }
+    // the graph of the current level: weighted adjacency lists, in both
+    // directions, and the weight of the self loops of every node
+    let mut adjacent = vec![Vec::new(); nodes.len()];
+    let mut loops = vec![0.; nodes.len()];
+    for edge in g.edge_references() {
+        let a = compact[g.to_index(edge.source())];
+        let b = compact[g.to_index(edge.target())];
+        let w = edge_cost(edge);
+        if a == b {
+            loops[a] += w;
+        } else {
+            adjacent[a].push((b, w));
+            adjacent[b].push((a, w));
+        }
+    }
+    // the node of the current level every original node belongs to
+    let mut membership: Vec<usize> = (0..nodes.len()).collect();
+
+    loop {
+        let n = adjacent.len();
+        let degree: Vec<f64> = (0..n)
+            .map(|a| adjacent[a].iter().map(|&(_, w)| w).sum::<f64>() + 2. * loops[a])
+            .collect();
+        let total: f64 = degree.iter().sum();
+        if total == 0. {
+            break;
+        }
+        let mut community: Vec<usize> = (0..n).collect();
+        let mut community_degree = degree.clone();
+        // the weight from the current node to every community, and the
+        // communities with a weight
+        let mut weight_to = vec![0.; n];
+        let mut touched = Vec::new();
+        let mut order: Vec<usize> = (0..n).collect();
+        let mut improved = false;
+        loop {
+            for i in (1..n).rev() {
+                let j = random_index(rng, i + 1);
+                order.swap(i, j);
+            }
+            let mut moved = false;
+            for &a in &order {
+                for &(b, w) in &adjacent[a] {
+                    let c = community[b];
+                    if weight_to[c] == 0. {
+                        touched.push(c);
+                    }
+                    weight_to[c] += w;
+                }
+                let current = community[a];
+                community_degree[current] -= degree[a];
+                let gain = |c: usize, weight: f64| weight - community_degree[c] * degree[a] / total;
+                let mut best = (current, gain(current, weight_to[current]));
+                for &c in &touched {
+                    let g = gain(c, weight_to[c]);
+                    if g > best.1 + 1e-12 {
+                        best = (c, g);
+                    }
+                }
+                community_degree[best.0] += degree[a];
+                if best.0 != current {
+                    community[a] = best.0;
+                    moved = true;
+                }
+                for c in touched.drain(..) {
+                    weight_to[c] = 0.;
+                }
+            }
+            if !moved {
+                break;
+            }
+            improved = true;
+        }
+        if !improved {
+            break;
+        }
+
+        // aggregate every community into a node of the next level
+        let mut renumber = vec![None; n];
+        let mut count = 0;
+        for c in community.iter_mut() {
+            *c = *renumber[*c].get_or_insert_with(|| {
+                count += 1;
+                count - 1
+            });
+        }
+        let mut next_loops = vec![0.; count];
+        let mut next_weights = vec![HashMap::new(); count];
+        for a in 0..n {
+            let c = community[a];
+            next_loops[c] += loops[a];
+            for &(b, w) in &adjacent[a] {
+                let d = community[b];
+                if c == d {
+                    // every internal edge is seen from both of its endpoints
+                    next_loops[c] += w / 2.;
+                } else {
+                    *next_weights[c].entry(d).or_insert(0.) += w;
+                }
+            }
+        }
+        adjacent = next_weights
+            .into_iter()
+            .map(|weights| {
+                let mut weights: Vec<_> = weights.into_iter().collect();
+                weights.sort_by_key(|&(d, _)| d);
+                weights
+            })
+            .collect();
+        loops = next_loops;
+        for m in &mut membership {
+            *m = community[*m];
+        }
+    }
+
+    // number the final communities in the order of the nodes
+    let mut renumber = HashMap::new();
+    nodes
+        .iter()
+        .zip(membership)
+        .map(|(&a, m)| {
+            let next = renumber.len();
+            (a, *renumber.entry(m).or_insert(next))
+        })
+        .collect()
+}
+
+/// \[Generic\] Compute the *edge betweenness* of every edge: the number of
+/// shortest paths between pairs of nodes that pass through it.
+///
+/// Edges are unweighted, so shortest paths are those with the fewest edges.
+/// When a pair of nodes is joined by several shortest paths, each of them
+/// counts as a fraction, so that every pair contributes **1** in total. Edges
+/// with a high betweenness are bottlenecks of the network: removing a bridge
+/// with betweenness **k** disconnects **k** pairs of nodes.
+///
+/// Return a map from the endpoints `(source, target)` of every edge, as
+/// given by `edge_references`, to its betweenness. For an undirected graph
+/// each unordered pair of nodes is counted once. Parallel edges share one
+/// entry.
+///
+/// This uses Brandes' accumulation of path dependencies, adapted to edges,
+/// with a breadth-first search from every node.
+///
+/// Computes in **O(|V| |E|)** time.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::edge_betweenness;
+///
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2)]);
+/// let betweenness = edge_betweenness(&g);
+/// // 0 -> 1 is used by the paths 0 -> 1 and 0 -> 2
+/// assert_eq!(betweenness[&(0.into(), 1.into())], 2.);
+/// assert_eq!(betweenness[&(1.into(), 2.into())], 2.);
+/// ```
+pub fn edge_betweenness<G>(g: G) -> HashMap<(G::NodeId, G::NodeId), f64>
+where
+    G: IntoEdgeReferences + IntoNeighbors + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+    G::NodeId: Eq + Hash,
+{
+    let mut betweenness: HashMap<_, f64> = g
+        .edge_references()
+        .map(|edge| ((edge.source(), edge.target()), 0.))
+        .collect();
+    let n = g.node_bound();
+    let mut order = Vec::with_capacity(n);
+    let mut preds = vec![Vec::new(); n];
+    let mut sigma = vec![0.; n];
+    let mut dist = vec![None; n];
+    let mut delta = vec![0.; n];
+    let mut queue = VecDeque::new();
+    for s in g.node_identifiers() {
+        order.clear();
+        for p in &mut preds {
+            p.clear();
+        }
+        sigma.iter_mut().for_each(|x| *x = 0.);
+        dist.iter_mut().for_each(|x| *x = None);
+        delta.iter_mut().for_each(|x| *x = 0.);
+        let si = g.to_index(s);
+        sigma[si] = 1.;
+        dist[si] = Some(0);
+        queue.push_back(s);
+        while let Some(v) = queue.pop_front() {
+            let vi = g.to_index(v);
+            order.push(v);
+            let d = dist[vi].unwrap();
+            for w in g.neighbors(v) {
+                let wi = g.to_index(w);
+                if dist[wi].is_none() {
+                    dist[wi] = Some(d + 1);
+                    queue.push_back(w);
+                }
+                if dist[wi] == Some(d + 1) {
+                    sigma[wi] += sigma[vi];
+                    preds[wi].push(v);
+                }
+            }
+        }
+        // accumulate dependencies in order of decreasing distance from s
+        for &w in order.iter().rev() {
+            let wi = g.to_index(w);
+            for &v in &preds[wi] {
+                let vi = g.to_index(v);
+                let c = sigma[vi] / sigma[wi] * (1. + delta[wi]);
+                delta[vi] += c;
+                let key = if betweenness.contains_key(&(v, w)) {
+                    (v, w)
+                } else {
+                    (w, v)
+                };
+                *betweenness.get_mut(&key).unwrap() += c;
+            }
+        }
+    }
+    if !g.is_directed() {
+        // every path was found from both of its ends
+        for value in betweenness.values_mut() {
+            *value /= 2.;
+        }
+    }
+    betweenness
+}
+
+/// \[Generic\] Compute the *betweenness centrality* of every node on a
+/// weighted graph: the number of shortest paths between other nodes that
+/// pass through it.
+///
+/// The length of a path is the sum of `edge_cost` over its edges, which must
+/// be positive. When a pair of nodes is joined by several shortest paths,
+/// each of them counts as a fraction, so that every pair contributes **1**
+/// in total; all the predecessors of a node on equally short paths are kept.
+/// For an undirected graph each unordered pair of nodes is counted once.
+/// The endpoints of a path do not count, so leaves have centrality `0`.
+///
+/// This is Brandes' algorithm, with Dijkstra's algorithm from every node.
+///
+/// Computes in **O(|V| (|E| + |V|) log |V|)** time.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::weighted_betweenness_centrality;
+///
+/// // 0 -> 1 -> 2 costs 2, the direct 0 -> 2 costs 3
+/// let g = Graph::<(), u32>::from_edges(&[(0, 1, 1), (1, 2, 1), (0, 2, 3)]);
+/// let centrality = weighted_betweenness_centrality(&g, |e| *e.weight());
+/// assert_eq!(centrality[&1.into()], 1.);
+/// assert_eq!(centrality[&0.into()], 0.);
+/// ```
+pub fn weighted_betweenness_centrality<G, F, K>(g: G, mut edge_cost: F) -> HashMap<G::NodeId, f64>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+    G::NodeId: Eq + Hash,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    let n = g.node_bound();
+    let mut centrality = vec![0.; n];
+    let mut order = Vec::with_capacity(n);
+    let mut preds = vec![Vec::new(); n];
+    let mut sigma = vec![0.; n];
+    let mut dist = vec![None; n];
+    let mut settled = FixedBitSet::with_capacity(n);
+    let mut delta = vec![0.; n];
+    let mut visit_next = BinaryHeap::new();
+    for s in g.node_identifiers() {
+        order.clear();
+        for p in &mut preds {
+            p.clear();
+        }
+        sigma.iter_mut().for_each(|x| *x = 0.);
+        dist.iter_mut().for_each(|x| *x = None);
+        delta.iter_mut().for_each(|x| *x = 0.);
+        settled.clear();
+        let si = g.to_index(s);
+        sigma[si] = 1.;
+        dist[si] = Some(K::default());
+        visit_next.push(MinScored(K::default(), s));
+        while let Some(MinScored(d, v)) = visit_next.pop() {
+            let vi = g.to_index(v);
+            if settled.put(vi) {
+                continue;
+            }
+            order.push(vi);
+            for edge in g.edges(v) {
+                let w = edge.target();
+                let wi = g.to_index(w);
+                if settled.contains(wi) {
+                    continue;
+                }
+                let next = d + edge_cost(edge);
+                match dist[wi] {
+                    Some(old) if next > old => {}
+                    Some(old) if next == old => {
+                        sigma[wi] += sigma[vi];
+                        preds[wi].push(vi);
+                    }
+                    _ => {
+                        dist[wi] = Some(next);
+                        sigma[wi] = sigma[vi];
+                        preds[wi].clear();
+                        preds[wi].push(vi);
+                        visit_next.push(MinScored(next, w));
+                    }
+                }
+            }
+        }
+        // accumulate dependencies in order of decreasing distance from s
+        for &wi in order.iter().rev() {
+            for &vi in &preds[wi] {
+                delta[vi] += sigma[vi] / sigma[wi] * (1. + delta[wi]);
+            }
+            if wi != si {
+                centrality[wi] += delta[wi];
+            }
+        }
+    }
+    if !g.is_directed() {
+        // every path was found from both of its ends
+        for value in &mut centrality {
+            *value /= 2.;
+        }
+    }
+    g.node_identifiers()
+        .map(|a| (a, centrality[g.to_index(a)]))
+        .collect()
+}
+
 /// \[Generic\] Compute a *minimum spanning tree* of a graph.
 ///
 /// The input graph is treated as if undirected.
@@ -890,7 +4028,7 @@ where
 }
 
 use std::fmt::Debug;
-use std::ops::Add;
+use std::ops::{Add, Sub};
 
 /// Associated data that can be used for measures (such as length).
 pub trait Measure: Debug + PartialOrd + Add<Self, Output = Self> + Default + Clone {}
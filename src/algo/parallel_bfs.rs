@@ -0,0 +1,173 @@
+use std::collections::hash_map::Entry::Vacant;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::mem;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, RwLock};
+use std::thread::{self, JoinHandle};
+
+use crate::visit::IntoNeighbors;
+
+/// \[Generic\] Compute the breadth-first distance (number of edges) from
+/// `start` to every reachable node, processing each BFS level on up to
+/// `threads` threads.
+///
+/// The search is level-synchronous: the current frontier is split into
+/// chunks that are expanded concurrently, and the newly discovered nodes are
+/// merged into the result once every chunk of the level has been expanded.
+/// The result is identical to a sequential breadth-first search, for example
+/// `dijkstra` with unit edge costs.
+///
+/// The threads are started once and kept for the whole search. Since the
+/// graph is shared between them, it (that is, the graph reference `G`) must
+/// be `Send + Sync`, and so must the node identifiers, which requires
+/// `N: Send + Sync` for a `GraphMap<N, E, Ty>`. A `threads` value of `0` is
+/// treated as `1`.
+///
+/// Threads are only worth their start-up cost for very large frontiers;
+/// on small graphs this is slower than a plain `Bfs`.
+///
+/// Returns a `HashMap` that maps `NodeId` to its distance from `start`.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::parallel_bfs_distances;
+///
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (0, 2), (2, 3), (1, 3)]);
+/// let dist = parallel_bfs_distances(&g, 0.into(), 2);
+/// assert_eq!(dist[&3.into()], 2);
+/// ```
+pub fn parallel_bfs_distances<G>(
+    graph: G,
+    start: G::NodeId,
+    threads: usize,
+) -> HashMap<G::NodeId, usize>
+where
+    G: IntoNeighbors + Send + Sync,
+    G::NodeId: Eq + Hash + Send + Sync,
+{
+    let threads = threads.max(1);
+    let mut distances = HashMap::new();
+    distances.insert(start, 0);
+    // The workers only read `visited` while a level is expanded, and the
+    // discovered nodes are only written once all of them are done.
+    let visited = RwLock::new(distances);
+    let pool = WorkerPool::new(threads, |chunk: Vec<G::NodeId>| {
+        let visited = visited.read().unwrap();
+        chunk
+            .into_iter()
+            .flat_map(|node| graph.neighbors(node))
+            .filter(|next| !visited.contains_key(next))
+            .collect::<Vec<_>>()
+    });
+
+    let mut frontier = vec![start];
+    let mut level = 0;
+    while !frontier.is_empty() {
+        level += 1;
+        // `usize::div_ceil` is too recent for the supported Rust version.
+        #[allow(clippy::manual_div_ceil)]
+        let chunk_size = (frontier.len() + threads - 1) / threads;
+        let discovered = pool.run(frontier.chunks(chunk_size).map(<[_]>::to_vec));
+
+        frontier.clear();
+        let mut distances = visited.write().unwrap();
+        for next in discovered.into_iter().flatten() {
+            if let Vacant(ent) = distances.entry(next) {
+                ent.insert(level);
+                frontier.push(next);
+            }
+        }
+    }
+    drop(pool);
+    visited.into_inner().unwrap()
+}
+
+/// A fixed set of worker threads, each running `work` on the jobs sent to
+/// it, until the pool is dropped.
+///
+/// The threads may borrow data living for `'a`: dropping the pool joins all
+/// of them, so they never outlive it. The pool must not be leaked.
+struct WorkerPool<'a, T, U> {
+    jobs: Vec<Sender<T>>,
+    results: Receiver<thread::Result<U>>,
+    handles: Vec<JoinHandle<()>>,
+    borrow: PhantomData<&'a ()>,
+}
+
+impl<'a, T, U> WorkerPool<'a, T, U>
+where
+    T: Send + 'a,
+    U: Send + 'a,
+{
+    /// Start `threads` workers, which must be at least one.
+    fn new<F>(threads: usize, work: F) -> Self
+    where
+        F: Fn(T) -> U + Send + Sync + 'a,
+    {
+        let work = Arc::new(work);
+        let (result_sender, results) = channel();
+        // Built before any thread is started, so that a failure to start one
+        // still joins the others.
+        let mut pool = WorkerPool {
+            jobs: Vec::with_capacity(threads),
+            results,
+            handles: Vec::with_capacity(threads),
+            borrow: PhantomData,
+        };
+        for _ in 0..threads {
+            let (job_sender, job_receiver) = channel::<T>();
+            let result_sender = result_sender.clone();
+            let work = work.clone();
+            let worker: Box<dyn FnOnce() + Send + 'a> = Box::new(move || {
+                for job in job_receiver {
+                    let result = panic::catch_unwind(AssertUnwindSafe(|| work(job)));
+                    if result_sender.send(result).is_err() {
+                        break;
+                    }
+                }
+            });
+            // SAFETY: the thread only uses data borrowed for `'a`, and it is
+            // joined when the pool, which can not outlive `'a`, is dropped.
+            let worker: Box<dyn FnOnce() + Send + 'static> = unsafe { mem::transmute(worker) };
+            pool.jobs.push(job_sender);
+            pool.handles.push(thread::spawn(worker));
+        }
+        pool
+    }
+
+    /// Hand out one job per worker, at most, and wait for all of their
+    /// results, in no particular order.
+    ///
+    /// **Panics** if there are more jobs than workers, or resumes the panic
+    /// of a worker.
+    fn run<I>(&self, jobs: I) -> Vec<U>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut count = 0;
+        for job in jobs {
+            self.jobs[count].send(job).unwrap();
+            count += 1;
+        }
+        (0..count)
+            .map(|_| match self.results.recv().unwrap() {
+                Ok(result) => result,
+                Err(payload) => panic::resume_unwind(payload),
+            })
+            .collect()
+    }
+}
+
+impl<'a, T, U> Drop for WorkerPool<'a, T, U> {
+    fn drop(&mut self) {
+        // Closing the job channels stops the workers.
+        self.jobs.clear();
+        for handle in self.handles.drain(..) {
+            let _ = handle.join();
+        }
+    }
+}
@@ -0,0 +1,360 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use fixedbitset::FixedBitSet;
+
+use crate::algo::tarjan_scc;
+use crate::visit::{Dfs, IntoNeighbors, IntoNodeIdentifiers, NodeIndexable, Visitable};
+
+/// \[Generic\] Compute the transitive closure of a graph as bitsets, using
+/// [Warshall's algorithm][1].
+///
+/// [1]: https://en.wikipedia.org/wiki/Floyd%E2%80%93Warshall_algorithm
+///
+/// Return the nodes in the order of `node_identifiers` together with one
+/// bitset per node: bit `j` of the set at position `i` is set if and only if
+/// `nodes[j]` can be reached from `nodes[i]` by following one or more edges.
+/// This is the same relation as computed by `reachability_matrix`.
+///
+/// The rows are packed into machine words, so the triple loop takes
+/// **O(|V|³ / 64)** time, independently of the number of edges. That is
+/// usually much faster than traversing from every node for small, dense
+/// graphs.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::transitive_closure_fast;
+///
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 1)]);
+/// let (nodes, closure) = transitive_closure_fast(&g);
+/// assert_eq!(nodes.len(), 3);
+/// assert_eq!(closure[0].ones().collect::<Vec<_>>(), vec![1, 2]);
+/// assert!(closure[1].contains(1));
+/// ```
+pub fn transitive_closure_fast<G>(g: G) -> (Vec<G::NodeId>, Vec<FixedBitSet>)
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+{
+    let nodes: Vec<_> = g.node_identifiers().collect();
+    let mut position = vec![0; g.node_bound()];
+    for (i, &n) in nodes.iter().enumerate() {
+        position[g.to_index(n)] = i;
+    }
+
+    let mut rows: Vec<FixedBitSet> = nodes
+        .iter()
+        .map(|&n| {
+            let mut row = FixedBitSet::with_capacity(nodes.len());
+            row.extend(g.neighbors(n).map(|m| position[g.to_index(m)]));
+            row
+        })
+        .collect();
+    for k in 0..rows.len() {
+        let (before, rest) = rows.split_at_mut(k);
+        let (row_k, after) = rest.split_first_mut().unwrap();
+        for row in before.iter_mut().chain(after) {
+            if row.contains(k) {
+                row.union_with(row_k);
+            }
+        }
+    }
+    (nodes, rows)
+}
+
+/// A precomputed answer to "can `a` reach `b`?" for every pair of nodes of
+/// a graph that does not change.
+///
+/// Building the oracle condenses the graph into its strongly connected
+/// components, and computes for every component the set of components it
+/// can reach, processing them in reverse topological order. Afterwards each
+/// query is two hash map lookups plus one bit test, whatever the size of the
+/// graph.
+///
+/// The build takes **O(|V| + |E| · c / 64)** time and the oracle uses
+/// **O(|V| + c² / 64)** words of space, where **c** is the number of strongly
+/// connected components.
+///
+/// The oracle is a snapshot: it is not updated when the graph is modified.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::ReachabilityOracle;
+///
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 1), (3, 0)]);
+/// let oracle = ReachabilityOracle::new(&g);
+/// assert!(oracle.can_reach(3.into(), 2.into()));
+/// assert!(oracle.can_reach(2.into(), 1.into()));
+/// assert!(!oracle.can_reach(2.into(), 0.into()));
+/// ```
+#[derive(Clone, Debug)]
+pub struct ReachabilityOracle<N>
+where
+    N: Eq + Hash,
+{
+    component: HashMap<N, usize>,
+    reach: Vec<FixedBitSet>,
+}
+
+impl<N> ReachabilityOracle<N>
+where
+    N: Copy + Eq + Hash,
+{
+    /// \[Generic\] Build the oracle for the graph `g`.
+    pub fn new<G>(g: G) -> Self
+    where
+        G: IntoNeighbors<NodeId = N> + IntoNodeIdentifiers + NodeIndexable,
+    {
+        // `tarjan_scc` emits the components in reverse topological order, so
+        // every successor component is complete once it is needed.
+        let sccs = tarjan_scc(g);
+        let mut component = HashMap::with_capacity(g.node_bound());
+        for (i, scc) in sccs.iter().enumerate() {
+            for &n in scc {
+                component.insert(n, i);
+            }
+        }
+
+        let mut reach: Vec<FixedBitSet> = Vec::with_capacity(sccs.len());
+        for (i, scc) in sccs.iter().enumerate() {
+            let mut set = FixedBitSet::with_capacity(sccs.len());
+            for &n in scc {
+                for succ in g.neighbors(n) {
+                    let c = component[&succ];
+                    set.insert(c);
+                    if c != i {
+                        set.union_with(&reach[c]);
+                    }
+                }
+            }
+            reach.push(set);
+        }
+        ReachabilityOracle { component, reach }
+    }
+
+    /// Return `true` if there is a path starting at `from` and reaching `to`.
+    ///
+    /// Like `has_path_connecting`, this returns `true` if `from` and `to` are
+    /// equal. Nodes that were not part of the graph reach nothing (except
+    /// themselves).
+    pub fn can_reach(&self, from: N, to: N) -> bool {
+        if from == to {
+            return true;
+        }
+        match (self.component.get(&from), self.component.get(&to)) {
+            (Some(&a), Some(&b)) => a == b || self.reach[a].contains(b),
+            _ => false,
+        }
+    }
+}
+
+/// A reachability index that is kept up to date while edges are added.
+///
+/// `IncrementalReachability` stores its own directed graph, given by calls
+/// to `add_edge`, together with the set of nodes reachable from every node.
+/// Answering whether `a` can reach `b` is a hash map lookup and a bit test.
+///
+/// Adding an edge `a -> b` that creates new paths extends the set of every
+/// node that reaches `a` by the set of `b`, which takes **O(|V|² / 64)**
+/// time instead of the **O(|V| (|V| + |E|))** of recomputing the closure.
+/// An edge whose endpoints were connected already takes **O(1)** time.
+///
+/// **Note:** Removing an edge may break paths in ways that are expensive to
+/// track, so `remove_edge` rebuilds the whole index, in **O(|V| (|V| +
+/// |E|))** time.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::IncrementalReachability;
+///
+/// let mut reach = IncrementalReachability::new();
+/// reach.add_edge("a", "b");
+/// reach.add_edge("c", "d");
+/// assert!(!reach.can_reach("a", "d"));
+/// reach.add_edge("b", "c");
+/// assert!(reach.can_reach("a", "d"));
+/// reach.remove_edge("b", "c");
+/// assert!(!reach.can_reach("a", "d"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct IncrementalReachability<N>
+where
+    N: Eq + Hash,
+{
+    index: HashMap<N, usize>,
+    succ: Vec<Vec<usize>>,
+    // bit `j` of `reach[i]` is set if node `j` can be reached from node `i`
+    // by following one or more edges
+    reach: Vec<FixedBitSet>,
+}
+
+impl<N> IncrementalReachability<N>
+where
+    N: Copy + Eq + Hash,
+{
+    /// Create a new, empty `IncrementalReachability`.
+    pub fn new() -> Self {
+        IncrementalReachability {
+            index: HashMap::new(),
+            succ: Vec::new(),
+            reach: Vec::new(),
+        }
+    }
+
+    /// Return the number of nodes.
+    pub fn node_count(&self) -> usize {
+        self.succ.len()
+    }
+
+    /// Return the number of edges.
+    pub fn edge_count(&self) -> usize {
+        self.succ.iter().map(Vec::len).sum()
+    }
+
+    /// Add node `n`, if it is not present yet.
+    ///
+    /// Return `true` if the node was added.
+    pub fn add_node(&mut self, n: N) -> bool {
+        if self.index.contains_key(&n) {
+            return false;
+        }
+        self.node_index(n);
+        true
+    }
+
+    /// Add an edge from `a` to `b`, adding the nodes if they are not present
+    /// yet, and update the reachability of every node that reaches `a`.
+    ///
+    /// Parallel edges are allowed.
+    pub fn add_edge(&mut self, a: N, b: N) {
+        let a = self.node_index(a);
+        let b = self.node_index(b);
+        self.succ[a].push(b);
+        if self.reach[a].contains(b) {
+            return;
+        }
+        let mut gained = self.reach[b].clone();
+        gained.grow(b + 1);
+        gained.insert(b);
+        for x in 0..self.reach.len() {
+            if x == a || self.reach[x].contains(a) {
+                self.reach[x].union_with(&gained);
+            }
+        }
+    }
+
+    /// Remove one edge from `a` to `b`, and rebuild the index.
+    ///
+    /// Return `true` if there was such an edge; otherwise nothing changes.
+    pub fn remove_edge(&mut self, a: N, b: N) -> bool {
+        let (a, b) = match (self.index.get(&a), self.index.get(&b)) {
+            (Some(&a), Some(&b)) => (a, b),
+            _ => return false,
+        };
+        match self.succ[a].iter().position(|&x| x == b) {
+            Some(i) => self.succ[a].swap_remove(i),
+            None => return false,
+        };
+        self.rebuild();
+        true
+    }
+
+    /// Return `true` if there is a path starting at `from` and reaching `to`.
+    ///
+    /// Like `ReachabilityOracle::can_reach`, this returns `true` if `from`
+    /// and `to` are equal. Nodes that are not present reach nothing (except
+    /// themselves).
+    pub fn can_reach(&self, from: N, to: N) -> bool {
+        if from == to {
+            return true;
+        }
+        match (self.index.get(&from), self.index.get(&to)) {
+            (Some(&a), Some(&b)) => self.reach[a].contains(b),
+            _ => false,
+        }
+    }
+
+    fn node_index(&mut self, n: N) -> usize {
+        let next = self.succ.len();
+        let i = *self.index.entry(n).or_insert(next);
+        if i == next {
+            self.succ.push(Vec::new());
+            self.reach.push(FixedBitSet::with_capacity(next + 1));
+        }
+        i
+    }
+
+    fn rebuild(&mut self) {
+        let n = self.succ.len();
+        let mut stack = Vec::new();
+        for (start, row) in self.reach.iter_mut().enumerate() {
+            *row = FixedBitSet::with_capacity(n);
+            stack.extend(&self.succ[start]);
+            while let Some(x) = stack.pop() {
+                if !row.put(x) {
+                    stack.extend(&self.succ[x]);
+                }
+            }
+        }
+    }
+}
+
+impl<N> Default for IncrementalReachability<N>
+where
+    N: Copy + Eq + Hash,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// \[Generic\] Compute which nodes can be reached from which, as a boolean
+/// table.
+///
+/// Return the nodes in the order of `node_identifiers` together with a
+/// square matrix where entry `[i][j]` is `true` if and only if `nodes[j]` can
+/// be reached from `nodes[i]` by following one or more edges. In
+/// particular, the diagonal entry of a node is only `true` if the node lies
+/// on a cycle.
+///
+/// This is the boolean form of the transitive closure, meant for small
+/// graphs: it runs a graph traversal from every node, computing in
+/// **O(|V| (|V| + |E|))** time and using **O(|V|²)** space.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::reachability_matrix;
+///
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2)]);
+/// let (nodes, matrix) = reachability_matrix(&g);
+/// assert_eq!(nodes.len(), 3);
+/// assert_eq!(matrix[0], vec![false, true, true]);
+/// assert_eq!(matrix[2], vec![false, false, false]);
+/// ```
+pub fn reachability_matrix<G>(g: G) -> (Vec<G::NodeId>, Vec<Vec<bool>>)
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + Visitable + NodeIndexable,
+{
+    let nodes: Vec<_> = g.node_identifiers().collect();
+    let mut position = vec![0; g.node_bound()];
+    for (i, &n) in nodes.iter().enumerate() {
+        position[g.to_index(n)] = i;
+    }
+
+    let mut dfs = Dfs::empty(g);
+    let matrix = nodes
+        .iter()
+        .map(|&n| {
+            let mut row = vec![false; nodes.len()];
+            dfs.reset(g);
+            dfs.stack.extend(g.neighbors(n));
+            while let Some(m) = dfs.next(g) {
+                row[position[g.to_index(m)]] = true;
+            }
+            row
+        })
+        .collect();
+    (nodes, matrix)
+}
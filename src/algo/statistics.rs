@@ -0,0 +1,529 @@
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::ops::Add;
+
+use crate::util::random_index;
+use crate::visit::{
+    EdgeRef, GraphProp, IntoEdgeReferences, IntoEdges, IntoNeighbors, IntoNeighborsDirected,
+    IntoNodeIdentifiers,
+};
+use crate::Direction;
+
+/// \[Generic\] Count the edges whose weight falls into each of `bins`
+/// equal-width buckets over the range `[min, max]`.
+///
+/// Bucket `i` covers the weights `w` with
+/// `min + i * width <= w < min + (i + 1) * width`, where
+/// `width = (max - min) / bins`; the last bucket also includes `max`.
+/// Weights below `min`, and NaN weights, are counted in the first bucket and
+/// weights above `max` in the last one.
+///
+/// Return a vector of `bins` counts, which is empty if `bins` is `0`.
+///
+/// **Panics** if `min` is not less than `max`.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::edge_weight_histogram;
+///
+/// let g = Graph::<(), f32>::from_edges(&[(0, 1, 0.5), (1, 2, 1.5), (2, 0, 1.8), (0, 2, 7.)]);
+/// assert_eq!(edge_weight_histogram(&g, 2, 0., 2.), vec![1, 3]);
+/// ```
+pub fn edge_weight_histogram<G>(g: G, bins: usize, min: f64, max: f64) -> Vec<usize>
+where
+    G: IntoEdgeReferences,
+    G::EdgeWeight: Clone + Into<f64>,
+{
+    assert!(
+        min < max,
+        "edge_weight_histogram: min must be less than max"
+    );
+    let mut counts = vec![0; bins];
+    if bins == 0 {
+        return counts;
+    }
+    let scale = bins as f64 / (max - min);
+    for edge in g.edge_references() {
+        let w: f64 = edge.weight().clone().into();
+        // Clamp before casting: out of range float to int casts are
+        // undefined behaviour before Rust 1.45.
+        let x = (w - min) * scale;
+        let bin = if x.is_nan() || x <= 0. {
+            0
+        } else if x >= bins as f64 {
+            bins - 1
+        } else {
+            x as usize
+        };
+        counts[bin] += 1;
+    }
+    counts
+}
+
+/// \[Generic\] Return the edge weight at quantile `q` of all edge weights.
+///
+/// This is the smallest weight `w` such that at least a fraction `q` of the
+/// edges have a weight less than or equal to `w` (the *nearest rank*), so
+/// `q = 0.5` gives the median, rounded down to the lower one for an even
+/// number of edges, and `q = 1.0` the maximum.
+///
+/// Return `None` if the graph has no edges.
+///
+/// **Panics** if `q` is not in the range `[0, 1]`.
+///
+/// Computes in **O(|E| log |E|)** time.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::edge_weight_quantile;
+///
+/// let g = Graph::<(), u32>::from_edges(&[(0, 1, 7), (1, 2, 1), (2, 0, 4), (0, 2, 9)]);
+/// assert_eq!(edge_weight_quantile(&g, 0.5), Some(4));
+/// assert_eq!(edge_weight_quantile(&g, 0.9), Some(9));
+/// ```
+pub fn edge_weight_quantile<G>(g: G, q: f64) -> Option<G::EdgeWeight>
+where
+    G: IntoEdgeReferences,
+    G::EdgeWeight: Clone + Ord,
+{
+    assert!(
+        (0. ..=1.).contains(&q),
+        "edge_weight_quantile: q must be in the range [0, 1]"
+    );
+    let mut weights: Vec<_> = g
+        .edge_references()
+        .map(|edge| edge.weight().clone())
+        .collect();
+    if weights.is_empty() {
+        return None;
+    }
+    weights.sort_unstable();
+    let rank = (q * weights.len() as f64).ceil() as usize;
+    Some(weights.swap_remove(rank.max(1) - 1))
+}
+
+/// \[Generic\] Compute the sum of the outgoing edge weights of every node.
+///
+/// Return a map from each node to the sum of the weights of the edges
+/// starting at it, or the default value (zero) for nodes without outgoing
+/// edges. In an undirected graph every edge counts for both of its
+/// endpoints, so this is the same as `in_weight_map`; a self loop counts
+/// once.
+///
+/// Computes in **O(|V| + |E|)** time, in one pass over the edges.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::out_weight_map;
+///
+/// let g = Graph::<(), u32>::from_edges(&[(0, 1, 2), (0, 2, 3), (2, 1, 4)]);
+/// let weights = out_weight_map(&g);
+/// assert_eq!(weights[&0.into()], 5);
+/// assert_eq!(weights[&1.into()], 0);
+/// assert_eq!(weights[&2.into()], 4);
+/// ```
+pub fn out_weight_map<G>(g: G) -> HashMap<G::NodeId, G::EdgeWeight>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + GraphProp,
+    G::NodeId: Eq + Hash,
+    G::EdgeWeight: Default + Add<Output = G::EdgeWeight> + Clone,
+{
+    weight_map(g, Direction::Outgoing)
+}
+
+/// \[Generic\] Compute the sum of the incoming edge weights of every node.
+///
+/// This is the counterpart of `out_weight_map`, summing over the edges
+/// ending at each node instead.
+pub fn in_weight_map<G>(g: G) -> HashMap<G::NodeId, G::EdgeWeight>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + GraphProp,
+    G::NodeId: Eq + Hash,
+    G::EdgeWeight: Default + Add<Output = G::EdgeWeight> + Clone,
+{
+    weight_map(g, Direction::Incoming)
+}
+
+fn weight_map<G>(g: G, dir: Direction) -> HashMap<G::NodeId, G::EdgeWeight>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + GraphProp,
+    G::NodeId: Eq + Hash,
+    G::EdgeWeight: Default + Add<Output = G::EdgeWeight> + Clone,
+{
+    let mut sums: HashMap<_, _> = g
+        .node_identifiers()
+        .map(|n| (n, G::EdgeWeight::default()))
+        .collect();
+    let mut add = |n, w: &G::EdgeWeight| {
+        if let Some(sum) = sums.get_mut(&n) {
+            *sum = sum.clone() + w.clone();
+        }
+    };
+    for edge in g.edge_references() {
+        let (start, end) = match dir {
+            Direction::Outgoing => (edge.source(), edge.target()),
+            Direction::Incoming => (edge.target(), edge.source()),
+        };
+        add(start, edge.weight());
+        if !g.is_directed() && end != start {
+            add(end, edge.weight());
+        }
+    }
+    sums
+}
+
+/// \[Generic\] Pick a random edge, with probability proportional to its
+/// weight.
+///
+/// `rng` is called to draw numbers uniformly distributed in `[0, 1)`, for
+/// example `|| rng.gen::<f64>()` with the `rand` crate; passing a seeded
+/// generator makes the choice reproducible. Edges whose weight is not
+/// positive are never picked.
+///
+/// Return `None` if the graph has no edge with a positive weight.
+///
+/// This uses weighted reservoir sampling: it makes a single pass over the
+/// edges and draws one random number per edge, computing in **O(|E|)** time
+/// and **O(1)** space.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::sample_edge;
+/// use petgraph::visit::EdgeRef;
+///
+/// let g = Graph::<(), f64>::from_edges(&[(0, 1, 0.), (1, 2, 2.5)]);
+/// // cycle through fixed draws in place of a random number generator
+/// let mut draws = [0.2, 0.9, 0.45].iter().cycle();
+/// let mut rng = || *draws.next().unwrap();
+/// let edge = sample_edge(&g, &mut rng).unwrap();
+/// assert_eq!((edge.source().index(), edge.target().index()), (1, 2));
+/// ```
+pub fn sample_edge<G, R>(g: G, rng: &mut R) -> Option<G::EdgeRef>
+where
+    G: IntoEdgeReferences,
+    G::EdgeWeight: Clone + Into<f64>,
+    R: FnMut() -> f64,
+{
+    let mut total = 0.;
+    let mut chosen = None;
+    for edge in g.edge_references() {
+        let w: f64 = edge.weight().clone().into();
+        if w > 0. {
+            total += w;
+            // Replace the current choice with probability w / total, which
+            // leaves every edge seen so far chosen with probability w_i / total.
+            if rng() * total < w {
+                chosen = Some(edge);
+            }
+        }
+    }
+    chosen
+}
+
+/// \[Generic\] Pick a uniformly random neighbor of `n`, as produced by
+/// `neighbors(n)`.
+///
+/// `rng` is called once to draw a number uniformly distributed in `[0, 1)`,
+/// like for `sample_edge`. Every edge counts once, so with parallel edges a
+/// neighbor is picked more often the more edges lead to it.
+///
+/// Return `None` if `n` has no neighbors, or is not part of the graph.
+///
+/// Computes in **O(d)** time, for the number of neighbors **d**.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::random_neighbor;
+///
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (0, 2), (1, 0)]);
+/// let mut draws = [0.25, 0.75].iter().cycle();
+/// let mut rng = || *draws.next().unwrap();
+/// assert!(random_neighbor(&g, 0.into(), &mut rng).is_some());
+/// assert_eq!(random_neighbor(&g, 2.into(), &mut rng), None);
+/// ```
+pub fn random_neighbor<G, R>(g: G, n: G::NodeId, rng: &mut R) -> Option<G::NodeId>
+where
+    G: IntoNeighbors,
+    R: FnMut() -> f64,
+{
+    let count = g.neighbors(n).count();
+    if count == 0 {
+        return None;
+    }
+    g.neighbors(n).nth(random_index(rng, count))
+}
+
+/// \[Generic\] Sample up to `k` of the edges starting at `n`, as produced
+/// by `edges(n)`, uniformly at random without replacement.
+///
+/// Return the target node and a clone of the weight of every sampled edge,
+/// in random order. If `n` has no more than `k` edges, all of them are
+/// returned. Every edge is sampled at most once, but with parallel edges a
+/// neighbor can appear once per edge.
+///
+/// `rng` is called to draw numbers uniformly distributed in `[0, 1)`, like
+/// for `sample_edge`, once per sampled edge.
+///
+/// Computes in **O(d)** time, for the number of edges **d** of `n`.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::sample_neighbors;
+///
+/// let g = Graph::<(), u32>::from_edges(&[(0, 1, 1), (0, 2, 2), (0, 3, 3)]);
+/// let mut draws = [0.6, 0.1, 0.8].iter().cycle();
+/// let mut rng = || *draws.next().unwrap();
+/// assert_eq!(sample_neighbors(&g, 0.into(), 2, &mut rng).len(), 2);
+/// assert_eq!(sample_neighbors(&g, 0.into(), 5, &mut rng).len(), 3);
+/// ```
+pub fn sample_neighbors<G, R>(
+    g: G,
+    n: G::NodeId,
+    k: usize,
+    rng: &mut R,
+) -> Vec<(G::NodeId, G::EdgeWeight)>
+where
+    G: IntoEdges,
+    G::EdgeWeight: Clone,
+    R: FnMut() -> f64,
+{
+    let mut edges: Vec<_> = g.edges(n).collect();
+    let k = k.min(edges.len());
+    // a partial Fisher-Yates shuffle
+    for i in 0..k {
+        let j = i + random_index(rng, edges.len() - i);
+        edges.swap(i, j);
+    }
+    edges
+        .into_iter()
+        .take(k)
+        .map(|edge| (edge.target(), edge.weight().clone()))
+        .collect()
+}
+
+/// \[Generic\] Sample up to `k` of the edges starting at `n` without
+/// replacement, with probabilities proportional to their weights.
+///
+/// This is like `sample_neighbors`, but an edge of weight `w` is picked
+/// next with probability `w / total`, where `total` is the weight of the
+/// edges not sampled yet. Edges whose weight is zero or less are only
+/// returned once every edge of positive weight is, to fill up the sample.
+///
+/// This uses the keys of Efraimidis and Spirakis: it draws one random number
+/// per edge, computing in **O(d log d)** time for the number of edges **d**
+/// of `n`.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::sample_neighbors_weighted;
+///
+/// let g = Graph::<(), f64>::from_edges(&[(0, 1, 0.), (0, 2, 8.), (0, 3, 1.)]);
+/// let mut draws = [0.1, 0.6].iter().cycle();
+/// let mut rng = || *draws.next().unwrap();
+/// let sample = sample_neighbors_weighted(&g, 0.into(), 1, &mut rng);
+/// assert_eq!(sample, vec![(2.into(), 8.)]);
+/// ```
+pub fn sample_neighbors_weighted<G, R>(
+    g: G,
+    n: G::NodeId,
+    k: usize,
+    rng: &mut R,
+) -> Vec<(G::NodeId, G::EdgeWeight)>
+where
+    G: IntoEdges,
+    G::EdgeWeight: Clone + Into<f64>,
+    R: FnMut() -> f64,
+{
+    let mut keyed: Vec<_> = g
+        .edges(n)
+        .map(|edge| {
+            let w: f64 = edge.weight().clone().into();
+            // the largest keys u^(1 / w) form the sample; compare their logarithms
+            let key = if w > 0. {
+                rng().ln() / w
+            } else {
+                std::f64::NEG_INFINITY
+            };
+            (key, edge)
+        })
+        .collect();
+    keyed.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(Ordering::Equal));
+    keyed
+        .into_iter()
+        .take(k)
+        .map(|(_, edge)| (edge.target(), edge.weight().clone()))
+        .collect()
+}
+
+/// \[Generic\] Return the *reciprocity* of a directed graph: the fraction of
+/// its edges `a -> b` for which the edge `b -> a` exists too.
+///
+/// Self loops are ignored, and parallel edges count as one. Return `0.0` if
+/// the graph has no other edges. For an undirected graph, every edge is its
+/// own reverse, so the result is `1.0` unless there are no edges.
+///
+/// Computes in **O(|E|)** time (average).
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::reciprocity;
+///
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 0), (1, 2), (2, 3)]);
+/// assert_eq!(reciprocity(&g), 0.5);
+/// ```
+pub fn reciprocity<G>(g: G) -> f64
+where
+    G: IntoEdgeReferences + GraphProp,
+    G::NodeId: Eq + Hash,
+{
+    let edges: HashSet<_> = g
+        .edge_references()
+        .map(|edge| (edge.source(), edge.target()))
+        .filter(|&(a, b)| a != b)
+        .collect();
+    if edges.is_empty() {
+        return 0.;
+    }
+    if !g.is_directed() {
+        return 1.;
+    }
+    let mutual = edges
+        .iter()
+        .filter(|&&(a, b)| edges.contains(&(b, a)))
+        .count();
+    mutual as f64 / edges.len() as f64
+}
+
+/// \[Generic\] Compute the Newman modularity of a partition of the nodes
+/// into communities.
+///
+/// The graph is treated as undirected, with edge weights given by
+/// `edge_cost`. The modularity is the fraction of the total weight on edges
+/// inside communities, minus the fraction expected if the edges were placed
+/// at random between nodes of the same weighted degrees. It is at most `1`;
+/// higher values mean better defined communities, and `0` is what a random
+/// partition gets on average. Return `0.0` if the total weight is zero.
+///
+/// `community` maps every node to the id of its community.
+///
+/// **Panics** if an endpoint of an edge has no community.
+///
+/// Computes in **O(|E|)** time (average).
+///
+/// # Example
+/// ```rust
+/// use std::collections::HashMap;
+/// use petgraph::Graph;
+/// use petgraph::algo::modularity;
+///
+/// // two triangles joined by an edge
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3), (2, 3)]);
+/// let community: HashMap<_, _> = g.node_indices().map(|n| (n, n.index() / 3)).collect();
+/// let q = modularity(&g, &community, |_| 1.);
+/// assert!((q - 5. / 14.).abs() < 1e-9);
+/// ```
+pub fn modularity<G, F>(g: G, community: &HashMap<G::NodeId, usize>, mut edge_cost: F) -> f64
+where
+    G: IntoEdgeReferences,
+    G::NodeId: Eq + Hash,
+    F: FnMut(G::EdgeRef) -> f64,
+{
+    let community_of = |n: G::NodeId| {
+        *community
+            .get(&n)
+            .expect("modularity: every node needs a community")
+    };
+    let mut total = 0.;
+    let mut internal = 0.;
+    // the sum of the weighted degrees of the nodes of each community
+    let mut degrees = HashMap::new();
+    for edge in g.edge_references() {
+        let w = edge_cost(edge);
+        let (a, b) = (community_of(edge.source()), community_of(edge.target()));
+        total += w;
+        if a == b {
+            internal += w;
+        }
+        *degrees.entry(a).or_insert(0.) += w;
+        *degrees.entry(b).or_insert(0.) += w;
+    }
+    if total == 0. {
+        return 0.;
+    }
+    let expected: f64 = degrees
+        .values()
+        .map(|&d| (d / (2. * total)) * (d / (2. * total)))
+        .sum();
+    internal / total - expected
+}
+
+/// \[Generic\] Return the *degree signature* of `n`: the pair of its number
+/// of incoming and of outgoing edges.
+///
+/// Edges are counted like `neighbors_directed` lists them, so parallel edges
+/// count separately and a self loop counts both ways. For an undirected
+/// graph, both numbers are the degree of `n`.
+///
+/// Computes in **O(d)** time for a node of degree `d`.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::degree_signature;
+///
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (0, 2), (2, 0)]);
+/// assert_eq!(degree_signature(&g, 0.into()), (1, 2));
+/// assert_eq!(degree_signature(&g, 1.into()), (1, 0));
+/// ```
+pub fn degree_signature<G>(g: G, n: G::NodeId) -> (usize, usize)
+where
+    G: IntoNeighborsDirected,
+{
+    (
+        g.neighbors_directed(n, Direction::Incoming).count(),
+        g.neighbors_directed(n, Direction::Outgoing).count(),
+    )
+}
+
+/// \[Generic\] Group the nodes by their degree signature, as given by
+/// `degree_signature`.
+///
+/// Nodes with the same signature are candidates for the same structural
+/// role; this is a cheap first pass, since nodes can share a signature
+/// without being equivalent. The nodes of every group are in the order of
+/// `node_identifiers`.
+///
+/// Computes in **O(|V| + |E|)** time (average).
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::group_by_signature;
+///
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (0, 2), (2, 0)]);
+/// let groups = group_by_signature(&g);
+/// assert_eq!(groups.len(), 3);
+/// assert_eq!(groups[&(1, 1)], vec![2.into()]);
+/// ```
+pub fn group_by_signature<G>(g: G) -> HashMap<(usize, usize), Vec<G::NodeId>>
+where
+    G: IntoNeighborsDirected + IntoNodeIdentifiers,
+{
+    let mut groups = HashMap::new();
+    for n in g.node_identifiers() {
+        groups
+            .entry(degree_signature(g, n))
+            .or_insert_with(Vec::new)
+            .push(n);
+    }
+    groups
+}
@@ -3,7 +3,7 @@ use std::collections::{BinaryHeap, HashMap};
 
 use std::hash::Hash;
 
-use super::visit::{EdgeRef, GraphBase, IntoEdges, Visitable};
+use super::visit::{EdgeRef, GraphBase, IntoEdges, IntoNeighbors, VisitMap, Visitable};
 use crate::scored::MinScored;
 
 use crate::algo::Measure;
@@ -139,6 +139,73 @@ where
     None
 }
 
+/// \[Generic\] Greedy best-first search.
+///
+/// Searches for a path from `start` to a node for which `is_goal` returns
+/// `true`, always expanding next the discovered node with the lowest
+/// `priority`, for example an estimate of its distance to the goal.
+///
+/// The search is purely heuristic: it ignores the cost of the path walked so
+/// far, so unlike `astar` it does **not** guarantee that the path found is
+/// the shortest, or the cheapest by any measure. In exchange it often reaches
+/// the goal after expanding only a few nodes when `priority` is a good guide.
+/// Every node is expanded at most once, so a path is found whenever one
+/// exists.
+///
+/// Returns the path of subsequent `NodeId` from `start` to the goal, if one
+/// was found.
+///
+/// # Example
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::algo::greedy_best_first;
+///
+/// let mut g = Graph::<i32, ()>::new();
+/// let a = g.add_node(3);
+/// let b = g.add_node(2);
+/// let c = g.add_node(5);
+/// let d = g.add_node(0);
+/// g.extend_with_edges(&[(a, b), (a, c), (b, d), (c, d)]);
+///
+/// // the weights are the estimated distances to d
+/// let path = greedy_best_first(&g, a, |n| n == d, |n| g[n]);
+/// assert_eq!(path, Some(vec![a, b, d]));
+/// ```
+pub fn greedy_best_first<G, P, K, IsGoal>(
+    graph: G,
+    start: G::NodeId,
+    mut is_goal: IsGoal,
+    mut priority: P,
+) -> Option<Vec<G::NodeId>>
+where
+    G: IntoNeighbors + Visitable,
+    IsGoal: FnMut(G::NodeId) -> bool,
+    G::NodeId: Eq + Hash,
+    P: FnMut(G::NodeId) -> K,
+    K: PartialOrd,
+{
+    let mut visit_next = BinaryHeap::new();
+    let mut discovered = graph.visit_map();
+    let mut path_tracker = PathTracker::<G>::new();
+
+    discovered.visit(start);
+    visit_next.push(MinScored(priority(start), start));
+
+    while let Some(MinScored(_, node)) = visit_next.pop() {
+        if is_goal(node) {
+            return Some(path_tracker.reconstruct_path_to(node));
+        }
+        for next in graph.neighbors(node) {
+            if discovered.visit(next) {
+                path_tracker.set_predecessor(next, node);
+                visit_next.push(MinScored(priority(next), next));
+            }
+        }
+    }
+
+    None
+}
+
 struct PathTracker<G>
 where
     G: GraphBase,
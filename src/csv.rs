@@ -0,0 +1,189 @@
+//! Simple CSV output of per-node values.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::hash::Hash;
+use std::io::{self, Write};
+
+use crate::visit::{IntoNodeIdentifiers, NodeIndexable};
+
+/// Write a table of node metrics to `w` as CSV, with one row per node and
+/// one column per metric.
+///
+/// The first column, `node`, holds the index of the node
+/// (`NodeIndexable::to_index`), and the other columns are named after the
+/// metrics, in the order of `metrics`. Rows are in the order of
+/// `node_identifiers`. A node missing from a metric map gets an empty cell,
+/// and values of nodes that are not part of the graph are ignored. Names are
+/// quoted if they contain a comma, a quote or a line break.
+///
+/// See [`write_node_metrics_csv_with_labels`](fn.write_node_metrics_csv_with_labels.html)
+/// to write other node labels, like the nodes of a `GraphMap`.
+///
+/// # Example
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use petgraph::Graph;
+/// use petgraph::csv::write_node_metrics_csv;
+///
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1)]);
+/// let degree: HashMap<_, _> = vec![(0.into(), 1.), (1.into(), 1.)].into_iter().collect();
+/// let rank: HashMap<_, _> = vec![(1.into(), 0.75)].into_iter().collect();
+///
+/// let mut out = Vec::new();
+/// write_node_metrics_csv(&g, &[("degree", degree), ("rank", rank)], &mut out).unwrap();
+/// assert_eq!(
+///     String::from_utf8(out).unwrap(),
+///     "node,degree,rank\n0,1,\n1,1,0.75\n"
+/// );
+/// ```
+pub fn write_node_metrics_csv<G, W>(
+    g: G,
+    metrics: &[(&str, HashMap<G::NodeId, f64>)],
+    w: &mut W,
+) -> io::Result<()>
+where
+    G: IntoNodeIdentifiers + NodeIndexable,
+    G::NodeId: Eq + Hash,
+    W: Write,
+{
+    write_node_metrics_csv_with_labels(g, metrics, |node| g.to_index(node), w)
+}
+
+/// Write a table of node metrics to `w` as CSV, like
+/// [`write_node_metrics_csv`](fn.write_node_metrics_csv.html), but with
+/// `node_label(node)` in the `node` column.
+///
+/// Labels are quoted like the metric names, if needed.
+///
+/// # Example
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use petgraph::Graph;
+/// use petgraph::csv::write_node_metrics_csv_with_labels;
+///
+/// let mut g = Graph::<&str, ()>::new();
+/// let a = g.add_node("Ada");
+/// let b = g.add_node("Lovelace, A.");
+/// g.add_edge(a, b, ());
+/// let degree: HashMap<_, _> = vec![(a, 1.), (b, 1.)].into_iter().collect();
+///
+/// let mut out = Vec::new();
+/// write_node_metrics_csv_with_labels(&g, &[("degree", degree)], |n| g[n], &mut out).unwrap();
+/// assert_eq!(
+///     String::from_utf8(out).unwrap(),
+///     "node,degree\nAda,1\n\"Lovelace, A.\",1\n"
+/// );
+/// ```
+pub fn write_node_metrics_csv_with_labels<G, F, L, W>(
+    g: G,
+    metrics: &[(&str, HashMap<G::NodeId, f64>)],
+    mut node_label: F,
+    w: &mut W,
+) -> io::Result<()>
+where
+    G: IntoNodeIdentifiers,
+    G::NodeId: Eq + Hash,
+    F: FnMut(G::NodeId) -> L,
+    L: Display,
+    W: Write,
+{
+    write!(w, "node")?;
+    for &(name, _) in metrics {
+        write!(w, ",")?;
+        write_field(w, name)?;
+    }
+    writeln!(w)?;
+    for node in g.node_identifiers() {
+        write_field(w, &node_label(node).to_string())?;
+        for (_, values) in metrics {
+            match values.get(&node) {
+                Some(value) => write!(w, ",{}", value)?,
+                None => write!(w, ",")?,
+            }
+        }
+        writeln!(w)?;
+    }
+    Ok(())
+}
+
+/// Write `field`, quoted if needed.
+fn write_field<W: Write>(w: &mut W, field: &str) -> io::Result<()> {
+    if field.contains(&[',', '"', '\n', '\r'][..]) {
+        write!(w, "\"{}\"", field.replace('"', "\"\""))
+    } else {
+        write!(w, "{}", field)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{write_node_metrics_csv, write_node_metrics_csv_with_labels};
+    use crate::graph::node_index;
+    use crate::prelude::Graph;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_two_metrics() {
+        let mut g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2)]);
+        let a = g.add_node(());
+        let mut pagerank = HashMap::new();
+        let mut betweenness = HashMap::new();
+        for nx in g.node_indices() {
+            pagerank.insert(nx, 0.25);
+        }
+        betweenness.insert(node_index(1), 2.);
+        betweenness.insert(node_index(2), 0.5);
+        // not part of the graph
+        betweenness.insert(node_index(9), 1.);
+
+        let mut out = Vec::new();
+        write_node_metrics_csv(
+            &g,
+            &[("pagerank", pagerank), ("betweenness", betweenness)],
+            &mut out,
+        )
+        .unwrap();
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<_> = text.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[0], "node,pagerank,betweenness");
+        assert_eq!(lines[1], "0,0.25,");
+        assert_eq!(lines[2], "1,0.25,2");
+        assert_eq!(lines[3], "2,0.25,0.5");
+        assert_eq!(lines[a.index() + 1], "3,0.25,");
+    }
+
+    #[test]
+    fn test_quoted_names() {
+        let g = Graph::<(), ()>::new();
+        let mut out = Vec::new();
+        write_node_metrics_csv(
+            &g,
+            &[("a,b", HashMap::new()), ("say \"hi\"", HashMap::new())],
+            &mut out,
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "node,\"a,b\",\"say \"\"hi\"\"\"\n"
+        );
+    }
+
+    #[test]
+    fn test_labels() {
+        let mut g = Graph::<&str, ()>::new();
+        g.add_node("x");
+        let b = g.add_node("a \"b\"");
+        let mut degree = HashMap::new();
+        degree.insert(b, 2.);
+        let mut out = Vec::new();
+        write_node_metrics_csv_with_labels(&g, &[("degree", degree)], |n| g[n], &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "node,degree\nx,\n\"a \"\"b\"\"\",2\n"
+        );
+    }
+}
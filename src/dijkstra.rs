@@ -1,10 +1,11 @@
 use std::collections::hash_map::Entry::{Occupied, Vacant};
-use std::collections::{BinaryHeap, HashMap};
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
 
 use std::hash::Hash;
 
-use super::visit::{EdgeRef, IntoEdges, VisitMap, Visitable};
+use super::visit::{EdgeRef, IntoEdges, NodeCount, NodeFiltered, VisitMap, Visitable};
 use crate::algo::Measure;
+use crate::astar::astar;
 use crate::scored::MinScored;
 
 /// \[Generic\] Dijkstra's shortest path algorithm.
@@ -120,3 +121,306 @@ where
     }
     scores
 }
+
+/// \[Generic\] Find every shortest path from `start` to `goal`, using
+/// Dijkstra's algorithm.
+///
+/// While exploring the graph, this records *all* predecessors through which a
+/// node is reached at minimal cost, instead of only the first one, and then
+/// enumerates the paths they form. The function `edge_cost` should return the
+/// cost for a particular edge. Edge costs must be non-negative; with zero-cost
+/// edges some of the equally short paths may be missed, but every path
+/// returned is a shortest path.
+///
+/// Return each path as the sequence of its nodes, from `start` to `goal`.
+/// Paths that differ only in which of several parallel edges they use are
+/// reported once per edge. The result is empty if `goal` is not reachable,
+/// and `vec![vec![start]]` if `start` is `goal`.
+///
+/// Finding the predecessors takes **O((|V| + |E|) log |V|)** time, like
+/// `dijkstra`, but the number of shortest paths can grow exponentially with
+/// the size of the graph (for example in a chain of diamonds), and so can
+/// the time and space needed to return them all.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::all_shortest_paths;
+///
+/// // 0 -> 1 -> 3 and 0 -> 2 -> 3 both cost 2; 0 -> 3 costs 3
+/// let g = Graph::<(), u32>::from_edges(&[(0, 1, 1), (1, 3, 1), (0, 2, 1), (2, 3, 1), (0, 3, 3)]);
+/// let mut paths = all_shortest_paths(&g, 0.into(), 3.into(), |e| *e.weight());
+/// paths.sort();
+/// assert_eq!(paths, vec![
+///     vec![0.into(), 1.into(), 3.into()],
+///     vec![0.into(), 2.into(), 3.into()],
+/// ]);
+/// ```
+pub fn all_shortest_paths<G, F, K>(
+    graph: G,
+    start: G::NodeId,
+    goal: G::NodeId,
+    mut edge_cost: F,
+) -> Vec<Vec<G::NodeId>>
+where
+    G: IntoEdges + Visitable,
+    G::NodeId: Eq + Hash,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    let mut visited = graph.visit_map();
+    let mut scores = HashMap::new();
+    let mut predecessors: HashMap<G::NodeId, Vec<G::NodeId>> = HashMap::new();
+    let mut visit_next = BinaryHeap::new();
+    let zero_score = K::default();
+    scores.insert(start, zero_score);
+    visit_next.push(MinScored(zero_score, start));
+    let mut found = false;
+    while let Some(MinScored(node_score, node)) = visit_next.pop() {
+        if visited.is_visited(&node) {
+            continue;
+        }
+        if node == goal {
+            found = true;
+            break;
+        }
+        for edge in graph.edges(node) {
+            let next = edge.target();
+            if visited.is_visited(&next) {
+                continue;
+            }
+            let next_score = node_score + edge_cost(edge);
+            match scores.entry(next) {
+                Occupied(ent) => {
+                    if next_score < *ent.get() {
+                        *ent.into_mut() = next_score;
+                        visit_next.push(MinScored(next_score, next));
+                        predecessors.insert(next, vec![node]);
+                    } else if next_score == *ent.get() {
+                        predecessors.entry(next).or_default().push(node);
+                    }
+                }
+                Vacant(ent) => {
+                    ent.insert(next_score);
+                    visit_next.push(MinScored(next_score, next));
+                    predecessors.insert(next, vec![node]);
+                }
+            }
+        }
+        visited.visit(node);
+    }
+
+    let mut paths = Vec::new();
+    if found {
+        // Walk the predecessors back from the goal, building the paths in reverse.
+        let mut stack = vec![(goal, vec![goal])];
+        while let Some((node, path)) = stack.pop() {
+            if node == start {
+                paths.push(path.into_iter().rev().collect());
+                continue;
+            }
+            for &pred in &predecessors[&node] {
+                let mut path = path.clone();
+                path.push(pred);
+                stack.push((pred, path));
+            }
+        }
+    }
+    paths
+}
+
+/// \[Generic\] Find a shortest path from `start` to `goal` that does not pass
+/// through any of `forbidden_nodes`, using Dijkstra's algorithm.
+///
+/// Only the nodes in between are restricted: `start` and `goal` may be
+/// contained in `forbidden_nodes`, and the path still starts and ends at them.
+/// The function `edge_cost` should return the cost for a particular edge.
+/// Edge costs must be non-negative.
+///
+/// Return the total cost and the path as a sequence of nodes from `start` to
+/// `goal`, or `None` if every path to `goal` is blocked.
+///
+/// # Example
+/// ```rust
+/// use std::collections::HashSet;
+/// use petgraph::Graph;
+/// use petgraph::algo::dijkstra_avoiding;
+///
+/// let g = Graph::<(), u32>::from_edges(&[(0, 1, 1), (1, 3, 1), (0, 2, 5), (2, 3, 5)]);
+/// let forbidden: HashSet<_> = [1.into()].iter().cloned().collect();
+/// let detour = dijkstra_avoiding(&g, 0.into(), 3.into(), |e| *e.weight(), &forbidden);
+/// assert_eq!(detour, Some((10, vec![0.into(), 2.into(), 3.into()])));
+/// ```
+pub fn dijkstra_avoiding<G, F, K>(
+    graph: G,
+    start: G::NodeId,
+    goal: G::NodeId,
+    edge_cost: F,
+    forbidden_nodes: &HashSet<G::NodeId>,
+) -> Option<(K, Vec<G::NodeId>)>
+where
+    G: IntoEdges + Visitable,
+    G::NodeId: Eq + Hash,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    let allowed = NodeFiltered::from_fn(graph, |n| {
+        n == start || n == goal || !forbidden_nodes.contains(&n)
+    });
+    // A* with a zero estimate is Dijkstra's algorithm, keeping track of the path.
+    astar(&allowed, start, |n| n == goal, edge_cost, |_| K::default())
+}
+
+/// \[Generic\] Find every node that can be reached from `start` by a path
+/// whose total cost is at most `budget`, using Dijkstra's algorithm.
+///
+/// The function `edge_cost` should return the cost for a particular edge.
+/// Edge costs must be non-negative. Paths are not expanded beyond the
+/// budget, so only the part of the graph within it is explored. `start` is
+/// always included.
+///
+/// Computes in **O((|V| + |E|) log |V|)** time at most, counting the nodes
+/// and edges within the budget.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::reachable_within_budget;
+///
+/// let g = Graph::<(), u32>::from_edges(&[(0, 1, 2), (1, 2, 2), (0, 2, 5)]);
+/// let reached = reachable_within_budget(&g, 0.into(), 3, |e| *e.weight());
+/// assert_eq!(reached.len(), 2);
+/// assert!(!reached.contains(&2.into()));
+/// ```
+pub fn reachable_within_budget<G, F, K>(
+    graph: G,
+    start: G::NodeId,
+    budget: K,
+    mut edge_cost: F,
+) -> HashSet<G::NodeId>
+where
+    G: IntoEdges + Visitable,
+    G::NodeId: Eq + Hash,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    let mut visited = graph.visit_map();
+    let mut scores = HashMap::new();
+    let mut reached = HashSet::new();
+    let mut visit_next = BinaryHeap::new();
+    let zero_score = K::default();
+    scores.insert(start, zero_score);
+    visit_next.push(MinScored(zero_score, start));
+    while let Some(MinScored(node_score, node)) = visit_next.pop() {
+        if !visited.visit(node) {
+            continue;
+        }
+        reached.insert(node);
+        for edge in graph.edges(node) {
+            let next = edge.target();
+            if visited.is_visited(&next) {
+                continue;
+            }
+            let next_score = node_score + edge_cost(edge);
+            if next_score > budget {
+                continue;
+            }
+            match scores.entry(next) {
+                Occupied(ent) => {
+                    if next_score < *ent.get() {
+                        *ent.into_mut() = next_score;
+                        visit_next.push(MinScored(next_score, next));
+                    }
+                }
+                Vacant(ent) => {
+                    ent.insert(next_score);
+                    visit_next.push(MinScored(next_score, next));
+                }
+            }
+        }
+    }
+    reached
+}
+
+/// \[Generic\] Compute the length of the shortest path from `start` to every
+/// reachable node, using the Shortest Path Faster Algorithm (SPFA), a queue
+/// based variant of the Bellman-Ford algorithm.
+///
+/// The function `edge_cost` should return the cost for a particular edge
+/// and may be called several times for the same edge. Unlike with
+/// `dijkstra`, edge costs may be negative.
+///
+/// Return a map from every node reachable from `start` to the cost of the
+/// shortest path to it, or `Err(node)` if a cycle of negative total cost can
+/// be reached from `start`. The `node` is then one whose path cost can be
+/// lowered indefinitely, by going around the cycle.
+///
+/// Only nodes whose cost was lowered are processed again, which makes this
+/// much faster than **O(|V| |E|)** on typical inputs; that remains the worst
+/// case.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::algo::spfa;
+///
+/// let g = Graph::<(), i32>::from_edges(&[(0, 1, 4), (0, 2, 1), (2, 1, -2)]);
+/// let costs = spfa(&g, 0.into(), |e| *e.weight()).unwrap();
+/// assert_eq!(costs[&1.into()], -1);
+///
+/// let g = Graph::<(), i32>::from_edges(&[(0, 1, 1), (1, 2, -2), (2, 1, 1)]);
+/// assert!(spfa(&g, 0.into(), |e| *e.weight()).is_err());
+/// ```
+pub fn spfa<G, F, K>(
+    graph: G,
+    start: G::NodeId,
+    mut edge_cost: F,
+) -> Result<HashMap<G::NodeId, K>, G::NodeId>
+where
+    G: IntoEdges + NodeCount,
+    G::NodeId: Eq + Hash,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    let node_count = graph.node_count();
+    let mut scores = HashMap::new();
+    // the number of edges of the best path found so far to each node
+    let mut path_len = HashMap::new();
+    let mut queued = HashSet::new();
+    let mut queue = VecDeque::new();
+    scores.insert(start, K::default());
+    path_len.insert(start, 0);
+    queued.insert(start);
+    queue.push_back(start);
+    while let Some(node) = queue.pop_front() {
+        queued.remove(&node);
+        let node_score = scores[&node];
+        let node_len = path_len[&node];
+        for edge in graph.edges(node) {
+            let next = edge.target();
+            let next_score = node_score + edge_cost(edge);
+            match scores.entry(next) {
+                Occupied(ent) => {
+                    if next_score < *ent.get() {
+                        *ent.into_mut() = next_score;
+                    } else {
+                        continue;
+                    }
+                }
+                Vacant(ent) => {
+                    ent.insert(next_score);
+                }
+            }
+            // A path of |V| edges repeats a node, and it only got cheaper
+            // than the shorter paths by going around a negative cycle.
+            if node_len + 1 >= node_count {
+                return Err(next);
+            }
+            path_len.insert(next, node_len + 1);
+            if queued.insert(next) {
+                queue.push_back(next);
+            }
+        }
+    }
+    Ok(scores)
+}
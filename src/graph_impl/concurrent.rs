@@ -0,0 +1,139 @@
+use std::sync::{Mutex, MutexGuard};
+
+use super::{DefaultIx, DiGraph, Graph, IndexType, NodeIndex};
+
+/// `ConcurrentBuilder` collects the edges of a directed graph from several
+/// threads at once, and merges them into a `DiGraph` at `.build()`.
+///
+/// The edges are sharded by their source node, and every shard has a lock of
+/// its own, so threads adding edges from sources in different shards do not
+/// wait for each other. `add_edge` only needs a shared reference, so the builder can
+/// be shared between threads with a reference or an `Arc`.
+///
+/// Nodes are identified by their index: the built graph has a node for every
+/// index up to the largest one used by an edge, with the default weight.
+///
+/// **Note:** The order of the edges in the built graph is not deterministic.
+/// Edges are grouped by shard, and within a shard the edges appear in the
+/// order their threads took its lock, so edge indices, and the order of the
+/// neighbors of a node, may differ from one run to the next. Edges added by
+/// the same thread for the same source node keep their relative order.
+///
+/// ```
+/// use std::sync::Arc;
+/// use std::thread;
+/// use petgraph::graph::{ConcurrentBuilder, DiGraph};
+///
+/// let builder = Arc::new(ConcurrentBuilder::<u32>::new(4));
+/// let threads: Vec<_> = (0..4u32)
+///     .map(|t| {
+///         let builder = builder.clone();
+///         thread::spawn(move || {
+///             for i in t * 10..t * 10 + 10 {
+///                 builder.add_edge(i.into(), (i + 1).into(), i);
+///             }
+///         })
+///     })
+///     .collect();
+/// for thread in threads {
+///     thread.join().unwrap();
+/// }
+///
+/// let builder = Arc::try_unwrap(builder).ok().unwrap();
+/// let g: DiGraph<(), u32> = builder.build();
+/// assert_eq!(g.node_count(), 41);
+/// assert_eq!(g.edge_count(), 40);
+/// ```
+pub struct ConcurrentBuilder<E, Ix = DefaultIx> {
+    shards: Vec<Mutex<Shard<E, Ix>>>,
+}
+
+/// The edges of a shard, in the order they were added.
+type Shard<E, Ix> = Vec<(NodeIndex<Ix>, NodeIndex<Ix>, E)>;
+
+impl<E, Ix> ConcurrentBuilder<E, Ix>
+where
+    Ix: IndexType,
+{
+    /// Create a new `ConcurrentBuilder` with `shards` separately locked
+    /// shards.
+    ///
+    /// **Panics** if `shards` is `0`.
+    pub fn new(shards: usize) -> Self {
+        assert!(
+            shards > 0,
+            "ConcurrentBuilder: there must be at least one shard"
+        );
+        ConcurrentBuilder {
+            shards: (0..shards).map(|_| Mutex::new(Vec::new())).collect(),
+        }
+    }
+
+    /// Add an edge from `a` to `b` with associated data `weight`.
+    ///
+    /// Only the shard of `a` is locked while the edge is added.
+    pub fn add_edge(&self, a: NodeIndex<Ix>, b: NodeIndex<Ix>, weight: E) {
+        self.shard(a).push((a, b, weight));
+    }
+
+    /// Add all the edges of `iterable`, locking the shard of each source
+    /// node once per edge.
+    pub fn extend_with_edges<I>(&self, iterable: I)
+    where
+        I: IntoIterator<Item = (NodeIndex<Ix>, NodeIndex<Ix>, E)>,
+    {
+        for (a, b, weight) in iterable {
+            self.add_edge(a, b, weight);
+        }
+    }
+
+    /// Return the number of edges added so far.
+    ///
+    /// Edges added by other threads at the same time may or may not be
+    /// counted.
+    pub fn edge_count(&self) -> usize {
+        self.shards.iter().map(|shard| lock(shard).len()).sum()
+    }
+
+    /// Merge the collected edges into a `DiGraph`.
+    ///
+    /// Node weights are `N::default()`. See the type documentation for the
+    /// order of the edges.
+    ///
+    /// **Panics** if the number of nodes or edges overflows the index type.
+    pub fn build<N>(self) -> DiGraph<N, E, Ix>
+    where
+        N: Default,
+    {
+        let shards: Vec<_> = self
+            .shards
+            .into_iter()
+            .map(|shard| shard.into_inner().unwrap_or_else(|e| e.into_inner()))
+            .collect();
+        let edge_count = shards.iter().map(Vec::len).sum();
+        let node_bound = shards
+            .iter()
+            .flat_map(|edges| edges.iter())
+            .map(|&(a, b, _)| a.index().max(b.index()) + 1)
+            .max()
+            .unwrap_or(0);
+        let mut g = Graph::with_capacity(node_bound, edge_count);
+        for _ in 0..node_bound {
+            g.add_node(N::default());
+        }
+        for (a, b, weight) in shards.into_iter().flatten() {
+            g.add_edge(a, b, weight);
+        }
+        g
+    }
+
+    fn shard(&self, a: NodeIndex<Ix>) -> MutexGuard<'_, Shard<E, Ix>> {
+        lock(&self.shards[a.index() % self.shards.len()])
+    }
+}
+
+/// Lock `shard`, ignoring poisoning: a thread that panicked while holding the
+/// lock can not have left a half pushed edge behind.
+fn lock<T>(shard: &Mutex<T>) -> MutexGuard<'_, T> {
+    shard.lock().unwrap_or_else(|e| e.into_inner())
+}
@@ -1,4 +1,6 @@
 use std::cmp;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
 use std::fmt;
 use std::hash::Hash;
 use std::iter;
@@ -11,7 +13,7 @@ use crate::{Directed, Direction, EdgeType, Incoming, IntoWeightedEdge, Outgoing,
 
 use crate::iter_format::{DebugMap, IterFormatExt, NoPretty};
 
-use crate::util::enumerate;
+use crate::util::{enumerate, random_index};
 use crate::visit::EdgeRef;
 use crate::visit::{IntoEdges, IntoEdgesDirected, IntoNodeReferences};
 
@@ -281,7 +283,10 @@ impl<E, Ix: IndexType> Edge<E, Ix> {
 /// - Index type `Ix`, which determines the maximum size of the graph.
 ///
 /// The `Graph` is a regular Rust collection and is `Send` and `Sync` (as long
-/// as associated data `N` and `E` are).
+/// as associated data `N` and `E` are). A graph can thus be shared between
+/// threads for reading, for example in an `Arc`; to build a graph from
+/// several threads at once, see
+/// [`ConcurrentBuilder`](struct.ConcurrentBuilder.html).
 ///
 /// The graph uses **O(|V| + |E|)** space, and allows fast node and edge insert,
 /// efficient graph search and graph algorithms.
@@ -1149,6 +1154,64 @@ where
         }
     }
 
+    /// Randomize the edges with up to `swaps` double edge swaps, keeping the
+    /// degree of every node.
+    ///
+    /// Each swap picks two edges `a → b` and `c → d` and replaces them with
+    /// `a → d` and `c → b`, keeping the edge indices and weights, so the
+    /// in- and out-degree of every node are preserved. A swap is skipped if
+    /// it would create a self loop or an edge that already exists, or if the
+    /// edges share their source or target. Return the number of swaps that
+    /// were performed.
+    ///
+    /// `rng` must return numbers uniformly distributed in `[0, 1)`. The order
+    /// of the edges of a node may change.
+    ///
+    /// Computes in **O(swaps · e')** time, where **e'** is the size of the
+    /// edge lists of the four nodes involved in a swap.
+    pub fn rewire_edges<R>(&mut self, swaps: usize, rng: &mut R) -> usize
+    where
+        R: FnMut() -> f64,
+    {
+        let m = self.edge_count();
+        if m < 2 {
+            return 0;
+        }
+        let mut pick = || EdgeIndex::new(random_index(rng, m));
+        let mut performed = 0;
+        for _ in 0..swaps {
+            let (e, f) = (pick(), pick());
+            let [a, b] = self.edges[e.index()].node;
+            let [c, d] = self.edges[f.index()].node;
+            if a == c || b == d || a == d || c == b {
+                continue;
+            }
+            if self.contains_edge(a, d) || self.contains_edge(c, b) {
+                continue;
+            }
+            self.retarget_edge(e, d);
+            self.retarget_edge(f, b);
+            performed += 1;
+        }
+        performed
+    }
+
+    /// Make `t` the target of the edge `e`, by moving it from the incoming
+    /// list of its old target to the front of the incoming list of `t`.
+    fn retarget_edge(&mut self, e: EdgeIndex<Ix>, t: NodeIndex<Ix>) {
+        let (edge_node, edge_next) = {
+            let edge = &self.edges[e.index()];
+            (edge.node, edge.next)
+        };
+        self.change_edge_links(edge_node, e, edge_next);
+        let s = edge_node[0];
+        let edge = &mut self.edges[e.index()];
+        edge.node[1] = t;
+        edge.next = [self.nodes[s.index()].next[0], self.nodes[t.index()].next[1]];
+        self.nodes[s.index()].next[0] = e;
+        self.nodes[t.index()].next[1] = e;
+    }
+
     /// Remove all nodes and edges
     pub fn clear(&mut self) {
         self.nodes.clear();
@@ -1262,6 +1325,34 @@ where
         }
     }
 
+    /// Remove all self loops, and return their nodes and weights, in the
+    /// order of their edge indices.
+    ///
+    /// Like `remove_edge`, this changes the indices of some of the edges
+    /// that remain.
+    ///
+    /// ```
+    /// use petgraph::Graph;
+    ///
+    /// let mut g = Graph::<(), char>::from_edges(&[(0, 0, 'a'), (0, 1, 'b'), (1, 1, 'c')]);
+    /// assert_eq!(g.remove_self_loops(), vec![(0.into(), 'a'), (1.into(), 'c')]);
+    /// assert_eq!(g.edge_count(), 1);
+    /// ```
+    pub fn remove_self_loops(&mut self) -> Vec<(NodeIndex<Ix>, E)> {
+        let mut removed = Vec::new();
+        // Removing an edge moves the last edge into its place, which has
+        // been checked already.
+        for index in self.edge_indices().rev() {
+            let [a, b] = self.edges[index.index()].node;
+            if a == b {
+                let weight = self.remove_edge(index).unwrap();
+                removed.push((a, weight));
+            }
+        }
+        removed.reverse();
+        removed
+    }
+
     /// Create a new `Graph` from an iterable of edges.
     ///
     /// Node weights `N` are set to default values.
@@ -1291,6 +1382,101 @@ where
         g
     }
 
+    /// Create a new `Graph` from an iterable of edges, merging the weights
+    /// of duplicate edges.
+    ///
+    /// Like `from_edges`, except that when an edge connecting the same pair
+    /// of nodes appears again, no parallel edge is added: the weight is
+    /// replaced by `combine(existing_weight, new_weight)`. Edges are added
+    /// in the order of their first occurrence.
+    ///
+    /// For an undirected graph, `(a, b)` and `(b, a)` are the same edge.
+    ///
+    /// ```
+    /// use petgraph::graph::{DiGraph, NodeIndex};
+    ///
+    /// let gr = DiGraph::<(), i32>::from_edges_combining(
+    ///     &[(0, 1, 1), (1, 2, 5), (0, 1, 2)],
+    ///     |a, b| a + b,
+    /// );
+    /// assert_eq!(gr.edge_count(), 2);
+    /// let ab = gr.find_edge(NodeIndex::new(0), NodeIndex::new(1)).unwrap();
+    /// assert_eq!(gr[ab], 3);
+    /// ```
+    pub fn from_edges_combining<I, F>(iterable: I, mut combine: F) -> Self
+    where
+        I: IntoIterator,
+        I::Item: IntoWeightedEdge<E>,
+        <I::Item as IntoWeightedEdge<E>>::NodeId: Into<NodeIndex<Ix>>,
+        N: Default,
+        F: FnMut(E, E) -> E,
+    {
+        let mut position: HashMap<_, usize> = HashMap::new();
+        let mut edges: Vec<(NodeIndex<Ix>, NodeIndex<Ix>, Option<E>)> = Vec::new();
+        for elt in iterable {
+            let (source, target, weight) = elt.into_weighted_edge();
+            let (source, target): (NodeIndex<Ix>, NodeIndex<Ix>) = (source.into(), target.into());
+            let key = if Ty::is_directed() {
+                (source, target)
+            } else {
+                (cmp::min(source, target), cmp::max(source, target))
+            };
+            match position.entry(key) {
+                Entry::Occupied(ent) => {
+                    let old = &mut edges[*ent.get()].2;
+                    *old = old.take().map(|old| combine(old, weight));
+                }
+                Entry::Vacant(ent) => {
+                    ent.insert(edges.len());
+                    edges.push((source, target, Some(weight)));
+                }
+            }
+        }
+        let mut g = Self::with_capacity(0, edges.len());
+        g.extend_with_edges(
+            edges
+                .into_iter()
+                .map(|(source, target, weight)| (source, target, weight.unwrap())),
+        );
+        g
+    }
+
+    /// Create a new `Graph` from an iterable of edges and an iterable of
+    /// node weights.
+    ///
+    /// All nodes are added first, in order, so the *i*th node weight gets
+    /// index *i*; then the edges are added between them. Unlike `from_edges`,
+    /// this keeps nodes without any edges, and does not require `N: Default`.
+    /// Edge weights `E` may either be specified in the list, or they are
+    /// filled with default values.
+    ///
+    /// **Panics** if an edge refers to a node index beyond the node weights.
+    ///
+    /// ```
+    /// use petgraph::graph::DiGraph;
+    ///
+    /// let gr = DiGraph::<&str, ()>::from_edges_and_nodes(&[(0, 1)], vec!["a", "b", "c"]);
+    /// assert_eq!(gr.node_count(), 3);
+    /// assert_eq!(gr.edge_count(), 1);
+    /// ```
+    pub fn from_edges_and_nodes<EI, NI>(edges: EI, nodes: NI) -> Self
+    where
+        EI: IntoIterator,
+        EI::Item: IntoWeightedEdge<E>,
+        <EI::Item as IntoWeightedEdge<E>>::NodeId: Into<NodeIndex<Ix>>,
+        NI: IntoIterator<Item = N>,
+    {
+        let mut g = Self::with_capacity(0, 0);
+        for weight in nodes {
+            g.add_node(weight);
+        }
+        for elt in edges {
+            let (source, target, weight) = elt.into_weighted_edge();
+            g.add_edge(source.into(), target.into(), weight);
+        }
+        g
+    }
+
     /// Extend the graph from an iterable of edges.
     ///
     /// Node weights `N` are set to default values.
@@ -1347,6 +1533,29 @@ where
         g
     }
 
+    /// Create a new `Graph` with the same nodes and structure as `self`, and
+    /// the edge weights converted by `f`.
+    ///
+    /// This is `map` with the node weights cloned, for the common case of
+    /// changing only the edge weight type. The result has the same graph
+    /// indices as `self`.
+    ///
+    /// ```
+    /// use petgraph::graph::{DiGraph, EdgeIndex};
+    ///
+    /// let g = DiGraph::<(), i32>::from_edges(&[(0, 1, 3), (1, 2, -2)]);
+    /// let h = g.cast_weights(|&w| f64::from(w) / 2.);
+    /// assert_eq!(h[EdgeIndex::new(0)], 1.5);
+    /// assert_eq!(h[EdgeIndex::new(1)], -1.);
+    /// ```
+    pub fn cast_weights<E2, F>(&self, mut f: F) -> Graph<N, E2, Ty, Ix>
+    where
+        N: Clone,
+        F: FnMut(&E) -> E2,
+    {
+        self.map(|_, weight| weight.clone(), |_, weight| f(weight))
+    }
+
     /// Create a new `Graph` by mapping nodes and edges.
     /// A node or edge may be mapped to `None` to exclude it from
     /// the resulting graph.
@@ -1389,6 +1598,82 @@ where
         g
     }
 
+    /// Create a new `Graph` by collapsing nodes that map to the same key.
+    ///
+    /// Each node `a` is mapped to `key(&self[a])`; nodes with equal keys
+    /// become a single node whose weight is the key. New nodes are added in
+    /// the order of first occurrence of their key. Every edge is moved to run
+    /// between the collapsed nodes of its endpoints; when several edges end up
+    /// connecting the same pair, they are merged into one by
+    /// `combine_edge(existing_weight, new_weight)`, in edge index order.
+    /// Edges within one group become self loops, which may be removed
+    /// afterwards with `retain_edges` if they are not wanted.
+    ///
+    /// For an undirected graph, `(a, b)` and `(b, a)` are the same edge.
+    ///
+    /// Computes in **O(|V| + |E|)** time.
+    ///
+    /// ```
+    /// use petgraph::graph::{DiGraph, NodeIndex};
+    ///
+    /// let gr = DiGraph::<_, i32>::from_edges_and_nodes(
+    ///     &[(0, 1, 1), (1, 2, 2), (0, 2, 3)],
+    ///     vec![("a", 0), ("a", 1), ("b", 0)],
+    /// );
+    /// let coarse = gr.coarsen_by(|&(group, _)| group, |x, y| x + y);
+    /// assert_eq!(coarse.node_count(), 2);
+    /// let ab = coarse.find_edge(NodeIndex::new(0), NodeIndex::new(1)).unwrap();
+    /// assert_eq!(coarse[ab], 5);
+    /// ```
+    pub fn coarsen_by<K, FK, FE>(&self, mut key: FK, mut combine_edge: FE) -> Graph<K, E, Ty, Ix>
+    where
+        K: Eq + Hash + Clone,
+        E: Clone,
+        FK: FnMut(&N) -> K,
+        FE: FnMut(E, E) -> E,
+    {
+        let mut g = Graph::with_capacity(0, 0);
+        let mut group_index: HashMap<K, NodeIndex<Ix>> = HashMap::new();
+        let node_group: Vec<NodeIndex<Ix>> = self
+            .nodes
+            .iter()
+            .map(|node| match group_index.entry(key(&node.weight)) {
+                Entry::Occupied(ent) => *ent.get(),
+                Entry::Vacant(ent) => {
+                    let ix = g.add_node(ent.key().clone());
+                    *ent.insert(ix)
+                }
+            })
+            .collect();
+        let mut position: HashMap<_, usize> = HashMap::new();
+        let mut edges: Vec<(NodeIndex<Ix>, NodeIndex<Ix>, Option<E>)> = Vec::new();
+        for edge in &self.edges {
+            let source = node_group[edge.source().index()];
+            let target = node_group[edge.target().index()];
+            let pair = if Ty::is_directed() {
+                (source, target)
+            } else {
+                (cmp::min(source, target), cmp::max(source, target))
+            };
+            let weight = edge.weight.clone();
+            match position.entry(pair) {
+                Entry::Occupied(ent) => {
+                    let old = &mut edges[*ent.get()].2;
+                    *old = old.take().map(|old| combine_edge(old, weight));
+                }
+                Entry::Vacant(ent) => {
+                    ent.insert(edges.len());
+                    edges.push((source, target, Some(weight)));
+                }
+            }
+        }
+        g.reserve_edges(edges.len());
+        for (source, target, weight) in edges {
+            g.add_edge(source, target, weight.unwrap());
+        }
+        g
+    }
+
     /// Convert the graph into either undirected or directed. No edge adjustments
     /// are done, so you may want to go over the result to remove or add edges.
     ///
@@ -2167,9 +2452,14 @@ where
 
 impl<'a, E, Ix> ExactSizeIterator for EdgeReferences<'a, E, Ix> where Ix: IndexType {}
 
+mod concurrent;
 mod frozen;
 #[cfg(feature = "stable_graph")]
 pub mod stable_graph;
+mod versioned;
+
+pub use self::concurrent::ConcurrentBuilder;
+pub use self::versioned::Versioned;
 
 /// `Frozen` is a graph wrapper.
 ///
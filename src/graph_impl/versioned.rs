@@ -0,0 +1,167 @@
+use std::fmt;
+use std::ops::Deref;
+
+use super::{DefaultIx, EdgeIndex, Graph, IndexType, NodeIndex};
+use crate::{Directed, EdgeType};
+
+/// `Versioned` is a graph wrapper that counts changes to the graph.
+///
+/// The version starts at `0` and is incremented by every mutation made
+/// through the wrapper; read-only access, through `Deref` to the underlying
+/// `Graph`, leaves it unchanged. A cached analysis of the graph can store the
+/// version it was computed at, and compare it with `.version()` to tell if it
+/// must be recomputed, without comparing the graphs themselves.
+///
+/// Operations that turn out not to change anything, like removing a node that
+/// does not exist, do not increment the version. `graph_mut` always does,
+/// since the changes made through it can not be observed.
+///
+/// ```
+/// use petgraph::graph::{DiGraph, Versioned};
+///
+/// let mut g = Versioned::new(DiGraph::<&str, ()>::new());
+/// let a = g.add_node("a");
+/// let b = g.add_node("b");
+/// assert_eq!(g.version(), 2);
+///
+/// let cached = (g.version(), g.edge_count());
+/// g.add_edge(a, b, ());
+/// assert_ne!(cached.0, g.version());
+/// ```
+pub struct Versioned<N, E, Ty = Directed, Ix = DefaultIx> {
+    graph: Graph<N, E, Ty, Ix>,
+    version: u64,
+}
+
+impl<N, E, Ty, Ix> Versioned<N, E, Ty, Ix>
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    /// Create a new `Versioned` wrapping `graph`, at version `0`.
+    pub fn new(graph: Graph<N, E, Ty, Ix>) -> Self {
+        Versioned { graph, version: 0 }
+    }
+
+    /// Return the number of changes made to the graph.
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// Return the underlying graph.
+    pub fn into_inner(self) -> Graph<N, E, Ty, Ix> {
+        self.graph
+    }
+
+    /// Return a mutable reference to the underlying graph, incrementing the
+    /// version.
+    pub fn graph_mut(&mut self) -> &mut Graph<N, E, Ty, Ix> {
+        self.bump();
+        &mut self.graph
+    }
+
+    /// Add a node, like `Graph::add_node`.
+    pub fn add_node(&mut self, weight: N) -> NodeIndex<Ix> {
+        self.bump();
+        self.graph.add_node(weight)
+    }
+
+    /// Add an edge, like `Graph::add_edge`.
+    pub fn add_edge(&mut self, a: NodeIndex<Ix>, b: NodeIndex<Ix>, weight: E) -> EdgeIndex<Ix> {
+        self.bump();
+        self.graph.add_edge(a, b, weight)
+    }
+
+    /// Add or update an edge, like `Graph::update_edge`.
+    pub fn update_edge(&mut self, a: NodeIndex<Ix>, b: NodeIndex<Ix>, weight: E) -> EdgeIndex<Ix> {
+        self.bump();
+        self.graph.update_edge(a, b, weight)
+    }
+
+    /// Remove a node, like `Graph::remove_node`.
+    pub fn remove_node(&mut self, a: NodeIndex<Ix>) -> Option<N> {
+        let removed = self.graph.remove_node(a);
+        if removed.is_some() {
+            self.bump();
+        }
+        removed
+    }
+
+    /// Remove an edge, like `Graph::remove_edge`.
+    pub fn remove_edge(&mut self, e: EdgeIndex<Ix>) -> Option<E> {
+        let removed = self.graph.remove_edge(e);
+        if removed.is_some() {
+            self.bump();
+        }
+        removed
+    }
+
+    /// Reverse the direction of all edges, like `Graph::reverse`.
+    pub fn reverse(&mut self) {
+        self.bump();
+        self.graph.reverse();
+    }
+
+    /// Remove all nodes and edges, like `Graph::clear`.
+    pub fn clear(&mut self) {
+        self.bump();
+        self.graph.clear();
+    }
+
+    /// Remove all edges, like `Graph::clear_edges`.
+    pub fn clear_edges(&mut self) {
+        self.bump();
+        self.graph.clear_edges();
+    }
+
+    fn bump(&mut self) {
+        self.version += 1;
+    }
+}
+
+impl<N, E, Ty, Ix: IndexType> Clone for Versioned<N, E, Ty, Ix>
+where
+    N: Clone,
+    E: Clone,
+{
+    fn clone(&self) -> Self {
+        Versioned {
+            graph: self.graph.clone(),
+            version: self.version,
+        }
+    }
+}
+
+impl<N, E, Ty, Ix> fmt::Debug for Versioned<N, E, Ty, Ix>
+where
+    N: fmt::Debug,
+    E: fmt::Debug,
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Versioned")
+            .field("version", &self.version)
+            .field("graph", &self.graph)
+            .finish()
+    }
+}
+
+/// Deref allows transparent access to all shared reference (read-only)
+/// functionality in the underlying graph.
+impl<N, E, Ty, Ix> Deref for Versioned<N, E, Ty, Ix> {
+    type Target = Graph<N, E, Ty, Ix>;
+    fn deref(&self) -> &Graph<N, E, Ty, Ix> {
+        &self.graph
+    }
+}
+
+impl<N, E, Ty, Ix> From<Graph<N, E, Ty, Ix>> for Versioned<N, E, Ty, Ix>
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    fn from(graph: Graph<N, E, Ty, Ix>) -> Self {
+        Versioned::new(graph)
+    }
+}
@@ -3,20 +3,26 @@
 
 use indexmap::map::Keys;
 use indexmap::map::{Iter as IndexMapIter, IterMut as IndexMapIterMut};
-use indexmap::IndexMap;
-use std::cmp::Ordering;
+use indexmap::{IndexMap, IndexSet};
+use std::cmp::{Ordering, Reverse};
+use std::collections::{HashMap, HashSet};
 use std::fmt;
-use std::hash::{self, Hash};
+use std::hash::{self, BuildHasher, Hash};
+use std::io;
 use std::iter::FromIterator;
-use std::iter::{Cloned, DoubleEndedIterator};
+use std::iter::{Cloned, DoubleEndedIterator, Rev};
 use std::marker::PhantomData;
+use std::mem;
 use std::ops::{Deref, Index, IndexMut};
 use std::slice::Iter;
+use std::str::FromStr;
+use std::vec;
 
 use crate::{Directed, Direction, EdgeType, Incoming, Outgoing, Undirected};
 
 use crate::graph::node_index;
 use crate::graph::Graph;
+use crate::unionfind::UnionFind;
 use crate::visit::{IntoEdgeReferences, IntoEdges, NodeCompactIndexable};
 use crate::visit::{IntoNodeIdentifiers, IntoNodeReferences, NodeCount, NodeIndexable};
 use crate::IntoWeightedEdge;
@@ -55,12 +61,21 @@ pub type DiGraphMap<N, E> = GraphMap<N, E, Directed>;
 ///
 /// `GraphMap` does not allow parallel edges, but self loops are allowed.
 ///
+/// Nodes and edges are stored in insertion-ordered hash maps, so iteration
+/// is deterministic: it follows the order in which nodes and edges were
+/// added, independently of their hash values, until something is removed
+/// (see [`set_stable_removal`](#method.set_stable_removal) and
+/// [`set_edge_order_tracking`](#method.set_edge_order_tracking)).
+///
 /// Depends on crate feature `graphmap` (default).
 #[derive(Clone)]
 pub struct GraphMap<N, E, Ty> {
     nodes: IndexMap<N, Vec<(N, CompactDirection)>>,
     edges: IndexMap<(N, N), E>,
+    // only kept with `set_edge_order_tracking(true)`
+    edge_order: Option<EdgeOrder<N>>,
     ty: PhantomData<Ty>,
+    stable_removal: bool,
 }
 
 impl<N: Eq + Hash + fmt::Debug, E: fmt::Debug, Ty: EdgeType> fmt::Debug for GraphMap<N, E, Ty> {
@@ -69,6 +84,17 @@ impl<N: Eq + Hash + fmt::Debug, E: fmt::Debug, Ty: EdgeType> fmt::Debug for Grap
     }
 }
 
+/// The insertion sequence numbers of the edges of a `GraphMap`.
+#[derive(Clone)]
+struct EdgeOrder<N> {
+    // the sequence number of every edge; it is never reused, so it also
+    // serves as the stable `EdgeId`
+    seq: HashMap<(N, N), u64>,
+    // the edge of every sequence number, for the `EdgeId` lookups
+    edges: HashMap<u64, (N, N)>,
+    next: u64,
+}
+
 /// A trait group for `GraphMap`'s node identifier.
 pub trait NodeTrait: Copy + Ord + Hash {}
 impl<N> NodeTrait for N where N: Copy + Ord + Hash {}
@@ -110,15 +136,111 @@ where
         GraphMap {
             nodes: IndexMap::with_capacity(nodes),
             edges: IndexMap::with_capacity(edges),
+            edge_order: None,
             ty: PhantomData,
+            stable_removal: false,
+        }
+    }
+
+    /// Choose how removals affect the iteration order of the graph.
+    ///
+    /// By default (`false`), `remove_node` and `remove_edge` fill the hole
+    /// they leave by moving the last element into it, in **O(1)** time. This
+    /// reorders the nodes, edges and neighbor lists: for example, the most
+    /// recently added neighbor of a node can jump to the position of a removed
+    /// one.
+    ///
+    /// With `stable` set to `true`, removals shift the following elements
+    /// instead, so the relative order of everything that remains is
+    /// preserved. This costs **O(|E|)** per edge removal, and
+    /// **O(|V| + d·|E|)** per node removal, for the degree **d** of the
+    /// removed node, since each of its edges is shifted out of the edge list.
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let mut g = DiGraphMap::<_, ()>::from_edges(&[(0, 1), (0, 2), (0, 3)]);
+    /// g.set_stable_removal(true);
+    /// g.remove_edge(0, 1);
+    /// assert_eq!(g.neighbors(0).collect::<Vec<_>>(), vec![2, 3]);
+    /// ```
+    pub fn set_stable_removal(&mut self, stable: bool) {
+        self.stable_removal = stable;
+    }
+
+    /// Return `true` if removals preserve the iteration order of the graph.
+    ///
+    /// See [`set_stable_removal`](#method.set_stable_removal).
+    pub fn stable_removal(&self) -> bool {
+        self.stable_removal
+    }
+
+    /// Choose whether the graph keeps track of the order in which the edges
+    /// were added, for [`neighbors_ordered`](#method.neighbors_ordered),
+    /// [`edges_ordered`](#method.edges_ordered) and the stable
+    /// [`EdgeId`](struct.EdgeId.html)s.
+    ///
+    /// Tracking is off by default, since it costs a map entry per edge, and
+    /// a map update for every edge added or removed. Enabling it numbers the
+    /// existing edges in the order of `all_edges`; disabling it forgets the
+    /// order, and invalidates all edge ids.
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let mut g = DiGraphMap::<_, ()>::new();
+    /// g.set_edge_order_tracking(true);
+    /// g.extend(&[(0, 1), (0, 2), (0, 3)]);
+    /// g.remove_edge(0, 1);
+    /// assert_eq!(g.neighbors_ordered(0).collect::<Vec<_>>(), vec![2, 3]);
+    /// ```
+    pub fn set_edge_order_tracking(&mut self, track: bool) {
+        if !track {
+            self.edge_order = None;
+        } else if self.edge_order.is_none() {
+            self.edge_order = Some(EdgeOrder {
+                seq: self.edges.keys().cloned().zip(0..).collect(),
+                edges: (0..).zip(self.edges.keys().cloned()).collect(),
+                next: self.edges.len() as u64,
+            });
         }
     }
 
+    /// Return `true` if the graph keeps track of the order in which the
+    /// edges were added.
+    ///
+    /// See [`set_edge_order_tracking`](#method.set_edge_order_tracking).
+    pub fn edge_order_tracking(&self) -> bool {
+        self.edge_order.is_some()
+    }
+
     /// Return the current node and edge capacity of the graph.
     pub fn capacity(&self) -> (usize, usize) {
         (self.nodes.capacity(), self.edges.capacity())
     }
 
+    /// Return the capacity of the adjacency list of node `n`, which holds
+    /// one entry per edge from or to `n`, or `None` if the node doesn't exist.
+    pub fn edge_capacity(&self, n: N) -> Option<usize> {
+        self.nodes.get(&n).map(|neighbors| neighbors.capacity())
+    }
+
+    /// Reserves capacity for at least `additional` more nodes to be inserted in
+    /// the graph. Graph may reserve more space to avoid frequent reallocations.
+    ///
+    /// **Panics** if the new capacity overflows `usize`.
+    pub fn reserve_nodes(&mut self, additional: usize) {
+        self.nodes.reserve(additional);
+    }
+
+    /// Reserves capacity for at least `additional` more edges to be inserted in
+    /// the graph. Graph may reserve more space to avoid frequent reallocations.
+    ///
+    /// **Panics** if the new capacity overflows `usize`.
+    pub fn reserve_edges(&mut self, additional: usize) {
+        self.edges.reserve(additional);
+    }
+
     /// Use their natural order to map the node pair (a, b) to a canonical edge id.
     #[inline]
     fn edge_key(a: N, b: N) -> (N, N) {
@@ -161,6 +283,43 @@ where
         Self::from_iter(iterable)
     }
 
+    /// Create a new `GraphMap` from nested maps, where `map[&a][&b]` is the
+    /// weight of the edge from `a` to `b`.
+    ///
+    /// Every key of the outer map becomes a node, also if its inner map is
+    /// empty; nodes that only appear in inner maps are inserted too. For an
+    /// undirected graph, `map[&a][&b]` and `map[&b][&a]` describe the same
+    /// edge, so if both are present, the weight of the one seen last is kept.
+    ///
+    /// This is the inverse of `to_nested_map`.
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    /// use std::collections::HashMap;
+    ///
+    /// let mut map = HashMap::new();
+    /// map.insert("a", vec![("b", 1)].into_iter().collect::<HashMap<_, _>>());
+    /// map.insert("c", HashMap::new());
+    /// let g = DiGraphMap::from_nested_map(&map);
+    /// assert_eq!(g.node_count(), 3);
+    /// assert_eq!(g.edge_weight("a", "b"), Some(&1));
+    /// ```
+    pub fn from_nested_map<S1, S2>(map: &HashMap<N, HashMap<N, E, S2>, S1>) -> Self
+    where
+        E: Clone,
+        S1: BuildHasher,
+        S2: BuildHasher,
+    {
+        let mut g = Self::with_capacity(map.len(), 0);
+        for (&a, targets) in map {
+            g.add_node(a);
+            for (&b, weight) in targets {
+                g.add_edge(a, b, weight.clone());
+            }
+        }
+        g
+    }
+
     /// Return the number of nodes in the graph.
     pub fn node_count(&self) -> usize {
         self.nodes.len()
@@ -175,6 +334,10 @@ where
     pub fn clear(&mut self) {
         self.nodes.clear();
         self.edges.clear();
+        if let Some(order) = &mut self.edge_order {
+            order.seq.clear();
+            order.edges.clear();
+        }
     }
 
     /// Add node `n` to the graph.
@@ -186,8 +349,18 @@ where
     /// Return `true` if node `n` was removed.
     ///
     /// Computes in **O(V)** time, due to the removal of edges with other nodes.
+    ///
+    /// **Note:** Unless [`stable_removal`](#method.set_stable_removal) is
+    /// enabled, this moves the last node and the last edges into the freed
+    /// positions, which changes the iteration order of nodes, edges and
+    /// neighbors.
     pub fn remove_node(&mut self, n: N) -> bool {
-        let links = match self.nodes.swap_remove(&n) {
+        let links = if self.stable_removal {
+            self.nodes.shift_remove(&n)
+        } else {
+            self.nodes.swap_remove(&n)
+        };
+        let links = match links {
             None => return false,
             Some(sus) => sus,
         };
@@ -195,11 +368,26 @@ where
             // remove all successor links
             self.remove_single_edge(&succ, &n, Incoming);
             // Remove all edge values
-            self.edges.swap_remove(&Self::edge_key(n, succ));
+            self.remove_edge_value(n, succ);
         }
         true
     }
 
+    /// Remove the weight of the edge from `a` to `b`, respecting `stable_removal`.
+    fn remove_edge_value(&mut self, a: N, b: N) -> Option<E> {
+        let key = Self::edge_key(a, b);
+        if let Some(order) = &mut self.edge_order {
+            if let Some(seq) = order.seq.remove(&key) {
+                order.edges.remove(&seq);
+            }
+        }
+        if self.stable_removal {
+            self.edges.shift_remove(&key)
+        } else {
+            self.edges.swap_remove(&key)
+        }
+    }
+
     /// Return `true` if the node is contained in the graph.
     pub fn contains_node(&self, n: N) -> bool {
         self.nodes.contains_key(&n)
@@ -227,9 +415,35 @@ where
     /// assert!(!g.contains_edge("y", "x"));
     /// ```
     pub fn add_edge(&mut self, a: N, b: N, weight: E) -> Option<E> {
+        self.insert_edge(a, b, weight, None)
+    }
+
+    /// Take the sequence numbers of all edges out of the order tracking, if
+    /// the edge order is tracked, leaving it empty.
+    fn take_edge_seq(&mut self) -> Option<HashMap<(N, N), u64>> {
+        let order = self.edge_order.as_mut()?;
+        order.edges.clear();
+        Some(mem::replace(&mut order.seq, HashMap::new()))
+    }
+
+    /// Add an edge like `add_edge`, but if it is new and the edge order is
+    /// tracked, give it the insertion sequence number `seq`, or the next one
+    /// if `seq` is `None`.
+    fn insert_edge(&mut self, a: N, b: N, weight: E, seq: Option<u64>) -> Option<E> {
         if let old @ Some(_) = self.edges.insert(Self::edge_key(a, b), weight) {
             old
         } else {
+            if let Some(order) = &mut self.edge_order {
+                let seq = match seq {
+                    Some(seq) => seq,
+                    None => {
+                        order.next += 1;
+                        order.next - 1
+                    }
+                };
+                order.seq.insert(Self::edge_key(a, b), seq);
+                order.edges.insert(seq, Self::edge_key(a, b));
+            }
             // insert in the adjacency list if it's a new edge
             self.nodes
                 .entry(a)
@@ -246,32 +460,116 @@ where
         }
     }
 
+    /// Add an edge like `add_edge`, and return its stable `EdgeId` together
+    /// with the old weight, if the edge existed already.
+    ///
+    /// A new edge gets a new id, an existing edge keeps its id. Ids are
+    /// never reused: once an edge is removed, its id no longer resolves,
+    /// even if an edge between the same nodes is added again.
+    ///
+    /// **Panics** if the edge order is not tracked, see
+    /// [`set_edge_order_tracking`](#method.set_edge_order_tracking).
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let mut g = DiGraphMap::new();
+    /// g.set_edge_order_tracking(true);
+    /// let (id, _) = g.add_edge_with_id("x", "y", 1);
+    /// g.add_edge("y", "z", 2);
+    /// assert_eq!(g.edge_by_id(id), Some(("x", "y", &1)));
+    /// assert_eq!(g.remove_edge_by_id(id), Some(1));
+    /// assert_eq!(g.edge_by_id(id), None);
+    /// ```
+    pub fn add_edge_with_id(&mut self, a: N, b: N, weight: E) -> (EdgeId, Option<E>) {
+        assert!(
+            self.edge_order.is_some(),
+            "GraphMap::add_edge_with_id: the edge order is not tracked"
+        );
+        let old = self.add_edge(a, b, weight);
+        (self.edge_id(a, b).unwrap(), old)
+    }
+
+    /// Return the stable `EdgeId` of the edge connecting `a` with `b`, or
+    /// `None` if the edge does not exist in the graph, or the edge order is
+    /// not tracked.
+    pub fn edge_id(&self, a: N, b: N) -> Option<EdgeId> {
+        let order = self.edge_order.as_ref()?;
+        order.seq.get(&Self::edge_key(a, b)).map(|&seq| EdgeId(seq))
+    }
+
+    /// Return the endpoints and the weight of the edge with the id `id`, or
+    /// `None` if it was removed, or the edge order is no longer tracked.
+    ///
+    /// For an undirected graph, the endpoints are returned in an arbitrary
+    /// order.
+    ///
+    /// Computes in **O(1)** time (average).
+    pub fn edge_by_id(&self, id: EdgeId) -> Option<(N, N, &E)> {
+        let &(a, b) = self.edge_order.as_ref()?.edges.get(&id.0)?;
+        Some((a, b, &self.edges[&(a, b)]))
+    }
+
+    /// Remove the edge with the id `id`, like `remove_edge`.
+    ///
+    /// Return the weight of the edge, or `None` if it was removed already,
+    /// or the edge order is no longer tracked.
+    pub fn remove_edge_by_id(&mut self, id: EdgeId) -> Option<E> {
+        let &(a, b) = self.edge_order.as_ref()?.edges.get(&id.0)?;
+        self.remove_edge(a, b)
+    }
+
+    /// Add an edge connecting `a` and `b` with the default edge weight, like
+    /// `.add_edge(a, b, E::default())`.
+    ///
+    /// Inserts nodes `a` and/or `b` if they aren't already part of the graph.
+    ///
+    /// Return `true` if the edge did not previously exist. Unlike `add_edge`,
+    /// the weight of an existing edge is left unchanged.
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let mut g = DiGraphMap::<_, u32>::new();
+    /// assert!(g.add_edge_default("x", "y"));
+    /// assert_eq!(g.edge_weight("x", "y"), Some(&0));
+    /// ```
+    pub fn add_edge_default(&mut self, a: N, b: N) -> bool
+    where
+        E: Default,
+    {
+        if self.contains_edge(a, b) {
+            false
+        } else {
+            self.add_edge(a, b, E::default());
+            true
+        }
+    }
+
     /// Remove edge relation from a to b
     ///
     /// Return `true` if it did exist.
     fn remove_single_edge(&mut self, a: &N, b: &N, dir: Direction) -> bool {
+        let stable_removal = self.stable_removal;
         match self.nodes.get_mut(a) {
             None => false,
             Some(sus) => {
-                if Ty::is_directed() {
-                    match sus
-                        .iter()
+                let index = if Ty::is_directed() {
+                    sus.iter()
                         .position(|elt| elt == &(*b, CompactDirection::from(dir)))
-                    {
-                        Some(index) => {
-                            sus.swap_remove(index);
-                            true
-                        }
-                        None => false,
-                    }
                 } else {
-                    match sus.iter().position(|elt| &elt.0 == b) {
-                        Some(index) => {
+                    sus.iter().position(|elt| &elt.0 == b)
+                };
+                match index {
+                    Some(index) => {
+                        if stable_removal {
+                            sus.remove(index);
+                        } else {
                             sus.swap_remove(index);
-                            true
                         }
-                        None => false,
+                        true
                     }
+                    None => false,
                 }
             }
         }
@@ -292,6 +590,11 @@ where
     /// assert_eq!(edge_data, Some(-1));
     /// assert_eq!(g.edge_count(), 0);
     /// ```
+    ///
+    /// **Note:** Unless [`stable_removal`](#method.set_stable_removal) is
+    /// enabled, this moves the last edge into the freed position, and the
+    /// last neighbor of `a` and `b` into the freed neighbor slots, which
+    /// changes the iteration order of edges and neighbors.
     pub fn remove_edge(&mut self, a: N, b: N) -> Option<E> {
         let exist1 = self.remove_single_edge(&a, &b, Outgoing);
         let exist2 = if a != b {
@@ -299,17 +602,126 @@ where
         } else {
             exist1
         };
-        let weight = self.edges.remove(&Self::edge_key(a, b));
+        let weight = self.remove_edge_value(a, b);
         debug_assert!(exist1 == exist2 && exist1 == weight.is_some());
         weight
     }
 
+    /// Remove every edge listed in `pairs` from the graph.
+    ///
+    /// Return the removed edge weights in the order of `pairs`, with `None`
+    /// for the edges that didn't exist (or were listed twice).
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let mut g = DiGraphMap::<_, u32>::from_edges(&[(0, 1, 1), (1, 2, 2), (2, 0, 3)]);
+    /// assert_eq!(g.remove_edges(vec![(2, 0), (0, 2), (0, 1)]), vec![Some(3), None, Some(1)]);
+    /// assert_eq!(g.edge_count(), 1);
+    /// ```
+    pub fn remove_edges<I>(&mut self, pairs: I) -> Vec<Option<E>>
+    where
+        I: IntoIterator<Item = (N, N)>,
+    {
+        pairs
+            .into_iter()
+            .map(|(a, b)| self.remove_edge(a, b))
+            .collect()
+    }
+
+    /// Keep only the edges starting from `n` for which `pred` returns `true`.
+    ///
+    /// `pred` is called with the target node and the weight of each edge
+    /// that `.edges(n)` would produce; other edges of the graph are left
+    /// untouched.
+    ///
+    /// Return the number of removed edges, which is `0` if the node doesn't
+    /// exist.
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let mut g = DiGraphMap::<_, u32>::from_edges(&[(0, 1, 1), (0, 2, 5), (1, 2, 7)]);
+    /// assert_eq!(g.retain_node_edges(0, |_, &w| w < 3), 1);
+    /// assert!(g.contains_edge(0, 1));
+    /// assert!(!g.contains_edge(0, 2));
+    /// assert!(g.contains_edge(1, 2));
+    /// ```
+    pub fn retain_node_edges<F>(&mut self, n: N, mut pred: F) -> usize
+    where
+        F: FnMut(&N, &E) -> bool,
+    {
+        let remove = self
+            .edges(n)
+            .filter(|(_, b, weight)| !pred(b, weight))
+            .map(|(_, b, _)| b)
+            .collect::<Vec<_>>();
+        for &b in &remove {
+            self.remove_edge(n, b);
+        }
+        remove.len()
+    }
+
+    /// Keep only the nodes for which `keep_node` returns `true` and the
+    /// edges for which `keep_edge` returns `true`, and return everything that
+    /// was removed.
+    ///
+    /// `keep_edge` is called with the endpoints and weight of each edge whose
+    /// endpoints are both kept; the edges of removed nodes are always removed.
+    ///
+    /// Return the removed nodes and the removed edges, as `(a, b, weight)`
+    /// triples like `.all_edges()` produces, each in the order of the graph.
+    /// The remaining nodes keep their relative order, and so do the
+    /// remaining edges in `.all_edges()`. The neighbor lists are rebuilt from
+    /// the edges, so the order of `.neighbors(a)` may change; the order of
+    /// `.neighbors_ordered(a)` is kept.
+    ///
+    /// Computes in **O(|V| + |E|)** time (average).
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let mut g = DiGraphMap::<_, u32>::from_edges(&[(0, 1, 1), (1, 2, 5), (2, 3, 7)]);
+    /// let (nodes, edges) = g.gc(|&n| n != 3, |_, _, &w| w < 3);
+    /// assert_eq!(nodes, vec![3]);
+    /// assert_eq!(edges, vec![(1, 2, 5), (2, 3, 7)]);
+    /// assert_eq!(g.edge_count(), 1);
+    /// ```
+    pub fn gc<FN, FE>(&mut self, mut keep_node: FN, mut keep_edge: FE) -> (Vec<N>, Vec<(N, N, E)>)
+    where
+        FN: FnMut(&N) -> bool,
+        FE: FnMut(&N, &N, &E) -> bool,
+    {
+        let nodes = mem::replace(&mut self.nodes, IndexMap::new());
+        let edges = mem::replace(&mut self.edges, IndexMap::new());
+        let edge_seq = self.take_edge_seq();
+        let mut removed_nodes = Vec::new();
+        for &n in nodes.keys() {
+            if keep_node(&n) {
+                self.add_node(n);
+            } else {
+                removed_nodes.push(n);
+            }
+        }
+        let mut removed_edges = Vec::new();
+        for ((a, b), weight) in edges {
+            if self.contains_node(a) && self.contains_node(b) && keep_edge(&a, &b, &weight) {
+                let seq = edge_seq.as_ref().map(|edge_seq| edge_seq[&(a, b)]);
+                self.insert_edge(a, b, weight, seq);
+            } else {
+                removed_edges.push((a, b, weight));
+            }
+        }
+        (removed_nodes, removed_edges)
+    }
+
     /// Return `true` if the edge connecting `a` with `b` is contained in the graph.
     pub fn contains_edge(&self, a: N, b: N) -> bool {
         self.edges.contains_key(&Self::edge_key(a, b))
     }
 
-    /// Return an iterator over the nodes of the graph.
+    /// Return an iterator over the nodes of the graph, in the order they
+    /// were added.
     ///
     /// Iterator element type is `N`.
     pub fn nodes(&self) -> Nodes<N> {
@@ -335,6 +747,99 @@ where
         }
     }
 
+    /// Return an iterator of all nodes with an edge starting from `a`, in
+    /// the reverse order of `.neighbors(a)`.
+    ///
+    /// Neighbors are stored in the order their edges were added, so this
+    /// visits the most recently connected neighbor first. Removing an edge
+    /// moves the last neighbor into its place, unless
+    /// [`stable_removal`](#method.set_stable_removal) is enabled, so after
+    /// removals the order only approximately follows insertion.
+    ///
+    /// Produces an empty iterator if the node doesn't exist.<br>
+    /// Iterator element type is `N`.
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let g = DiGraphMap::<_, ()>::from_edges(&[(0, 1), (0, 2), (0, 3)]);
+    /// assert_eq!(g.neighbors_rev(0).collect::<Vec<_>>(), vec![3, 2, 1]);
+    /// ```
+    pub fn neighbors_rev(&self, a: N) -> Rev<Neighbors<'_, N, Ty>> {
+        self.neighbors(a).rev()
+    }
+
+    /// Return an iterator of all nodes with an edge starting from `a`, or
+    /// `None` if the node doesn't exist.
+    ///
+    /// This is like `.neighbors(a)`, but tells a missing node apart from an
+    /// isolated one, which gives `Some` empty iterator.
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let mut g = DiGraphMap::<_, ()>::new();
+    /// g.add_node(1);
+    /// assert_eq!(g.try_neighbors(1).map(|it| it.count()), Some(0));
+    /// assert!(g.try_neighbors(2).is_none());
+    /// ```
+    pub fn try_neighbors(&self, a: N) -> Option<Neighbors<'_, N, Ty>> {
+        self.nodes.get(&a).map(|neigh| Neighbors {
+            iter: neigh.iter(),
+            ty: self.ty,
+        })
+    }
+
+    /// Return an iterator of all nodes with an edge starting from `a`, like
+    /// `.neighbors(a)`, but leaving out `exclude`.
+    ///
+    /// This is useful to skip the parent of a node in a tree walk.
+    ///
+    /// Produces an empty iterator if the node doesn't exist.<br>
+    /// Iterator element type is `N`.
+    ///
+    /// ```
+    /// use petgraph::graphmap::UnGraphMap;
+    ///
+    /// let g = UnGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2), (1, 3)]);
+    /// assert_eq!(g.neighbors_except(1, 0).collect::<Vec<_>>(), vec![2, 3]);
+    /// ```
+    pub fn neighbors_except(&self, a: N, exclude: N) -> impl Iterator<Item = N> + '_ {
+        self.neighbors(a).filter(move |&n| n != exclude)
+    }
+
+    /// Return an iterator of the edges adjacent to the edge from `a` to `b`
+    /// in its line graph, without building the line graph: the edges
+    /// `(b, c)` starting at its head `b`.
+    ///
+    /// - `Directed`: The outgoing edges of `b`, including `b -> a` if it
+    ///   exists.
+    /// - `Undirected`: The edges of `b` other than the edge itself.
+    ///
+    /// Produces an empty iterator if the edge doesn't exist.<br>
+    /// Iterator element type is `(N, N)`.
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let g = DiGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2), (1, 3), (2, 1)]);
+    /// let adjacent: Vec<_> = g.edge_neighbors(0, 1).collect();
+    /// assert_eq!(adjacent, vec![(1, 2), (1, 3)]);
+    /// ```
+    pub fn edge_neighbors(&self, a: N, b: N) -> impl Iterator<Item = (N, N)> + '_ {
+        let heads = if self.contains_edge(a, b) {
+            self.neighbors(b)
+        } else {
+            Neighbors {
+                iter: [].iter(),
+                ty: self.ty,
+            }
+        };
+        heads
+            .filter(move |&c| Ty::is_directed() || c != a)
+            .map(move |c| (b, c))
+    }
+
     /// Return an iterator of all neighbors that have an edge between them and
     /// `a`, in the specified direction.
     /// If the graph's edges are undirected, this is equivalent to *.neighbors(a)*.
@@ -373,6 +878,165 @@ where
         }
     }
 
+    /// Return the neighbors of `a` in the order in which the edges to them
+    /// were added.
+    ///
+    /// - `Directed`: Targets of outgoing edges from `a`.
+    /// - `Undirected`: All edges from or to `a`.
+    ///
+    /// Every edge remembers its insertion sequence number, so unlike
+    /// `.neighbors(a)`, this order is not disturbed by removals, even without
+    /// [`stable_removal`](#method.set_stable_removal). Edges that are only
+    /// updated with `add_edge` keep their place.
+    ///
+    /// **Panics** if the edge order is not tracked, see
+    /// [`set_edge_order_tracking`](#method.set_edge_order_tracking).
+    ///
+    /// Produces an empty iterator if the node doesn't exist.<br>
+    /// Iterator element type is `N`.
+    ///
+    /// Computes in **O(d log d)** time, where **d** is the number of
+    /// neighbors of `a`.
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let mut g = DiGraphMap::<_, ()>::from_edges(&[(0, 1), (0, 2), (0, 3)]);
+    /// g.set_edge_order_tracking(true);
+    /// g.remove_edge(0, 1);
+    /// assert_eq!(g.neighbors(0).collect::<Vec<_>>(), vec![3, 2]);
+    /// assert_eq!(g.neighbors_ordered(0).collect::<Vec<_>>(), vec![2, 3]);
+    /// ```
+    pub fn neighbors_ordered(&self, a: N) -> vec::IntoIter<N> {
+        let neighbors: Vec<_> = self.edges_ordered(a).map(|(_, b, _)| b).collect();
+        neighbors.into_iter()
+    }
+
+    /// Return the edges starting from `from`, like `.edges(from)`, in the
+    /// order in which they were added.
+    ///
+    /// See [`neighbors_ordered`](#method.neighbors_ordered).
+    ///
+    /// **Panics** if the edge order is not tracked.
+    ///
+    /// Produces an empty iterator if the node doesn't exist.<br>
+    /// Iterator element type is `(N, N, &E)`.
+    pub fn edges_ordered(&self, from: N) -> vec::IntoIter<(N, N, &E)> {
+        let order = self
+            .edge_order
+            .as_ref()
+            .expect("GraphMap::edges_ordered: the edge order is not tracked");
+        let mut edges: Vec<_> = self.edges(from).collect();
+        edges.sort_by_key(|&(a, b, _)| order.seq[&Self::edge_key(a, b)]);
+        edges.into_iter()
+    }
+
+    /// Return an iterator over the pairs of nodes `(a, b)`, with `a < b`, that
+    /// are connected in both directions: by an edge from `a` to `b` and by an
+    /// edge from `b` to `a`.
+    ///
+    /// Each pair is produced once. Self loops are not included. For an
+    /// undirected graph, every edge between two distinct nodes is mutual.
+    ///
+    /// Iterator element type is `(N, N)`.
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let g = DiGraphMap::<_, ()>::from_edges(&[(1, 2), (2, 1), (2, 3), (3, 3)]);
+    /// assert_eq!(g.mutual_edges().collect::<Vec<_>>(), vec![(1, 2)]);
+    /// ```
+    pub fn mutual_edges(&self) -> impl Iterator<Item = (N, N)> + '_ {
+        self.edges
+            .keys()
+            .cloned()
+            .filter(move |&(a, b)| a < b && self.contains_edge(b, a))
+    }
+
+    /// Return `true` if `a` and `b` have at least one neighbor in common.
+    ///
+    /// - `Directed`: Whether some node is the target of edges from both
+    ///   `a` and `b`.
+    /// - `Undirected`: Whether some node is connected to both `a` and `b`.
+    ///
+    /// Stops at the first common neighbor found; no intermediate set is built,
+    /// since edge existence is tested in constant time.
+    ///
+    /// Computes in **O(d)** time (average), where **d** is the number of
+    /// neighbors of `a`.
+    pub fn shares_neighbor(&self, a: N, b: N) -> bool {
+        self.neighbors(a).any(|n| self.contains_edge(b, n))
+    }
+
+    /// Return every edge from a node in `set_a` to a node in `set_b`, as
+    /// `(a, b, &weight)` with `a` in `set_a` and `b` in `set_b`.
+    ///
+    /// - `Directed`: Edges starting in `set_a` and ending in `set_b`.
+    /// - `Undirected`: Edges with one endpoint in each set. An edge whose
+    ///   endpoints both are in both sets is listed once per direction.
+    ///
+    /// This is the cut between the two sets, when they are disjoint. Nodes
+    /// of `set_a` that are not part of the graph are ignored. The order of
+    /// the edges is unspecified.
+    ///
+    /// Computes in **O(d)** time (average), where **d** is the sum of the
+    /// degrees of the nodes in `set_a`.
+    pub fn boundary_edges<S>(&self, set_a: &HashSet<N, S>, set_b: &HashSet<N, S>) -> Vec<(N, N, &E)>
+    where
+        S: BuildHasher,
+    {
+        set_a
+            .iter()
+            .flat_map(|&a| self.edges(a))
+            .filter(|&(_, b, _)| set_b.contains(&b))
+            .collect()
+    }
+
+    /// Return an owned snapshot of the target nodes with an edge starting
+    /// from `from`, paired with clones of their edge weights.
+    ///
+    /// - `Directed`: Outgoing edges from `from`.
+    /// - `Undirected`: All edges from or to `from`.
+    ///
+    /// The result does not borrow the graph, so it can be handed off to other
+    /// threads or kept across later mutations of the graph. The order is the
+    /// same as for `.edges(from)`.
+    ///
+    /// Produces an empty vector if the node doesn't exist.
+    pub fn neighbors_owned(&self, from: N) -> Vec<(N, E)>
+    where
+        E: Clone,
+    {
+        self.edges(from)
+            .map(|(_, b, weight)| (b, weight.clone()))
+            .collect()
+    }
+
+    /// Return the neighbors of `from`, sorted by their own degree.
+    ///
+    /// - `Directed`: The targets of edges from `from`, sorted by their
+    ///   out-degree.
+    /// - `Undirected`: All nodes connected to `from`, sorted by their degree.
+    ///
+    /// `order` selects whether low or high degree neighbors come first.
+    /// Neighbors of equal degree keep the order of `.neighbors(from)`.
+    ///
+    /// Produces an empty vector if the node doesn't exist.
+    ///
+    /// Computes in **O(s + d log d)** time, where **d** is the number of
+    /// neighbors of `from` and **s** is the sum of their degrees.
+    pub fn neighbors_by_degree(&self, from: N, order: DegreeOrder) -> Vec<N> {
+        let mut neighbors: Vec<_> = self
+            .neighbors(from)
+            .map(|b| (self.neighbors(b).count(), b))
+            .collect();
+        match order {
+            DegreeOrder::Ascending => neighbors.sort_by_key(|&(degree, _)| degree),
+            DegreeOrder::Descending => neighbors.sort_by_key(|&(degree, _)| Reverse(degree)),
+        }
+        neighbors.into_iter().map(|(_, b)| b).collect()
+    }
+
     /// Return a reference to the edge weight connecting `a` with `b`, or
     /// `None` if the edge does not exist in the graph.
     pub fn edge_weight(&self, a: N, b: N) -> Option<&E> {
@@ -395,15 +1059,463 @@ where
         }
     }
 
-    /// Return an iterator over all edges of the graph in arbitrary order, with a mutable reference
-    /// to their weight.
+    /// Return an iterator over all edges of the graph in arbitrary order, with a mutable reference
+    /// to their weight.
+    ///
+    /// Iterator element type is `(N, N, &mut E)`
+    pub fn all_edges_mut(&mut self) -> AllEdgesMut<N, E, Ty> {
+        AllEdgesMut {
+            inner: self.edges.iter_mut(),
+            ty: self.ty,
+        }
+    }
+
+    /// Format the graph as an adjacency table, one line per node.
+    ///
+    /// Each line has the form `a -> [b (2), c (5)]`, listing the neighbors
+    /// of the node (as for `.edges(a)`) followed by the edge weight in
+    /// parentheses. Both the lines and the neighbors within a line are sorted
+    /// by node, so the output does not depend on the insertion order.
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let g = DiGraphMap::<_, u32>::from_edges(&[(2, 1, 7), (1, 3, 5), (1, 2, 2)]);
+    /// assert_eq!(g.to_adjacency_string(), "1 -> [2 (2), 3 (5)]\n2 -> [1 (7)]\n3 -> []\n");
+    /// ```
+    pub fn to_adjacency_string(&self) -> String
+    where
+        N: fmt::Debug,
+        E: fmt::Debug,
+    {
+        use std::fmt::Write;
+
+        let mut nodes: Vec<N> = self.nodes().collect();
+        nodes.sort();
+        let mut out = String::new();
+        for a in nodes {
+            let mut edges: Vec<_> = self.edges(a).map(|(_, b, w)| (b, w)).collect();
+            edges.sort_by_key(|&(b, _)| b);
+            // writing to a `String` can not fail
+            let _ = write!(out, "{:?} -> [", a);
+            for (i, (b, w)) in edges.into_iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                let _ = write!(out, "{:?} ({:?})", b, w);
+            }
+            out.push_str("]\n");
+        }
+        out
+    }
+
+    /// Write the graph to `w` as an adjacency list, one line per node.
+    ///
+    /// Each line has the form `a: b c`, listing the neighbors of the node (as
+    /// for `.neighbors(a)`) separated by spaces. Nodes without neighbors get
+    /// a line of their own too, so that they survive a round trip through
+    /// `read_adjacency`; edge weights are not written. Lines are in the order
+    /// of `.nodes()`.
+    ///
+    /// The `Display` form of the nodes must not contain whitespace or `:`.
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let mut g = DiGraphMap::<_, ()>::from_edges(&[(1, 2), (1, 3)]);
+    /// g.add_node(4);
+    /// let mut out = Vec::new();
+    /// g.write_adjacency(&mut out).unwrap();
+    /// assert_eq!(String::from_utf8(out).unwrap(), "1: 2 3\n2:\n3:\n4:\n");
+    /// ```
+    pub fn write_adjacency<W>(&self, mut w: W) -> io::Result<()>
+    where
+        W: io::Write,
+        N: fmt::Display,
+    {
+        for a in self.nodes() {
+            write!(w, "{}:", a)?;
+            for b in self.neighbors(a) {
+                write!(w, " {}", b)?;
+            }
+            writeln!(w)?;
+        }
+        Ok(())
+    }
+
+    /// Read a graph from an adjacency list in the format written by
+    /// `write_adjacency`.
+    ///
+    /// Every line `a: b c` adds the node `a` and edges from `a` to `b` and to
+    /// `c`, with default weights. Blank lines are skipped. For an undirected
+    /// graph, an edge may be listed from both of its endpoints.
+    ///
+    /// Return an error of kind `InvalidData` if a line has no `:` or a node
+    /// fails to parse, and pass on errors from reading `r`.
+    pub fn read_adjacency<R>(r: R) -> io::Result<Self>
+    where
+        R: io::BufRead,
+        N: FromStr,
+        E: Default,
+    {
+        let parse = |s: &str| {
+            s.parse::<N>().map_err(|_| {
+                io::Error::new(io::ErrorKind::InvalidData, format!("invalid node {:?}", s))
+            })
+        };
+        let mut g = Self::new();
+        for line in r.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let colon = match line.find(':') {
+                Some(colon) => colon,
+                None => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("missing `:` in line {:?}", line),
+                    ))
+                }
+            };
+            let a = g.add_node(parse(line[..colon].trim())?);
+            for b in line[colon + 1..].split_whitespace() {
+                g.add_edge(a, parse(b)?, E::default());
+            }
+        }
+        Ok(g)
+    }
+
+    /// Add all nodes and edges of `other` to the graph.
+    ///
+    /// Nodes are identified by their value, so nodes of `other` that already
+    /// exist are shared. If an edge exists in both graphs, its weight is set to
+    /// `combine(self_weight, other_weight)`; edges only in `other` are added with
+    /// a clone of their weight.
+    ///
+    /// Computes in **O(|V'| + |E'|)** time (average), where `V'` and `E'` are
+    /// the nodes and edges of `other`.
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let mut a = DiGraphMap::<_, u32>::from_edges(&[(0, 1, 1), (1, 2, 3)]);
+    /// let b = DiGraphMap::<_, u32>::from_edges(&[(0, 1, 1), (2, 0, 4)]);
+    /// a.merge_with(&b, |x, y| x + y);
+    /// assert_eq!(a.edge_weight(0, 1), Some(&2));
+    /// assert_eq!(a.edge_weight(2, 0), Some(&4));
+    /// assert_eq!(a.edge_count(), 3);
+    /// ```
+    pub fn merge_with<F>(&mut self, other: &GraphMap<N, E, Ty>, mut combine: F)
+    where
+        E: Clone,
+        F: FnMut(&E, &E) -> E,
+    {
+        for n in other.nodes() {
+            self.add_node(n);
+        }
+        for (a, b, weight) in other.all_edges() {
+            match self.edge_weight_mut(a, b) {
+                Some(old) => *old = combine(old, weight),
+                None => {
+                    self.add_edge(a, b, weight.clone());
+                }
+            }
+        }
+    }
+
+    /// Contract every edge for which `pred(a, b, weight)` returns `true`,
+    /// merging its endpoints into a single node.
+    ///
+    /// Each group of nodes connected by contracted edges is replaced by its
+    /// smallest node, the *survivor*. All other edges are kept and redirected
+    /// to the survivors of their endpoints; an edge between two nodes of the
+    /// same group becomes a self loop. When several edges end up connecting
+    /// the same pair of survivors, the weight of the one added last is kept.
+    ///
+    /// The groups are computed with a union-find before the graph is rebuilt,
+    /// so the result does not depend on the order of the edges.
+    ///
+    /// Return a map from every node of the original graph to its survivor.
+    ///
+    /// Computes in **O(|V| + |E|)** time (average).
+    ///
+    /// ```
+    /// use petgraph::graphmap::UnGraphMap;
+    ///
+    /// let mut g = UnGraphMap::<_, u32>::from_edges(&[(1, 2, 0), (2, 3, 4), (3, 4, 0)]);
+    /// let survivors = g.contract_edges_where(|_, _, &w| w == 0);
+    /// assert_eq!(survivors[&2], 1);
+    /// assert_eq!(survivors[&4], 3);
+    /// assert_eq!(g.all_edges().collect::<Vec<_>>(), vec![(1, 3, &4)]);
+    /// ```
+    pub fn contract_edges_where<F>(&mut self, mut pred: F) -> HashMap<N, N>
+    where
+        F: FnMut(&N, &N, &E) -> bool,
+    {
+        let mut sets = UnionFind::new(self.nodes.len());
+        let mut contracted = Vec::with_capacity(self.edges.len());
+        for (&(a, b), weight) in &self.edges {
+            let contract = pred(&a, &b, weight);
+            if contract {
+                sets.union(self.to_index(a), self.to_index(b));
+            }
+            contracted.push(contract);
+        }
+        let labels = sets.into_labeling();
+        let mut survivors = HashMap::new();
+        for (&n, &label) in self.nodes.keys().zip(&labels) {
+            let survivor = survivors.entry(label).or_insert(n);
+            if n < *survivor {
+                *survivor = n;
+            }
+        }
+        let mapping: HashMap<N, N> = self
+            .nodes
+            .keys()
+            .zip(&labels)
+            .map(|(&n, label)| (n, survivors[label]))
+            .collect();
+
+        let nodes = mem::replace(&mut self.nodes, IndexMap::with_capacity(survivors.len()));
+        let edges = mem::replace(&mut self.edges, IndexMap::new());
+        let edge_seq = self.take_edge_seq();
+        for n in nodes.keys() {
+            self.add_node(mapping[n]);
+        }
+        for (((a, b), weight), contract) in edges.into_iter().zip(contracted) {
+            if !contract {
+                let seq = edge_seq.as_ref().map(|edge_seq| edge_seq[&(a, b)]);
+                self.insert_edge(mapping[&a], mapping[&b], weight, seq);
+            }
+        }
+        mapping
+    }
+
+    /// Return `true` if both graphs have the same nodes and edges, and
+    /// `weight_eq` returns `true` for the weights of every pair of
+    /// corresponding edges.
+    ///
+    /// The order in which nodes, edges and neighbors were added does not
+    /// matter. `weight_eq` is called with the weight in `self` first, so it
+    /// can compare for example floating point weights up to a tolerance.
+    ///
+    /// Computes in **O(|V| + |E|)** time (average).
+    ///
+    /// ```
+    /// use petgraph::graphmap::UnGraphMap;
+    ///
+    /// let a = UnGraphMap::<_, f64>::from_edges(&[(0, 1, 0.3), (1, 2, 1.)]);
+    /// let b = UnGraphMap::<_, f64>::from_edges(&[(2, 1, 1.), (1, 0, 0.1 + 0.2)]);
+    /// assert!(a.eq_with_weights(&b, |x, y| (x - y).abs() < 1e-9));
+    /// assert!(!a.eq_with_weights(&b, |x, y| x == y));
+    /// ```
+    pub fn eq_with_weights<F>(&self, other: &GraphMap<N, E, Ty>, mut weight_eq: F) -> bool
+    where
+        F: FnMut(&E, &E) -> bool,
+    {
+        self.node_count() == other.node_count()
+            && self.edge_count() == other.edge_count()
+            && self.nodes().all(|n| other.contains_node(n))
+            && self
+                .edges
+                .iter()
+                .all(|(&(a, b), weight)| match other.edge_weight(a, b) {
+                    Some(w) => weight_eq(weight, w),
+                    None => false,
+                })
+    }
+
+    /// Return `true` if both graphs have the same edges with equal weights,
+    /// ignoring isolated nodes.
+    ///
+    /// Unlike `eq_with_weights`, only the edges are compared: a node without
+    /// any edges may be present in one graph and missing in the other. The
+    /// nodes that do have edges are then necessarily the same.
+    ///
+    /// Computes in **O(|E|)** time (average).
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    ///
+    /// let a = DiGraphMap::<_, u8>::from_edges(&[(0, 1, 2)]);
+    /// let mut b = a.clone();
+    /// b.add_node(5);
+    /// assert!(a.eq_edges(&b));
+    /// ```
+    pub fn eq_edges(&self, other: &GraphMap<N, E, Ty>) -> bool
+    where
+        E: PartialEq,
+    {
+        self.edge_count() == other.edge_count()
+            && self
+                .edges
+                .iter()
+                .all(|(&(a, b), weight)| other.edge_weight(a, b) == Some(weight))
+    }
+
+    /// Check the internal consistency of the graph.
+    ///
+    /// This verifies that:
+    ///
+    /// - every neighbor in an adjacency list is itself a node of the graph,
+    /// - no adjacency list contains the same neighbor twice,
+    /// - the adjacency lists of both endpoints of an edge agree with each
+    ///   other and with the stored edge weights,
+    /// - the number of stored edge weights (`edge_count`) matches the
+    ///   adjacency lists,
+    /// - every edge has an insertion sequence number.
+    ///
+    /// Return `Err` with a description of the first violation found. This is
+    /// meant for tests and fuzzing: a graph that was only modified through
+    /// the public API should always be valid.
+    ///
+    /// ```
+    /// use petgraph::graphmap::UnGraphMap;
+    ///
+    /// let g = UnGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2), (2, 2)]);
+    /// assert_eq!(g.validate(), Ok(()));
+    /// ```
+    pub fn validate(&self) -> Result<(), String>
+    where
+        N: fmt::Debug,
+    {
+        let mut outgoing = 0;
+        for (&a, neighbors) in &self.nodes {
+            let mut seen = HashSet::with_capacity(neighbors.len());
+            for &(b, dir) in neighbors {
+                let other = match self.nodes.get(&b) {
+                    Some(other) => other,
+                    None => {
+                        return Err(format!(
+                            "node {:?} has neighbor {:?}, which is not a node",
+                            a, b
+                        ))
+                    }
+                };
+                // Directed graphs may list a neighbor once in each direction.
+                let key = (b, Ty::is_directed() && dir == CompactDirection::Incoming);
+                if !seen.insert(key) {
+                    return Err(format!(
+                        "node {:?} lists neighbor {:?} more than once",
+                        a, b
+                    ));
+                }
+                let (source, target, mirror) = match dir {
+                    CompactDirection::Outgoing => {
+                        outgoing += 1;
+                        (a, b, (a, CompactDirection::Incoming))
+                    }
+                    CompactDirection::Incoming => (b, a, (a, CompactDirection::Outgoing)),
+                };
+                if !self.edges.contains_key(&Self::edge_key(source, target)) {
+                    return Err(format!(
+                        "the edge from {:?} to {:?} has no weight",
+                        source, target
+                    ));
+                }
+                // Self loops only have the outgoing entry.
+                let mirrored = if a == b {
+                    dir == CompactDirection::Outgoing
+                } else {
+                    other.contains(&mirror)
+                };
+                if !mirrored {
+                    return Err(format!(
+                        "node {:?} lists neighbor {:?}, but not the other way around",
+                        a, b
+                    ));
+                }
+            }
+        }
+        if outgoing != self.edges.len() {
+            return Err(format!(
+                "{} edge weights are stored for {} edges",
+                self.edges.len(),
+                outgoing
+            ));
+        }
+        if let Some(order) = &self.edge_order {
+            if let Some(&(a, b)) = self.edges.keys().find(|k| !order.seq.contains_key(k)) {
+                return Err(format!(
+                    "the edge from {:?} to {:?} has no sequence number",
+                    a, b
+                ));
+            }
+            if order.edges.len() != order.seq.len()
+                || order
+                    .seq
+                    .iter()
+                    .any(|(key, seq)| order.edges.get(seq) != Some(key))
+            {
+                return Err("the edge ids are not all resolved to their edges".to_string());
+            }
+        }
+        Ok(())
+    }
+
+    /// Return a copy of the graph with every node `n` renamed to `map[&n]`.
+    ///
+    /// Nodes and edges keep their order. If several nodes are renamed to the
+    /// same value, they are merged into one node, and of the edges that end
+    /// up connecting the same pair of nodes, the weight of the last one is
+    /// kept. Entries of `map` for nodes that are not in the graph are
+    /// ignored.
+    ///
+    /// Return `Err(n)` for the first node `n` that has no entry in `map`.
+    ///
+    /// Computes in **O(|V| + |E|)** time (average).
+    ///
+    /// ```
+    /// use petgraph::graphmap::DiGraphMap;
+    /// use std::collections::HashMap;
+    ///
+    /// let g = DiGraphMap::<_, ()>::from_edges(&[(1, 2), (2, 3)]);
+    /// let names: HashMap<_, _> = vec![(1, "a"), (2, "b"), (3, "c")].into_iter().collect();
+    /// let h = g.relabel_with_map(&names).unwrap();
+    /// assert!(h.contains_edge("b", "c"));
+    /// ```
+    pub fn relabel_with_map<N2, S>(&self, map: &HashMap<N, N2, S>) -> Result<GraphMap<N2, E, Ty>, N>
+    where
+        N2: NodeTrait,
+        E: Clone,
+        S: BuildHasher,
+    {
+        let mut g = GraphMap::with_capacity(self.node_count(), self.edge_count());
+        for n in self.nodes() {
+            match map.get(&n) {
+                Some(&m) => g.add_node(m),
+                None => return Err(n),
+            };
+        }
+        for (a, b, weight) in self.all_edges() {
+            g.add_edge(map[&a], map[&b], weight.clone());
+        }
+        Ok(g)
+    }
+
+    /// Return the adjacency of the graph as nested maps, where
+    /// `map[&a][&b]` is the weight of the edge from `a` to `b`.
     ///
-    /// Iterator element type is `(N, N, &mut E)`
-    pub fn all_edges_mut(&mut self) -> AllEdgesMut<N, E, Ty> {
-        AllEdgesMut {
-            inner: self.edges.iter_mut(),
-            ty: self.ty,
-        }
+    /// - `Directed`: `map[&a]` holds the outgoing edges of `a`.
+    /// - `Undirected`: Every edge is found both as `map[&a][&b]` and
+    ///   `map[&b][&a]`.
+    ///
+    /// Every node is a key of the outer map, with an empty inner map if it
+    /// has no edges. The result supports edge lookups in constant time
+    /// without borrowing the graph.
+    ///
+    /// Computes in **O(|V| + |E|)** time (average).
+    pub fn to_nested_map(&self) -> HashMap<N, HashMap<N, E>>
+    where
+        E: Clone,
+    {
+        self.nodes()
+            .map(|a| {
+                let targets = self.edges(a).map(|(_, b, w)| (b, w.clone())).collect();
+                (a, targets)
+            })
+            .collect()
     }
 
     /// Return a `Graph` that corresponds to this `GraphMap`.
@@ -435,6 +1547,40 @@ where
     }
 }
 
+/// Convenience methods for graphs without edge weights.
+impl<N, Ty> GraphMap<N, (), Ty>
+where
+    N: NodeTrait,
+    Ty: EdgeType,
+{
+    /// Add an edge connecting `a` and `b` to the graph, like
+    /// `.add_edge(a, b, ())`.
+    ///
+    /// Inserts nodes `a` and/or `b` if they aren't already part of the graph.
+    ///
+    /// Return `true` if the edge did not previously exist.
+    ///
+    /// ```
+    /// use petgraph::graphmap::UnGraphMap;
+    ///
+    /// let mut g = UnGraphMap::new();
+    /// assert!(g.connect("x", "y"));
+    /// assert!(!g.connect("y", "x"));
+    /// assert!(g.disconnect("x", "y"));
+    /// assert_eq!(g.edge_count(), 0);
+    /// ```
+    pub fn connect(&mut self, a: N, b: N) -> bool {
+        self.add_edge(a, b, ()).is_none()
+    }
+
+    /// Remove the edge from `a` to `b`, like `.remove_edge(a, b)`.
+    ///
+    /// Return `true` if it did exist.
+    pub fn disconnect(&mut self, a: N, b: N) -> bool {
+        self.remove_edge(a, b).is_some()
+    }
+}
+
 /// Create a new `GraphMap` from an iterable of edges.
 impl<N, E, Ty, Item> FromIterator<Item> for GraphMap<N, E, Ty>
 where
@@ -478,6 +1624,423 @@ where
     }
 }
 
+/// A stable handle of an edge in a `GraphMap`, returned by
+/// [`GraphMap::add_edge_with_id`](struct.GraphMap.html#method.add_edge_with_id).
+///
+/// Edge ids are only handed out while the graph tracks its edge order, see
+/// [`GraphMap::set_edge_order_tracking`](struct.GraphMap.html#method.set_edge_order_tracking).
+/// An id stays valid until its edge is removed or the tracking is disabled,
+/// independently of other mutations of the graph; ids of removed edges are
+/// never reused.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EdgeId(u64);
+
+/// The order of neighbors returned by
+/// [`GraphMap::neighbors_by_degree`](struct.GraphMap.html#method.neighbors_by_degree).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DegreeOrder {
+    /// Neighbors with the fewest edges come first.
+    Ascending,
+    /// Neighbors with the most edges come first.
+    Descending,
+}
+
+/// The effect of adding an edge with
+/// [`IncrementalComponents::add_edge_tracked`](struct.IncrementalComponents.html#method.add_edge_tracked).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum EdgeEffect {
+    /// The edge was added between two nodes of the same component.
+    NewEdge,
+    /// The edge was added and joined two previously separate components.
+    MergedComponents,
+    /// The edge already existed; the graph was left unchanged.
+    DuplicateIgnored,
+}
+
+/// A `GraphMap` that keeps track of its *weakly* connected components as
+/// edges are added.
+///
+/// The components are maintained with a union-find structure, so the number
+/// of components and whether two nodes are connected can be queried at any
+/// time in **O(α(|V|))** amortized time. Since a union-find can not split
+/// sets, nodes and edges can only be added, not removed; use `into_graph` to
+/// get back the underlying `GraphMap`.
+///
+/// ```
+/// use petgraph::graphmap::{EdgeEffect, IncrementalComponents, UnGraphMap};
+///
+/// let mut g = IncrementalComponents::new(UnGraphMap::new());
+/// assert_eq!(g.add_edge_tracked(1, 2, ()), EdgeEffect::MergedComponents);
+/// assert_eq!(g.add_edge_tracked(3, 4, ()), EdgeEffect::MergedComponents);
+/// assert_eq!(g.component_count(), 2);
+/// assert_eq!(g.add_edge_tracked(2, 3, ()), EdgeEffect::MergedComponents);
+/// assert_eq!(g.add_edge_tracked(4, 1, ()), EdgeEffect::NewEdge);
+/// assert_eq!(g.add_edge_tracked(1, 4, ()), EdgeEffect::DuplicateIgnored);
+/// assert_eq!(g.component_count(), 1);
+/// ```
+#[derive(Clone, Debug)]
+pub struct IncrementalComponents<N, E, Ty>
+where
+    N: NodeTrait,
+    Ty: EdgeType,
+{
+    graph: GraphMap<N, E, Ty>,
+    // Elements are the node positions in `graph.nodes`, which only grow.
+    sets: UnionFind<usize>,
+    components: usize,
+}
+
+impl<N, E, Ty> IncrementalComponents<N, E, Ty>
+where
+    N: NodeTrait,
+    Ty: EdgeType,
+{
+    /// Start tracking the components of `graph`.
+    ///
+    /// Computes in **O(|V| + |E|)** time.
+    pub fn new(graph: GraphMap<N, E, Ty>) -> Self {
+        let mut sets = UnionFind::new(graph.node_count());
+        let mut components = graph.node_count();
+        for (a, b, _) in graph.all_edges() {
+            if sets.union(graph.to_index(a), graph.to_index(b)) {
+                components -= 1;
+            }
+        }
+        IncrementalComponents {
+            graph,
+            sets,
+            components,
+        }
+    }
+
+    /// Add node `n` to the graph, as a component of its own if it is new.
+    pub fn add_node(&mut self, n: N) -> N {
+        self.graph.add_node(n);
+        self.grow();
+        n
+    }
+
+    /// Add an edge connecting `a` and `b` to the graph, unless it already
+    /// exists, and report how this changed the components.
+    ///
+    /// Inserts nodes `a` and/or `b` if they aren't already part of the graph.
+    /// Unlike `GraphMap::add_edge`, the weight of an existing edge is not
+    /// updated.
+    pub fn add_edge_tracked(&mut self, a: N, b: N, weight: E) -> EdgeEffect {
+        if self.graph.contains_edge(a, b) {
+            return EdgeEffect::DuplicateIgnored;
+        }
+        self.graph.add_edge(a, b, weight);
+        self.grow();
+        let (ai, bi) = (self.graph.to_index(a), self.graph.to_index(b));
+        if self.sets.union(ai, bi) {
+            self.components -= 1;
+            EdgeEffect::MergedComponents
+        } else {
+            EdgeEffect::NewEdge
+        }
+    }
+
+    /// Return the number of weakly connected components.
+    pub fn component_count(&self) -> usize {
+        self.components
+    }
+
+    /// Return `true` if `a` and `b` are part of the graph and in the same
+    /// component.
+    pub fn same_component(&self, a: N, b: N) -> bool {
+        match (self.graph.nodes.get_full(&a), self.graph.nodes.get_full(&b)) {
+            (Some((ai, _, _)), Some((bi, _, _))) => self.sets.equiv(ai, bi),
+            _ => false,
+        }
+    }
+
+    /// Return a reference to the underlying graph.
+    pub fn graph(&self) -> &GraphMap<N, E, Ty> {
+        &self.graph
+    }
+
+    /// Stop tracking and return the underlying graph.
+    pub fn into_graph(self) -> GraphMap<N, E, Ty> {
+        self.graph
+    }
+
+    /// Give every newly inserted node a set of its own.
+    fn grow(&mut self) {
+        while self.sets.len() < self.graph.node_count() {
+            self.sets.new_set();
+            self.components += 1;
+        }
+    }
+}
+
+/// A `GraphMap` from which nodes are removed in order of their degree, for
+/// peeling algorithms like computing degeneracy orderings or k-cores.
+///
+/// The degree of a node is the number of its neighbor entries: for a
+/// directed graph the number of its outgoing plus its incoming edges, for an
+/// undirected graph the number of its edges; a self loop counts once. The
+/// nodes are kept in buckets by degree, which are updated as the neighbors
+/// of removed nodes lose edges. Removing an edge scans the neighbor lists of
+/// both of its endpoints, so peeling the whole graph takes
+/// **O(|V| + Σ d(v)²)** time (average) in total, for the initial degrees
+/// **d(v)**, which is at most **O(|V| + |E| · Δ)** for the largest degree
+/// **Δ**.
+///
+/// ```
+/// use petgraph::graphmap::{DegreePeeling, UnGraphMap};
+///
+/// // a triangle with a pendant node
+/// let g = UnGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2), (2, 0), (2, 3)]);
+/// let mut peeling = DegreePeeling::new(g);
+/// assert_eq!(peeling.remove_min_degree_node(), Some((3, 1)));
+/// assert_eq!(peeling.remove_min_degree_node().map(|(_, d)| d), Some(2));
+/// assert_eq!(peeling.graph().node_count(), 2);
+/// ```
+#[derive(Clone, Debug)]
+pub struct DegreePeeling<N, E, Ty>
+where
+    N: NodeTrait,
+    Ty: EdgeType,
+{
+    graph: GraphMap<N, E, Ty>,
+    degree: HashMap<N, usize>,
+    // the nodes of degree `d` are in `buckets[d]`
+    buckets: Vec<IndexSet<N>>,
+    // no bucket below this one contains a node
+    min_degree: usize,
+}
+
+impl<N, E, Ty> DegreePeeling<N, E, Ty>
+where
+    N: NodeTrait,
+    Ty: EdgeType,
+{
+    /// Start peeling `graph`.
+    ///
+    /// Computes in **O(|V|)** time (average).
+    pub fn new(graph: GraphMap<N, E, Ty>) -> Self {
+        let mut degree = HashMap::with_capacity(graph.node_count());
+        let mut buckets = Vec::new();
+        for (&n, neighbors) in &graph.nodes {
+            let d = neighbors.len();
+            if buckets.len() <= d {
+                buckets.resize_with(d + 1, IndexSet::new);
+            }
+            buckets[d].insert(n);
+            degree.insert(n, d);
+        }
+        DegreePeeling {
+            graph,
+            degree,
+            buckets,
+            min_degree: 0,
+        }
+    }
+
+    /// Return the current degree of `n`, or `None` if it is not part of the
+    /// graph (anymore).
+    pub fn degree(&self, n: N) -> Option<usize> {
+        self.degree.get(&n).cloned()
+    }
+
+    /// Remove a node of the smallest degree together with its edges, and
+    /// return it and its degree, or `None` if the graph is empty.
+    ///
+    /// Of several nodes of the same degree, which one is removed is
+    /// unspecified.
+    ///
+    /// Computes in **O(d² + Σ d(m))** time (average), for the degree **d**
+    /// of the removed node and the degrees **d(m)** of its neighbors.
+    pub fn remove_min_degree_node(&mut self) -> Option<(N, usize)> {
+        while self.min_degree < self.buckets.len() && self.buckets[self.min_degree].is_empty() {
+            self.min_degree += 1;
+        }
+        let d = self.min_degree;
+        let n = self.buckets.get_mut(d)?.pop()?;
+        self.degree.remove(&n);
+        let neighbors = self.graph.nodes[&n].clone();
+        for (m, dir) in neighbors {
+            if dir == Outgoing {
+                self.graph.remove_edge(n, m);
+            } else {
+                self.graph.remove_edge(m, n);
+            }
+            if m != n {
+                let dm = self.degree[&m];
+                self.buckets[dm].swap_remove(&m);
+                self.buckets[dm - 1].insert(m);
+                self.degree.insert(m, dm - 1);
+                self.min_degree = self.min_degree.min(dm - 1);
+            }
+        }
+        self.graph.remove_node(n);
+        Some((n, d))
+    }
+
+    /// Return a reference to the underlying graph, with the nodes removed so
+    /// far.
+    pub fn graph(&self) -> &GraphMap<N, E, Ty> {
+        &self.graph
+    }
+
+    /// Stop peeling and return the underlying graph.
+    pub fn into_graph(self) -> GraphMap<N, E, Ty> {
+        self.graph
+    }
+}
+
+/// A `GraphMap` that keeps a histogram of its node degrees up to date as
+/// nodes and edges are added and removed.
+///
+/// The degree of a node is counted like for [`DegreePeeling`]: the number of
+/// its outgoing plus its incoming edges for a directed graph, the number of
+/// its edges for an undirected graph, and a self loop counts once. Every
+/// change only moves the nodes it touches between the buckets of the
+/// histogram, so it can be read at any time without a scan of the graph.
+///
+/// [`DegreePeeling`]: struct.DegreePeeling.html
+///
+/// ```
+/// use petgraph::graphmap::{DegreeHistogram, UnGraphMap};
+///
+/// let mut g = DegreeHistogram::new(UnGraphMap::<_, ()>::new());
+/// g.add_edge(0, 1, ());
+/// g.add_edge(0, 2, ());
+/// assert_eq!(g.live_degree_histogram()[&1], 2);
+/// assert_eq!(g.live_degree_histogram()[&2], 1);
+/// g.remove_node(0);
+/// assert_eq!(g.live_degree_histogram()[&0], 2);
+/// assert_eq!(g.live_degree_histogram().len(), 1);
+/// ```
+#[derive(Clone, Debug)]
+pub struct DegreeHistogram<N, E, Ty>
+where
+    N: NodeTrait,
+    Ty: EdgeType,
+{
+    graph: GraphMap<N, E, Ty>,
+    // the number of nodes of every degree; degrees without nodes are absent
+    histogram: HashMap<usize, usize>,
+}
+
+impl<N, E, Ty> DegreeHistogram<N, E, Ty>
+where
+    N: NodeTrait,
+    Ty: EdgeType,
+{
+    /// Start tracking the degrees of `graph`.
+    ///
+    /// Computes in **O(|V|)** time (average).
+    pub fn new(graph: GraphMap<N, E, Ty>) -> Self {
+        let mut histogram = HashMap::new();
+        for neighbors in graph.nodes.values() {
+            *histogram.entry(neighbors.len()).or_insert(0) += 1;
+        }
+        DegreeHistogram { graph, histogram }
+    }
+
+    /// Return the number of nodes of every degree.
+    ///
+    /// Degrees that no node has are not part of the map.
+    pub fn live_degree_histogram(&self) -> &HashMap<usize, usize> {
+        &self.histogram
+    }
+
+    /// Add node `n` to the graph, like `GraphMap::add_node`.
+    pub fn add_node(&mut self, n: N) -> N {
+        let before = self.degrees(&[n]);
+        self.graph.add_node(n);
+        self.update(before);
+        n
+    }
+
+    /// Add an edge connecting `a` and `b` to the graph, like
+    /// `GraphMap::add_edge`.
+    pub fn add_edge(&mut self, a: N, b: N, weight: E) -> Option<E> {
+        let before = self.degrees(&[a, b]);
+        let old = self.graph.add_edge(a, b, weight);
+        self.update(before);
+        old
+    }
+
+    /// Remove the edge from `a` to `b`, like `GraphMap::remove_edge`.
+    pub fn remove_edge(&mut self, a: N, b: N) -> Option<E> {
+        let before = self.degrees(&[a, b]);
+        let weight = self.graph.remove_edge(a, b);
+        self.update(before);
+        weight
+    }
+
+    /// Remove node `n` and its edges, like `GraphMap::remove_node`.
+    ///
+    /// Removing an edge scans the neighbor lists of both of its endpoints,
+    /// so this computes in **O(d² + Σ d(m))** time (average), for the degree
+    /// **d** of `n` and the degrees **d(m)** of its neighbors, which is at
+    /// most **O(d · Δ)** for the largest degree **Δ**.
+    pub fn remove_node(&mut self, n: N) -> bool {
+        let links = match self.graph.nodes.get(&n) {
+            Some(links) => links.clone(),
+            None => return false,
+        };
+        let mut touched: Vec<_> = links.iter().map(|&(m, _)| m).collect();
+        touched.push(n);
+        let before = self.degrees(&touched);
+        for (m, dir) in links {
+            if dir == Outgoing {
+                self.graph.remove_edge(n, m);
+            } else {
+                self.graph.remove_edge(m, n);
+            }
+        }
+        self.graph.remove_node(n);
+        self.update(before);
+        true
+    }
+
+    /// Return a reference to the underlying graph.
+    pub fn graph(&self) -> &GraphMap<N, E, Ty> {
+        &self.graph
+    }
+
+    /// Stop tracking and return the underlying graph.
+    pub fn into_graph(self) -> GraphMap<N, E, Ty> {
+        self.graph
+    }
+
+    /// Return the distinct nodes of `nodes` with their current degree, or
+    /// `None` for nodes that are not part of the graph.
+    fn degrees(&self, nodes: &[N]) -> Vec<(N, Option<usize>)> {
+        let mut degrees: Vec<(N, Option<usize>)> = Vec::with_capacity(nodes.len());
+        for &n in nodes {
+            if degrees.iter().all(|&(m, _)| m != n) {
+                degrees.push((n, self.graph.nodes.get(&n).map(Vec::len)));
+            }
+        }
+        degrees
+    }
+
+    /// Move the nodes of `before` from their old buckets to their new ones.
+    fn update(&mut self, before: Vec<(N, Option<usize>)>) {
+        for (n, old) in before {
+            let new = self.graph.nodes.get(&n).map(Vec::len);
+            if old == new {
+                continue;
+            }
+            if let Some(d) = old {
+                let count = self.histogram.get_mut(&d).unwrap();
+                *count -= 1;
+                if *count == 0 {
+                    self.histogram.remove(&d);
+                }
+            }
+            if let Some(d) = new {
+                *self.histogram.entry(d).or_insert(0) += 1;
+            }
+        }
+    }
+}
+
 iterator_wrap! {
     impl (Iterator DoubleEndedIterator ExactSizeIterator) for
     struct Nodes <'a, N> where { N: 'a + NodeTrait }
@@ -509,8 +2072,37 @@ where
             self.iter.next().map(|&(n, _)| n)
         }
     }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        if Ty::is_directed() {
+            (0, upper)
+        } else {
+            (lower, upper)
+        }
+    }
+}
+
+impl<'a, N, Ty> DoubleEndedIterator for Neighbors<'a, N, Ty>
+where
+    N: NodeTrait,
+    Ty: EdgeType,
+{
+    fn next_back(&mut self) -> Option<N> {
+        if Ty::is_directed() {
+            (&mut self.iter)
+                .rev()
+                .filter_map(|&(n, dir)| if dir == Outgoing { Some(n) } else { None })
+                .next()
+        } else {
+            self.iter.next_back().map(|&(n, _)| n)
+        }
+    }
 }
 
+/// Only for an undirected graph, where every entry of the adjacency list is
+/// a neighbor.
+impl<'a, N> ExactSizeIterator for Neighbors<'a, N, Undirected> where N: NodeTrait {}
+
 pub struct NeighborsDirected<'a, N, Ty>
 where
     N: 'a,
@@ -545,8 +2137,45 @@ where
             self.iter.next().map(|&(n, _)| n)
         }
     }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        if Ty::is_directed() {
+            (0, upper)
+        } else {
+            (lower, upper)
+        }
+    }
+}
+
+impl<'a, N, Ty> DoubleEndedIterator for NeighborsDirected<'a, N, Ty>
+where
+    N: NodeTrait,
+    Ty: EdgeType,
+{
+    fn next_back(&mut self) -> Option<N> {
+        if Ty::is_directed() {
+            let self_dir = self.dir;
+            let start_node = self.start_node;
+            (&mut self.iter)
+                .rev()
+                .filter_map(move |&(n, dir)| {
+                    if dir == self_dir || n == start_node {
+                        Some(n)
+                    } else {
+                        None
+                    }
+                })
+                .next()
+        } else {
+            self.iter.next_back().map(|&(n, _)| n)
+        }
+    }
 }
 
+/// Only for an undirected graph, where every entry of the adjacency list is
+/// a neighbor.
+impl<'a, N> ExactSizeIterator for NeighborsDirected<'a, N, Undirected> where N: NodeTrait {}
+
 pub struct Edges<'a, N, E: 'a, Ty>
 where
     N: 'a + NodeTrait,
@@ -573,6 +2202,33 @@ where
             }
         })
     }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, N, E, Ty> DoubleEndedIterator for Edges<'a, N, E, Ty>
+where
+    N: 'a + NodeTrait,
+    E: 'a,
+    Ty: EdgeType,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|b| {
+            let a = self.from;
+            match self.edges.get(&GraphMap::<N, E, Ty>::edge_key(a, b)) {
+                None => unreachable!(),
+                Some(edge) => (a, b, edge),
+            }
+        })
+    }
+}
+
+impl<'a, N, E> ExactSizeIterator for Edges<'a, N, E, Undirected>
+where
+    N: 'a + NodeTrait,
+    E: 'a,
+{
 }
 
 impl<'a, N: 'a, E: 'a, Ty> IntoEdgeReferences for &'a GraphMap<N, E, Ty>
@@ -915,3 +2571,70 @@ where
     Ty: EdgeType,
 {
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build a graph directly from its internal maps, skipping the invariants
+    /// maintained by the public API.
+    fn from_parts(
+        nodes: Vec<(u32, Vec<(u32, CompactDirection)>)>,
+        edges: Vec<((u32, u32), ())>,
+    ) -> DiGraphMap<u32, ()> {
+        GraphMap {
+            nodes: nodes.into_iter().collect(),
+            edge_order: None,
+            edges: edges.into_iter().collect(),
+            ty: PhantomData,
+            stable_removal: false,
+        }
+    }
+
+    #[test]
+    fn validate_corrupt() {
+        use self::CompactDirection::{Incoming as In, Outgoing as Out};
+
+        let valid = from_parts(
+            vec![(0, vec![(1, Out), (0, Out)]), (1, vec![(0, In)])],
+            vec![((0, 1), ()), ((0, 0), ())],
+        );
+        assert_eq!(valid.validate(), Ok(()));
+
+        let dangling = from_parts(vec![(0, vec![(1, Out)])], vec![((0, 1), ())]);
+        assert_eq!(
+            dangling.validate(),
+            Err("node 0 has neighbor 1, which is not a node".to_string())
+        );
+
+        let duplicate = from_parts(
+            vec![(0, vec![(1, Out), (1, Out)]), (1, vec![(0, In)])],
+            vec![((0, 1), ())],
+        );
+        assert_eq!(
+            duplicate.validate(),
+            Err("node 0 lists neighbor 1 more than once".to_string())
+        );
+
+        let one_sided = from_parts(vec![(0, vec![(1, Out)]), (1, vec![])], vec![((0, 1), ())]);
+        assert_eq!(
+            one_sided.validate(),
+            Err("node 0 lists neighbor 1, but not the other way around".to_string())
+        );
+
+        let no_weight = from_parts(vec![(0, vec![(1, Out)]), (1, vec![(0, In)])], vec![]);
+        assert_eq!(
+            no_weight.validate(),
+            Err("the edge from 0 to 1 has no weight".to_string())
+        );
+
+        let miscounted = from_parts(
+            vec![(0, vec![(1, Out)]), (1, vec![(0, In)])],
+            vec![((0, 1), ()), ((1, 0), ())],
+        );
+        assert_eq!(
+            miscounted.validate(),
+            Err("2 edge weights are stored for 1 edges".to_string())
+        );
+    }
+}
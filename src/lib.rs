@@ -137,6 +137,7 @@ pub mod adj;
 pub mod algo;
 mod astar;
 pub mod csr;
+pub mod csv;
 mod dijkstra;
 pub mod dot;
 #[cfg(feature = "generate")]
@@ -150,6 +151,7 @@ mod iter_utils;
 mod k_shortest_path;
 #[cfg(feature = "matrix_graph")]
 pub mod matrix_graph;
+pub mod mermaid;
 #[cfg(feature = "quickcheck")]
 mod quickcheck;
 #[cfg(feature = "serde-1")]
@@ -165,10 +167,10 @@ pub mod prelude;
 /// `Graph<N, E, Ty, Ix>` is a graph datastructure using an adjacency list representation.
 pub mod graph {
     pub use crate::graph_impl::{
-        edge_index, node_index, DefaultIx, DiGraph, Edge, EdgeIndex, EdgeIndices, EdgeReference,
-        EdgeReferences, EdgeWeightsMut, Edges, EdgesConnecting, Externals, Frozen, Graph,
-        GraphIndex, IndexType, Neighbors, Node, NodeIndex, NodeIndices, NodeReferences,
-        NodeWeightsMut, UnGraph, WalkNeighbors,
+        edge_index, node_index, ConcurrentBuilder, DefaultIx, DiGraph, Edge, EdgeIndex,
+        EdgeIndices, EdgeReference, EdgeReferences, EdgeWeightsMut, Edges, EdgesConnecting,
+        Externals, Frozen, Graph, GraphIndex, IndexType, Neighbors, Node, NodeIndex, NodeIndices,
+        NodeReferences, NodeWeightsMut, UnGraph, Versioned, WalkNeighbors,
     };
 }
 
@@ -0,0 +1,155 @@
+//! Simple Mermaid flowchart output.
+
+use std::fmt::{self, Display, Write};
+
+use crate::visit::{
+    EdgeRef, GraphProp, IntoEdgeReferences, IntoNodeReferences, NodeIndexable, NodeRef,
+};
+
+/// Render the graph `g` as a Mermaid flowchart (`graph TD`).
+///
+/// Nodes are given the identifiers `n0`, `n1`, … from their index and labeled
+/// using the `Display` implementation of their weight. Edges are written as
+/// `a -->|weight| b` for directed graphs and `a ---|weight| b` for undirected
+/// graphs. Quotes and pipes in labels are replaced by Mermaid entity codes.
+///
+/// Formatting is rather simple, this is mostly intended for debugging and
+/// documentation. Exact output may change.
+///
+/// # Example
+///
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::mermaid::to_mermaid;
+///
+/// let mut graph = Graph::<&str, u32>::new();
+/// let a = graph.add_node("A");
+/// let b = graph.add_node("B");
+/// graph.add_edge(a, b, 7);
+///
+/// assert_eq!(
+///     to_mermaid(&graph),
+///     "graph TD\n    n0[\"A\"]\n    n1[\"B\"]\n    n0 -->|7| n1\n"
+/// );
+/// ```
+pub fn to_mermaid<G>(g: G) -> String
+where
+    G: IntoEdgeReferences + IntoNodeReferences + NodeIndexable + GraphProp,
+    G::NodeWeight: Display,
+    G::EdgeWeight: Display,
+{
+    let mut out = String::new();
+    // Writing to a String cannot fail.
+    let _ = write_mermaid(g, &mut out);
+    out
+}
+
+fn write_mermaid<G, W>(g: G, out: &mut W) -> fmt::Result
+where
+    G: IntoEdgeReferences + IntoNodeReferences + NodeIndexable + GraphProp,
+    G::NodeWeight: Display,
+    G::EdgeWeight: Display,
+    W: Write,
+{
+    let arrow = if g.is_directed() { "-->" } else { "---" };
+    writeln!(out, "graph TD")?;
+    for node in g.node_references() {
+        writeln!(
+            out,
+            "    n{}[\"{}\"]",
+            g.to_index(node.id()),
+            Escaped(node.weight())
+        )?;
+    }
+    for edge in g.edge_references() {
+        writeln!(
+            out,
+            "    n{} {}|{}| n{}",
+            g.to_index(edge.source()),
+            arrow,
+            Escaped(edge.weight()),
+            g.to_index(edge.target())
+        )?;
+    }
+    Ok(())
+}
+
+/// Escape for Mermaid labels
+struct Escaper<W>(W);
+
+impl<W> Write for Escaper<W>
+where
+    W: Write,
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            self.write_char(c)?;
+        }
+        Ok(())
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        match c {
+            '"' => self.0.write_str("#quot;"),
+            '|' => self.0.write_str("#124;"),
+            '\n' => self.0.write_str("<br/>"),
+            _ => self.0.write_char(c),
+        }
+    }
+}
+
+/// Pass Display formatting through a simple escaping filter
+struct Escaped<T>(T);
+
+impl<T> Display for Escaped<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(&mut Escaper(f), "{}", &self.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{to_mermaid, Escaper};
+    use crate::prelude::{Graph, UnGraph};
+    use std::fmt::Write;
+
+    #[test]
+    fn test_escape() {
+        let mut buff = String::new();
+        {
+            let mut e = Escaper(&mut buff);
+            let _ = e.write_str("\"a|b\"\n");
+        }
+        assert_eq!(buff, "#quot;a#124;b#quot;<br/>");
+    }
+
+    #[test]
+    fn test_directed() {
+        let mut graph = Graph::<&str, &str>::new();
+        let a = graph.add_node("A");
+        let b = graph.add_node("B \"quoted\"");
+        let c = graph.add_node("C");
+        graph.add_edge(a, b, "x");
+        graph.add_edge(b, c, "y");
+        let out = to_mermaid(&graph);
+        assert!(out.starts_with("graph TD\n"));
+        assert!(out.contains("    n1[\"B #quot;quoted#quot;\"]\n"));
+        assert!(out.contains("    n0 -->|x| n1\n"));
+        assert!(out.contains("    n1 -->|y| n2\n"));
+    }
+
+    #[test]
+    fn test_undirected() {
+        let mut graph = UnGraph::<u32, u32>::new_undirected();
+        let a = graph.add_node(1);
+        let b = graph.add_node(2);
+        graph.add_edge(a, b, 5);
+        assert_eq!(
+            to_mermaid(&graph),
+            "graph TD\n    n0[\"1\"]\n    n1[\"2\"]\n    n0 ---|5| n1\n"
+        );
+    }
+}
@@ -50,6 +50,25 @@ where
         UnionFind { parent, rank }
     }
 
+    /// Return the number of elements.
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    /// Return `true` if there are no elements.
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Add a new element *n*, where *n* is the previous number of elements,
+    /// in a set of its own, and return it.
+    pub fn new_set(&mut self) -> K {
+        let x = K::new(self.parent.len());
+        self.parent.push(x);
+        self.rank.push(0);
+        x
+    }
+
     /// Return the representative for `x`.
     ///
     /// **Panics** if `x` is out of bounds.
@@ -1,5 +1,17 @@
 use std::iter;
 
+/// Draw an index in `0..len` from `rng`, which returns numbers uniformly
+/// distributed in `[0, 1)`.
+///
+/// The result is clamped to `len - 1`, which guards against generators that
+/// return exactly `1`. `len` must not be `0`.
+pub fn random_index<R>(rng: &mut R, len: usize) -> usize
+where
+    R: FnMut() -> f64,
+{
+    ((rng() * len as f64) as usize).min(len - 1)
+}
+
 pub fn enumerate<I>(iterable: I) -> iter::Enumerate<I::IntoIter>
 where
     I: IntoIterator,
@@ -9,6 +9,27 @@ use crate::visit::{
 /// An edge-reversing graph adaptor.
 ///
 /// All edges have the opposite direction with `Reversed`.
+///
+/// The adaptor is a zero-copy view: nothing is allocated, and the neighbors
+/// of a node are its predecessors in the wrapped graph, found with
+/// `neighbors_directed(n, Incoming)`. Any algorithm that only reads the graph
+/// can run on the reversed orientation this way, instead of on a reversed
+/// copy made with `Graph::reverse`.
+///
+/// The cost of a neighbor query is that of the wrapped graph's incoming
+/// query. `Graph` and `StableGraph` link the incoming edges of every node, so
+/// it is proportional to the number of predecessors, and `GraphMap` scans the
+/// incoming and outgoing neighbors of the node. `MatrixGraph`, however, scans
+/// a whole column of its adjacency matrix, in **O(|V|)** time.
+///
+/// ```
+/// use petgraph::graph::{DiGraph, NodeIndex};
+/// use petgraph::visit::{Bfs, Reversed, Walker};
+///
+/// let g = DiGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (3, 2)]);
+/// let ancestors: Vec<_> = Bfs::new(&g, NodeIndex::new(2)).iter(Reversed(&g)).collect();
+/// assert_eq!(ancestors.len(), 4);
+/// ```
 #[derive(Copy, Clone, Debug)]
 pub struct Reversed<G>(pub G);
 
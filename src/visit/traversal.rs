@@ -305,6 +305,35 @@ where
     }
 }
 
+/// Visit the nodes that can reach `start`, in breadth-first order along the
+/// incoming edges.
+///
+/// This is a `Bfs` over the `Reversed` graph, so it explores the predecessors
+/// of each node without building a reversed copy. `start` comes first,
+/// followed by its ancestors in nondecreasing distance to `start`.
+///
+/// Each step scans the incoming edges of a node with `neighbors_directed`,
+/// which for `Graph` and `GraphMap` is as cheap as scanning the outgoing
+/// ones: the whole traversal takes **O(|V| + |E|)** time.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::visit::bfs_incoming;
+///
+/// let g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (3, 2), (2, 4)]);
+/// let ancestors: Vec<_> = bfs_incoming(&g, 2.into()).map(|n| n.index()).collect();
+/// assert_eq!(ancestors, vec![2, 3, 1, 0]);
+/// ```
+pub fn bfs_incoming<G>(
+    graph: G,
+    start: G::NodeId,
+) -> WalkerIter<Bfs<G::NodeId, G::Map>, Reversed<G>>
+where
+    G: IntoNeighborsDirected + Visitable,
+{
+    Bfs::new(Reversed(graph), start).iter(Reversed(graph))
+}
+
 /// A topological order traversal for a graph.
 ///
 /// **Note** that `Topo` only visits nodes that are not part of cycles,
@@ -10,7 +10,7 @@ use petgraph as pg;
 
 use petgraph::algo::{
     dominators, has_path_connecting, is_bipartite_undirected, is_cyclic_undirected,
-    is_isomorphic_matching, min_spanning_tree,
+    is_isomorphic_matching, min_spanning_arborescence, min_spanning_tree,
 };
 
 use petgraph::graph::node_index as n;
@@ -165,6 +165,29 @@ fn bfs() {
     assert_eq!(bfs.next(&gr), None);
 }
 
+#[test]
+fn bfs_reversed_view() {
+    let mut gr = Graph::<_, ()>::new();
+    let a = gr.add_node("A");
+    let b = gr.add_node("B");
+    let c = gr.add_node("C");
+    let d = gr.add_node("D");
+    let e = gr.add_node("E");
+    gr.extend_with_edges(&[(a, b), (a, c), (b, d), (c, d), (d, e), (e, a)]);
+
+    let mut reversed = gr.clone();
+    reversed.reverse();
+    for start in gr.node_indices() {
+        let view: Vec<_> = Bfs::new(&gr, start).iter(Reversed(&gr)).collect();
+        let copy: Vec<_> = Bfs::new(&reversed, start).iter(&reversed).collect();
+        assert_eq!(view, copy);
+    }
+    let from_d: Vec<_> = Bfs::new(&gr, d).iter(Reversed(&gr)).collect();
+    assert_eq!(from_d.len(), 5);
+    assert_eq!(from_d[0], d);
+    assert_eq!(from_d[4], e);
+}
+
 #[test]
 fn mst() {
     use petgraph::data::FromElements;
@@ -574,6 +597,61 @@ fn test_astar_manhattan_heuristic() {
     }
 }
 
+#[test]
+fn greedy_best_first() {
+    use petgraph::algo::greedy_best_first;
+
+    let mut g = Graph::new();
+    let a = g.add_node((0., 0.));
+    let b = g.add_node((2., 0.));
+    let c = g.add_node((1., 1.));
+    let d = g.add_node((0., 2.));
+    let e = g.add_node((3., 3.));
+    let f = g.add_node((4., 2.));
+    let z = g.add_node((5., 5.)); // no path to node
+    g.add_edge(a, b, 2.);
+    g.add_edge(a, d, 4.);
+    g.add_edge(b, c, 1.);
+    g.add_edge(b, f, 7.);
+    g.add_edge(c, e, 5.);
+    g.add_edge(e, f, 1.);
+    g.add_edge(d, e, 1.);
+
+    let distance_to = |node: NodeIndex, goal: NodeIndex| -> f32 {
+        let (x1, y1): (f32, f32) = g[node];
+        let (x2, y2): (f32, f32) = g[goal];
+        (x2 - x1).abs() + (y2 - y1).abs()
+    };
+    for goal in g.node_indices().filter(|&goal| goal != z) {
+        let path =
+            greedy_best_first(&g, a, |node| node == goal, |node| distance_to(node, goal)).unwrap();
+        assert_eq!(path[0], a);
+        assert_eq!(*path.last().unwrap(), goal);
+        for pair in path.windows(2) {
+            assert!(g.contains_edge(pair[0], pair[1]));
+        }
+    }
+
+    // leaving b for last still finds a path, only not via b
+    let path = greedy_best_first(
+        &g,
+        a,
+        |node| node == f,
+        |node| if node == b { 1 } else { 0 },
+    );
+    assert_eq!(path, Some(vec![a, d, e, f]));
+    // the heuristic leads straight to f through b, though a -> d -> e -> f is cheaper
+    let path = greedy_best_first(
+        &g,
+        a,
+        |node| node == f,
+        |node| if node == b { 0 } else { 1 },
+    );
+    assert_eq!(path, Some(vec![a, b, f]));
+
+    assert_eq!(greedy_best_first(&g, a, |node| node == z, |_| 0), None);
+}
+
 #[test]
 fn test_astar_runtime_optimal() {
     let mut g = Graph::new();
@@ -836,6 +914,30 @@ fn is_cyclic_directed() {
     assert!(petgraph::algo::is_cyclic_directed(&gr));
 }
 
+#[test]
+fn is_on_cycle() {
+    use petgraph::algo::is_on_cycle;
+
+    // a -> b -> c -> a is a cycle, c -> d -> e is an acyclic branch
+    let mut gr = Graph::<_, ()>::new();
+    let a = gr.add_node("A");
+    let b = gr.add_node("B");
+    let c = gr.add_node("C");
+    let d = gr.add_node("D");
+    let e = gr.add_node("E");
+    gr.extend_with_edges(&[(a, b), (b, c), (c, a), (c, d), (d, e)]);
+
+    assert!(is_on_cycle(&gr, a));
+    assert!(is_on_cycle(&gr, b));
+    assert!(is_on_cycle(&gr, c));
+    assert!(!is_on_cycle(&gr, d));
+    assert!(!is_on_cycle(&gr, e));
+
+    gr.add_edge(e, e, ());
+    assert!(!is_on_cycle(&gr, d));
+    assert!(is_on_cycle(&gr, e));
+}
+
 /// Compare two scc sets. Inside each scc, the order does not matter,
 /// but the order of the sccs is significant.
 fn assert_sccs_eq(
@@ -2436,3 +2538,2431 @@ fn test_dominators_simple_fast() {
         "nodes that aren't reachable from the root do not have an idom"
     );
 }
+
+#[test]
+fn min_spanning_arborescence_nested_cycles() {
+    let mut g = Graph::<&str, u32>::new();
+    let r = g.add_node("r");
+    let a = g.add_node("a");
+    let b = g.add_node("b");
+    let c = g.add_node("c");
+    g.extend_with_edges(&[
+        (r, a, 10),
+        (r, b, 5),
+        (r, c, 8),
+        (a, b, 1),
+        (b, a, 1),
+        (b, c, 3),
+        (c, a, 2),
+    ]);
+
+    // The cheapest incoming edges form the cycle a <-> b, which after
+    // contraction forms a second cycle with c.
+    let tree = min_spanning_arborescence(&g, r, |e| *e.weight()).unwrap();
+    assert_eq!(tree.node_count(), 4);
+    assert_eq!(tree.edge_count(), 3);
+    assert!(tree.find_edge(r, b).is_some());
+    assert!(tree.find_edge(b, a).is_some());
+    assert!(tree.find_edge(b, c).is_some());
+    assert_eq!(tree.raw_edges().iter().map(|e| e.weight).sum::<u32>(), 9);
+    for n in tree.node_indices().filter(|&n| n != r) {
+        assert_eq!(tree.neighbors_directed(n, Incoming).count(), 1);
+    }
+
+    // d can not be reached from the root
+    let d = g.add_node("d");
+    g.add_edge(d, a, 1);
+    assert!(min_spanning_arborescence(&g, r, |e| *e.weight()).is_none());
+}
+
+#[test]
+fn toposort_with_depth_diamond() {
+    let mut g = Graph::<_, ()>::new();
+    let a = g.add_node("a");
+    let b = g.add_node("b");
+    let c = g.add_node("c");
+    let d = g.add_node("d");
+    let e = g.add_node("e");
+    g.extend_with_edges(&[(a, b), (a, c), (b, d), (c, d), (e, c)]);
+
+    let order = pg::algo::toposort_with_depth(&g).unwrap();
+    assert_eq!(order.len(), 5);
+    let depth = |n| order.iter().find(|&&(m, _)| m == n).unwrap().1;
+    assert_eq!(depth(a), 0);
+    assert_eq!(depth(e), 0);
+    assert_eq!(depth(b), 1);
+    assert_eq!(depth(c), 1);
+    assert_eq!(depth(d), 2);
+    let nodes: Vec<_> = order.iter().map(|&(n, _)| n).collect();
+    assert_eq!(nodes, pg::algo::toposort(&g, None).unwrap());
+
+    g.add_edge(d, a, ());
+    assert!(pg::algo::toposort_with_depth(&g).is_err());
+}
+
+#[test]
+fn reachability_matrix_chain() {
+    let mut g = Graph::<u32, ()>::new();
+    let nodes: Vec<_> = (0..4).map(|i| g.add_node(i)).collect();
+    for w in nodes.windows(2) {
+        g.add_edge(w[0], w[1], ());
+    }
+
+    let (order, matrix) = pg::algo::reachability_matrix(&g);
+    assert_eq!(order, nodes);
+    for (i, row) in matrix.iter().enumerate() {
+        for (j, &reachable) in row.iter().enumerate() {
+            assert_eq!(reachable, i < j, "entry [{}][{}]", i, j);
+        }
+    }
+
+    // closing the chain into a cycle makes everything reachable
+    g.add_edge(nodes[3], nodes[0], ());
+    let (_, matrix) = pg::algo::reachability_matrix(&g);
+    assert!(matrix.iter().all(|row| row.iter().all(|&r| r)));
+}
+
+#[test]
+fn parallel_bfs_distances_matches_sequential() {
+    let count = 2000;
+    let mut g = Graph::<(), ()>::with_capacity(count, 3 * count);
+    for _ in 0..count {
+        g.add_node(());
+    }
+    for i in 0..count {
+        g.add_edge(n(i), n((i * 7 + 3) % count), ());
+        g.add_edge(n(i), n((i * 13 + 5) % count), ());
+        if i % 3 == 0 {
+            g.add_edge(n(i), n((i + 1) % count), ());
+        }
+    }
+
+    let sequential = dijkstra(&g, n(0), None, |_| 1usize);
+    for &threads in &[0, 1, 3, 8] {
+        let parallel = pg::algo::parallel_bfs_distances(&g, n(0), threads);
+        assert_eq!(parallel, sequential);
+    }
+}
+
+#[test]
+fn from_edges_combining() {
+    let gr = DiGraph::<(), i32>::from_edges_combining(
+        &[(0, 1, 1), (1, 2, 4), (0, 1, 2), (1, 0, 8)],
+        |a, b| a + b,
+    );
+    assert_eq!(gr.node_count(), 3);
+    assert_eq!(gr.edge_count(), 3);
+    assert_eq!(gr[gr.find_edge(n(0), n(1)).unwrap()], 3);
+    assert_eq!(gr[gr.find_edge(n(1), n(0)).unwrap()], 8);
+    assert_eq!(gr[gr.find_edge(n(1), n(2)).unwrap()], 4);
+
+    // undirected edges are merged regardless of orientation
+    let gr =
+        UnGraph::<(), i32>::from_edges_combining(&[(0, 1, 1), (1, 0, 2), (0, 1, 4)], std::cmp::max);
+    assert_eq!(gr.edge_count(), 1);
+    assert_eq!(gr.raw_edges()[0].weight, 4);
+}
+
+#[test]
+fn reachable_subgraph() {
+    let mut g = Graph::<&str, u32>::new();
+    let a = g.add_node("a");
+    let b = g.add_node("b");
+    let c = g.add_node("c");
+    let d = g.add_node("d");
+    let e = g.add_node("e");
+    g.extend_with_edges(&[
+        (a, b, 1),
+        (b, c, 2),
+        (c, b, 3),
+        (d, b, 4),
+        (d, e, 5),
+        (e, a, 6),
+    ]);
+
+    let sub = pg::algo::reachable_subgraph(&g, b);
+    let mut names: Vec<_> = sub.node_indices().map(|n| sub[n]).collect();
+    names.sort();
+    assert_eq!(names, vec!["b", "c"]);
+    let mut weights: Vec<_> = sub.raw_edges().iter().map(|e| e.weight).collect();
+    weights.sort();
+    assert_eq!(weights, vec![2, 3]);
+
+    let sub = pg::algo::reachable_subgraph(&g, a);
+    assert_eq!(sub.node_count(), 3);
+    assert!(sub.node_indices().all(|n| sub[n] != "d" && sub[n] != "e"));
+    assert_eq!(sub.edge_count(), 3);
+}
+
+#[test]
+fn edge_weight_histogram() {
+    let mut g = Graph::<(), u32>::new();
+    let a = g.add_node(());
+    let b = g.add_node(());
+    for &w in &[0, 1, 2, 2, 5, 9, 10, 10, 12] {
+        g.add_edge(a, b, w);
+    }
+
+    assert_eq!(
+        pg::algo::edge_weight_histogram(&g, 5, 0., 10.),
+        vec![2, 2, 1, 0, 4]
+    );
+    assert_eq!(pg::algo::edge_weight_histogram(&g, 1, 0., 10.), vec![9]);
+    assert_eq!(pg::algo::edge_weight_histogram(&g, 2, 3., 8.), vec![5, 4]);
+    assert!(pg::algo::edge_weight_histogram(&g, 0, 0., 10.).is_empty());
+
+    // out of range weights are clamped to the outer buckets
+    let mut g = Graph::<(), f64>::new();
+    let a = g.add_node(());
+    let b = g.add_node(());
+    for &w in &[-1e30, -3., -0.5, 0.5, 1.5, 4., 1e30, std::f64::NAN] {
+        g.add_edge(a, b, w);
+    }
+    assert_eq!(pg::algo::edge_weight_histogram(&g, 2, 0., 2.), vec![5, 3]);
+}
+
+#[test]
+fn edge_weight_quantile() {
+    use petgraph::algo::edge_weight_quantile;
+
+    let mut g = Graph::<(), u32>::new();
+    let a = g.add_node(());
+    let b = g.add_node(());
+    assert_eq!(edge_weight_quantile(&g, 0.5), None);
+    for &w in &[10, 2, 9, 0, 5, 12, 1, 10, 2] {
+        g.add_edge(a, b, w);
+    }
+
+    // sorted: 0 1 2 2 5 9 10 10 12
+    assert_eq!(edge_weight_quantile(&g, 0.5), Some(5));
+    assert_eq!(edge_weight_quantile(&g, 0.), Some(0));
+    assert_eq!(edge_weight_quantile(&g, 0.25), Some(2));
+    assert_eq!(edge_weight_quantile(&g, 0.9), Some(12));
+    assert_eq!(edge_weight_quantile(&g, 1.), Some(12));
+
+    g.add_edge(b, a, 6);
+    assert_eq!(edge_weight_quantile(&g, 0.5), Some(5));
+}
+
+#[test]
+fn strong_bridges() {
+    // in a directed cycle, every edge is a strong bridge
+    let gr = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 0)]);
+    assert_eq!(
+        pg::algo::strong_bridges(&gr),
+        vec![(n(0), n(1)), (n(1), n(2)), (n(2), n(3)), (n(3), n(0))]
+    );
+
+    // two cycles sharing node 0: only the connections through 0 are spared
+    let gr = Graph::<(), ()>::from_edges(&[(0, 1), (1, 0), (0, 2), (2, 0), (1, 1)]);
+    assert_eq!(pg::algo::strong_bridges(&gr).len(), 4);
+
+    // parallel edges protect each other, and 1 -> 2 lies on no cycle
+    let gr = Graph::<(), ()>::from_edges(&[(0, 1), (1, 0), (1, 0), (1, 2)]);
+    assert_eq!(pg::algo::strong_bridges(&gr), vec![(n(0), n(1))]);
+}
+
+#[test]
+fn all_shortest_paths() {
+    use petgraph::algo::all_shortest_paths;
+
+    // two routes of cost 4 from a to d, and a longer one through e
+    let mut g = Graph::new();
+    let a = g.add_node("A");
+    let b = g.add_node("B");
+    let c = g.add_node("C");
+    let d = g.add_node("D");
+    let e = g.add_node("E");
+    g.extend_with_edges(&[
+        (a, b, 1),
+        (b, d, 3),
+        (a, c, 2),
+        (c, d, 2),
+        (a, e, 1),
+        (e, d, 4),
+    ]);
+
+    let mut paths = all_shortest_paths(&g, a, d, |e| *e.weight());
+    paths.sort();
+    assert_eq!(paths, vec![vec![a, b, d], vec![a, c, d]]);
+
+    assert_eq!(all_shortest_paths(&g, a, a, |e| *e.weight()), vec![vec![a]]);
+    assert!(all_shortest_paths(&g, d, a, |e| *e.weight()).is_empty());
+
+    // a chain of two diamonds has 2 * 2 shortest paths
+    let g = Graph::<(), ()>::from_edges(&[
+        (0, 1),
+        (0, 2),
+        (1, 3),
+        (2, 3),
+        (3, 4),
+        (3, 5),
+        (4, 6),
+        (5, 6),
+    ]);
+    let paths = all_shortest_paths(&g, n(0), n(6), |_| 1);
+    assert_eq!(paths.len(), 4);
+    for path in &paths {
+        assert_eq!(path.len(), 5);
+        assert_eq!(path[2], n(3));
+    }
+}
+
+#[test]
+fn components_above_threshold() {
+    use petgraph::algo::components_above_threshold;
+
+    // two tight clusters joined by a weak link
+    let mut g = Graph::new_undirected();
+    let a = g.add_node("A");
+    let b = g.add_node("B");
+    let c = g.add_node("C");
+    let d = g.add_node("D");
+    let e = g.add_node("E");
+    let f = g.add_node("F");
+    g.extend_with_edges(&[
+        (a, b, 0.9),
+        (b, c, 0.8),
+        (c, d, 0.2),
+        (d, e, 0.7),
+        (f, e, 0.9),
+    ]);
+
+    let components = components_above_threshold(&g, 0.1, |e| *e.weight());
+    assert_eq!(components, vec![vec![a, b, c, d, e, f]]);
+
+    let components = components_above_threshold(&g, 0.5, |e| *e.weight());
+    assert_eq!(components, vec![vec![a, b, c], vec![d, e, f]]);
+
+    let components = components_above_threshold(&g, 0.85, |e| *e.weight());
+    assert_eq!(components, vec![vec![a, b], vec![c], vec![d], vec![e, f]]);
+}
+
+#[test]
+fn connected_component_ids() {
+    use petgraph::algo::{connected_component_ids, connected_components};
+
+    let mut g = Graph::<_, ()>::new();
+    let a = g.add_node("A");
+    let b = g.add_node("B");
+    let c = g.add_node("C");
+    let d = g.add_node("D");
+    let e = g.add_node("E");
+    let f = g.add_node("F");
+    let h = g.add_node("H");
+    // {a, c, e}, {b, f}, {d}, {h}; directions do not matter
+    g.extend_with_edges(&[(e, a), (c, e), (b, f), (h, h)]);
+
+    let ids = connected_component_ids(&g);
+    assert_eq!(ids.len(), g.node_count());
+    assert_eq!(ids[&a], ids[&c]);
+    assert_eq!(ids[&a], ids[&e]);
+    assert_eq!(ids[&b], ids[&f]);
+    assert_ne!(ids[&a], ids[&b]);
+    assert_ne!(ids[&a], ids[&d]);
+    assert_ne!(ids[&b], ids[&d]);
+    assert_ne!(ids[&d], ids[&h]);
+
+    // numbered by first appearance
+    let order: Vec<_> = g.node_indices().map(|nx| ids[&nx]).collect();
+    assert_eq!(order, vec![0, 1, 0, 2, 0, 1, 3]);
+    let count = connected_components(&g);
+    assert!(ids.values().all(|&id| id < count));
+}
+
+#[test]
+fn from_edges_and_nodes() {
+    let mut source = Graph::<&str, u32>::new();
+    let a = source.add_node("A");
+    let b = source.add_node("B");
+    let c = source.add_node("C");
+    source.add_edge(a, b, 1);
+    source.add_edge(b, c, 7);
+
+    // dropping the heavy edge leaves C isolated; from_edges would lose it
+    let light = || {
+        source
+            .edge_references()
+            .filter(|e| *e.weight() < 5)
+            .map(|e| (e.source(), e.target(), *e.weight()))
+    };
+    assert_eq!(Graph::<(), u32>::from_edges(light()).node_count(), 2);
+
+    let rebuilt =
+        DiGraph::from_edges_and_nodes(light(), source.raw_nodes().iter().map(|node| node.weight));
+    assert_eq!(rebuilt.node_count(), 3);
+    assert_eq!(rebuilt.edge_count(), 1);
+    assert_eq!(rebuilt[c], "C");
+    assert!(rebuilt.contains_edge(a, b));
+    assert_eq!(rebuilt.neighbors_undirected(c).count(), 0);
+}
+
+#[test]
+#[should_panic]
+fn from_edges_and_nodes_out_of_bounds() {
+    Graph::<_, ()>::from_edges_and_nodes(&[(0, 2)], vec!["a", "b"]);
+}
+
+#[test]
+fn coarsen_by() {
+    // nodes are (rack, host) pairs; collapse them to racks
+    let mut g = Graph::<(u8, u8), u32>::new();
+    let r0h0 = g.add_node((0, 0));
+    let r0h1 = g.add_node((0, 1));
+    let r1h0 = g.add_node((1, 0));
+    let r1h1 = g.add_node((1, 1));
+    let r2h0 = g.add_node((2, 0));
+    g.add_edge(r0h0, r1h0, 1);
+    g.add_edge(r0h1, r1h1, 2);
+    g.add_edge(r0h0, r1h1, 4);
+    g.add_edge(r1h0, r0h0, 8);
+    g.add_edge(r0h0, r0h1, 16);
+    g.add_edge(r1h1, r2h0, 32);
+
+    let racks = g.coarsen_by(|&(rack, _)| rack, |x, y| x + y);
+    assert_eq!(racks.node_count(), 3);
+    assert_eq!(racks.edge_count(), 4);
+    let (r0, r1, r2) = (n(0), n(1), n(2));
+    assert_eq!(racks[r0], 0);
+    assert_eq!(racks[r1], 1);
+    assert_eq!(racks[r2], 2);
+    assert_eq!(racks[racks.find_edge(r0, r1).unwrap()], 7);
+    assert_eq!(racks[racks.find_edge(r1, r0).unwrap()], 8);
+    assert_eq!(racks[racks.find_edge(r1, r2).unwrap()], 32);
+    // the edge inside rack 0 becomes a self loop
+    assert_eq!(racks[racks.find_edge(r0, r0).unwrap()], 16);
+
+    // undirected, both directions between two racks are merged
+    let undirected = g.into_edge_type::<Undirected>();
+    let racks = undirected.coarsen_by(|&(rack, _)| rack, |x, y| x + y);
+    assert_eq!(racks.edge_count(), 3);
+    assert_eq!(racks[racks.find_edge(r1, r0).unwrap()], 15);
+}
+
+#[test]
+fn cast_weights() {
+    let mut g = Graph::<&str, i32>::new();
+    let a = g.add_node("A");
+    let b = g.add_node("B");
+    let c = g.add_node("C");
+    g.add_edge(a, b, 7);
+    g.add_edge(b, c, -3);
+    g.add_edge(c, a, 0);
+    g.add_edge(b, b, 2);
+
+    let h: Graph<&str, f64> = g.cast_weights(|&w| f64::from(w));
+    assert_eq!(h.node_count(), g.node_count());
+    assert_eq!(h.edge_count(), g.edge_count());
+    for node in g.node_indices() {
+        assert_eq!(h[node], g[node]);
+        assert!(h.neighbors(node).eq(g.neighbors(node)));
+    }
+    for edge in g.edge_indices() {
+        assert_eq!(h.edge_endpoints(edge), g.edge_endpoints(edge));
+        assert_eq!(h[edge], f64::from(g[edge]));
+    }
+}
+
+#[test]
+fn versioned() {
+    use petgraph::graph::Versioned;
+
+    let mut g = Versioned::new(Graph::<&str, u32>::new());
+    assert_eq!(g.version(), 0);
+    let a = g.add_node("A");
+    let b = g.add_node("B");
+    let c = g.add_node("C");
+    assert_eq!(g.version(), 3);
+
+    let ab = g.add_edge(a, b, 1);
+    assert_eq!(g.version(), 4);
+    g.add_edge(b, c, 2);
+    assert_eq!(g.version(), 5);
+
+    // read-only queries leave the version alone
+    assert_eq!(g.node_count(), 3);
+    assert_eq!(g[ab], 1);
+    assert!(g.contains_edge(a, b));
+    assert_eq!(dijkstra(&*g, a, None, |e| *e.weight())[&c], 3);
+    assert_eq!(g.version(), 5);
+
+    assert_eq!(g.remove_node(a), Some("A"));
+    assert_eq!(g.version(), 6);
+    assert_eq!(g.edge_count(), 1);
+    // nothing to remove, nothing changed
+    assert_eq!(g.remove_node(n(7)), None);
+    assert_eq!(g.remove_edge(EdgeIndex::new(3)), None);
+    assert_eq!(g.version(), 6);
+
+    g.graph_mut()[n(0)] = "C2";
+    assert_eq!(g.version(), 7);
+    let g = g.into_inner();
+    assert_eq!(g[n(0)], "C2");
+}
+
+#[test]
+fn out_weight_map() {
+    use petgraph::algo::{in_weight_map, out_weight_map};
+
+    let mut g =
+        Graph::<(), i32>::from_edges(&[(0, 1, 2), (0, 2, -1), (1, 2, 4), (2, 2, 3), (3, 0, 5)]);
+    g.add_node(());
+    let out_weights = out_weight_map(&g);
+    let in_weights = in_weight_map(&g);
+    assert_eq!(out_weights.len(), g.node_count());
+    assert_eq!(in_weights.len(), g.node_count());
+    for node in g.node_indices() {
+        let out_strength: i32 = g.edges(node).map(|e| *e.weight()).sum();
+        let in_strength: i32 = g.edges_directed(node, Incoming).map(|e| *e.weight()).sum();
+        assert_eq!(out_weights[&node], out_strength);
+        assert_eq!(in_weights[&node], in_strength);
+    }
+    assert_eq!(out_weights[&n(4)], 0);
+
+    // undirected edges count for both endpoints, self loops once
+    let g = Graph::<(), i32, Undirected>::from_edges(&[(0, 1, 2), (1, 2, 4), (2, 2, 3)]);
+    let out_weights = out_weight_map(&g);
+    assert_eq!(out_weights, in_weight_map(&g));
+    assert_eq!(out_weights[&n(1)], 6);
+    assert_eq!(out_weights[&n(2)], 7);
+}
+
+#[test]
+fn sample_edge() {
+    use petgraph::algo::sample_edge;
+    use rand::{ChaChaRng, Rng, SeedableRng};
+
+    let g = Graph::<(), f64>::from_edges(&[(0, 1, 1.), (1, 2, 8.), (2, 0, 1.), (0, 2, 0.)]);
+    let mut rng = ChaChaRng::from_seed([7; 32]);
+    let mut rng = || rng.gen::<f64>();
+    let mut counts = [0; 4];
+    for _ in 0..10_000 {
+        counts[sample_edge(&g, &mut rng).unwrap().id().index()] += 1;
+    }
+    // expected 1000, 8000, 1000, 0
+    assert!(counts[1] > 7500 && counts[1] < 8500, "{:?}", counts);
+    assert!(counts[0] > 700 && counts[0] < 1300, "{:?}", counts);
+    assert!(counts[2] > 700 && counts[2] < 1300, "{:?}", counts);
+    assert_eq!(counts[3], 0);
+
+    let mut g = Graph::<(), f64>::new();
+    assert!(sample_edge(&g, &mut rng).is_none());
+    g.extend_with_edges(&[(0, 1, 0.)]);
+    assert!(sample_edge(&g, &mut rng).is_none());
+}
+
+#[test]
+fn bfs_incoming() {
+    use petgraph::visit::bfs_incoming;
+
+    // 0 -> 1 -> 3 -> 5 -> 6 and 4 -> 2 -> 3, with 7 isolated
+    let mut g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 3), (2, 3), (3, 5), (4, 2), (5, 6)]);
+    g.add_node(());
+    let start = n(5);
+    let order: Vec<_> = bfs_incoming(&g, start).collect();
+    assert_eq!(order[0], start);
+
+    let mut ancestors = order.clone();
+    ancestors.sort();
+    let expected: Vec<_> = g
+        .node_indices()
+        .filter(|&node| has_path_connecting(&g, node, start, None))
+        .collect();
+    assert_eq!(ancestors, expected);
+
+    let distances = dijkstra(Reversed(&g), start, None, |_| 1);
+    for pair in order.windows(2) {
+        assert!(distances[&pair[0]] <= distances[&pair[1]]);
+    }
+    assert_eq!(distances[order.last().unwrap()], 3);
+}
+
+#[test]
+fn edge_map() {
+    let mut g = Graph::new();
+    let a = g.add_node("A");
+    let b = g.add_node("B");
+    let c = g.add_node("C");
+    g.add_edge(a, b, 1.5);
+    g.add_edge(b, c, 2.);
+    g.add_edge(c, a, -1.);
+    g.add_edge(c, c, 0.5);
+
+    let weights = pg::algo::edge_map(&g);
+    assert_eq!(weights.len(), g.edge_count());
+    for edge in g.edge_references() {
+        assert_eq!(weights[&(edge.source(), edge.target())], *edge.weight());
+    }
+    assert_eq!(weights.get(&(a, c)), None);
+}
+
+#[test]
+fn dijkstra_avoiding() {
+    use petgraph::algo::dijkstra_avoiding;
+    use std::collections::HashSet;
+
+    let mut g = Graph::new();
+    let a = g.add_node("A");
+    let b = g.add_node("B");
+    let c = g.add_node("C");
+    let d = g.add_node("D");
+    let e = g.add_node("E");
+    g.extend_with_edges(&[
+        (a, b, 1),
+        (b, e, 1),
+        (a, c, 3),
+        (c, d, 3),
+        (d, e, 3),
+        (c, e, 9),
+    ]);
+
+    let none = HashSet::new();
+    assert_eq!(
+        dijkstra_avoiding(&g, a, e, |e| *e.weight(), &none),
+        Some((2, vec![a, b, e]))
+    );
+
+    // forbidding the cheap intermediate node forces the detour
+    let forbidden: HashSet<_> = vec![b].into_iter().collect();
+    assert_eq!(
+        dijkstra_avoiding(&g, a, e, |e| *e.weight(), &forbidden),
+        Some((9, vec![a, c, d, e]))
+    );
+
+    // start and goal may be forbidden themselves
+    let forbidden: HashSet<_> = vec![a, e, d].into_iter().collect();
+    assert_eq!(
+        dijkstra_avoiding(&g, a, e, |e| *e.weight(), &forbidden),
+        Some((2, vec![a, b, e]))
+    );
+
+    let forbidden: HashSet<_> = vec![b, c].into_iter().collect();
+    assert_eq!(
+        dijkstra_avoiding(&g, a, e, |e| *e.weight(), &forbidden),
+        None
+    );
+}
+
+#[test]
+fn reachable_within_budget() {
+    use petgraph::algo::reachable_within_budget;
+
+    let mut g = Graph::new();
+    let a = g.add_node("A");
+    let b = g.add_node("B");
+    let c = g.add_node("C");
+    let d = g.add_node("D");
+    let e = g.add_node("E");
+    let f = g.add_node("F");
+    g.extend_with_edges(&[
+        (a, b, 3),
+        (b, c, 4),
+        // too expensive directly, but c is within budget via b
+        (a, c, 9),
+        (c, d, 1),
+        (a, e, 8),
+        (f, a, 1),
+    ]);
+
+    let reached = reachable_within_budget(&g, a, 8, |e| *e.weight());
+    // d costs 8 (exactly the budget), e costs 8
+    let expected: HashSet<_> = vec![a, b, c, d, e].into_iter().collect();
+    assert_eq!(reached, expected);
+
+    let reached = reachable_within_budget(&g, a, 7, |e| *e.weight());
+    // d and e are just beyond the budget
+    let expected: HashSet<_> = vec![a, b, c].into_iter().collect();
+    assert_eq!(reached, expected);
+
+    let reached = reachable_within_budget(&g, a, 0, |e| *e.weight());
+    assert_eq!(reached.into_iter().collect::<Vec<_>>(), vec![a]);
+
+    // agrees with dijkstra
+    let costs = dijkstra(&g, a, None, |e| *e.weight());
+    for budget in 0..12 {
+        let reached = reachable_within_budget(&g, a, budget, |e| *e.weight());
+        let within: HashSet<_> = costs
+            .iter()
+            .filter(|&(_, &cost)| cost <= budget)
+            .map(|(&node, _)| node)
+            .collect();
+        assert_eq!(reached, within);
+    }
+}
+
+#[test]
+fn spfa() {
+    use petgraph::algo::spfa;
+
+    // matches dijkstra on non-negative costs, including unequal parallel
+    // edges and an unreachable node
+    let g = Graph::<(), i32>::from_edges(&[
+        (0, 1, 7),
+        (0, 2, 9),
+        (0, 5, 14),
+        (1, 2, 10),
+        (1, 3, 15),
+        (2, 3, 11),
+        (2, 5, 2),
+        (3, 4, 6),
+        (4, 5, 9),
+        (5, 4, 9),
+        (5, 4, 1),
+        (6, 0, 1),
+    ]);
+    for start in g.node_indices() {
+        assert_eq!(
+            spfa(&g, start, |e| *e.weight()),
+            Ok(dijkstra(&g, start, None, |e| *e.weight()))
+        );
+    }
+
+    // negative costs without a negative cycle
+    let g = Graph::<(), i32>::from_edges(&[(0, 1, 2), (1, 2, -3), (0, 2, 1), (2, 3, 2), (3, 1, 1)]);
+    let costs = spfa(&g, n(0), |e| *e.weight()).unwrap();
+    assert_eq!(costs[&n(2)], -1);
+    assert_eq!(costs[&n(3)], 1);
+    assert_eq!(costs[&n(1)], 2);
+
+    // 1 -> 2 -> 3 -> 1 costs -1 in total, and leads on to 4
+    let g = Graph::<(), i32>::from_edges(&[(0, 1, 1), (1, 2, 2), (2, 3, -4), (3, 1, 1), (3, 4, 0)]);
+    let node = spfa(&g, n(0), |e| *e.weight()).unwrap_err();
+    assert!(node != n(0));
+    // the cycle is not reachable from 4
+    assert_eq!(spfa(&g, n(4), |e| *e.weight()).unwrap().len(), 1);
+    // a negative self loop
+    let g = Graph::<(), i32>::from_edges(&[(0, 0, -1)]);
+    assert_eq!(spfa(&g, n(0), |e| *e.weight()), Err(n(0)));
+}
+
+#[test]
+fn random_neighbor() {
+    use petgraph::algo::random_neighbor;
+    use rand::{ChaChaRng, Rng, SeedableRng};
+
+    let g = Graph::<(), ()>::from_edges(&[(0, 1), (0, 2), (0, 3), (2, 0)]);
+    let draw = |seed| {
+        let mut rng = ChaChaRng::from_seed([seed; 32]);
+        let mut rng = || rng.gen::<f64>();
+        (0..20)
+            .map(|_| random_neighbor(&g, n(0), &mut rng).unwrap())
+            .collect::<Vec<_>>()
+    };
+    // the same seed gives the same choices
+    let picks = draw(3);
+    assert_eq!(picks, draw(3));
+    for neighbor in &[n(1), n(2), n(3)] {
+        assert!(picks.contains(neighbor));
+    }
+
+    // fixed draws select by position in `neighbors`
+    let neighbors: Vec<_> = g.neighbors(n(0)).collect();
+    assert_eq!(random_neighbor(&g, n(0), &mut || 0.), Some(neighbors[0]));
+    assert_eq!(random_neighbor(&g, n(0), &mut || 0.5), Some(neighbors[1]));
+    assert_eq!(random_neighbor(&g, n(0), &mut || 0.99), Some(neighbors[2]));
+    assert_eq!(random_neighbor(&g, n(0), &mut || 1.), Some(neighbors[2]));
+
+    assert_eq!(random_neighbor(&g, n(1), &mut || 0.5), None);
+}
+
+#[test]
+fn sample_neighbors() {
+    use petgraph::algo::{sample_neighbors, sample_neighbors_weighted};
+    use rand::{ChaChaRng, Rng, SeedableRng};
+
+    let mut g = Graph::<(), f64>::new();
+    let center = g.add_node(());
+    for i in 0..10 {
+        let leaf = g.add_node(());
+        g.add_edge(center, leaf, i as f64);
+    }
+    g.add_edge(n(1), center, 1.);
+
+    let mut rng = ChaChaRng::from_seed([5; 32]);
+    let mut rng = || rng.gen::<f64>();
+    for &k in &[0, 1, 4, 10, 15] {
+        for sample in &[
+            sample_neighbors(&g, center, k, &mut rng),
+            sample_neighbors_weighted(&g, center, k, &mut rng),
+        ] {
+            assert_eq!(sample.len(), k.min(10));
+            let distinct: HashSet<_> = sample.iter().map(|&(nx, _)| nx).collect();
+            assert_eq!(distinct.len(), sample.len());
+            for &(nx, w) in sample {
+                assert_eq!(g[g.find_edge(center, nx).unwrap()], w);
+            }
+        }
+    }
+    assert_eq!(sample_neighbors(&g, n(1), 3, &mut rng), vec![(center, 1.)]);
+    assert_eq!(sample_neighbors(&g, n(2), 3, &mut rng), vec![]);
+
+    // the zero weight edge goes last, heavy edges are picked more often
+    let mut counts = [0; 10];
+    for _ in 0..2000 {
+        let sample = sample_neighbors_weighted(&g, center, 9, &mut rng);
+        assert!(sample.iter().all(|&(_, w)| w != 0.));
+        counts[sample[0].1 as usize] += 1;
+    }
+    assert!(counts[9] > counts[1] * 4, "{:?}", counts);
+}
+
+#[test]
+fn toposort_subset() {
+    use petgraph::algo::toposort_subset;
+
+    let mut chain = Graph::<_, ()>::new();
+    let a = chain.add_node("a");
+    let b = chain.add_node("b");
+    let c = chain.add_node("c");
+    let d = chain.add_node("d");
+    chain.add_node("e");
+    chain.extend_with_edges(&[(a, b), (b, c), (c, d)]);
+    assert_eq!(toposort_subset(&chain, vec![d, a, c]), Ok(vec![a, c, d]));
+    assert_eq!(toposort_subset(&chain, vec![c, c]), Ok(vec![c]));
+    assert_eq!(toposort_subset(&chain, vec![]), Ok(vec![]));
+
+    // a cycle through b and c only matters once both are selected
+    chain.add_edge(c, b, ());
+    assert_eq!(toposort_subset(&chain, vec![d, a, c]), Ok(vec![a, c, d]));
+    let err = toposort_subset(&chain, vec![b, d, c]).unwrap_err();
+    assert!(err.node_id() == b || err.node_id() == c);
+
+    // every reachable pair is ordered, in a larger graph
+    let g = Graph::<(), ()>::from_edges(&[(0, 5), (5, 2), (2, 7), (1, 7), (7, 3), (4, 4), (6, 1)]);
+    let order = toposort_subset(&g, (0..8).map(n).filter(|nx| nx.index() % 2 == 0)).unwrap();
+    assert_eq!(order.len(), 4);
+    for (i, &a) in order.iter().enumerate() {
+        for &b in &order[i + 1..] {
+            assert!(!has_path_connecting(&g, b, a, None));
+        }
+    }
+}
+
+#[test]
+fn toposort_by_key() {
+    use petgraph::algo::toposort_by_key;
+
+    // tasks with their cost; compile -> link -> package and docs -> package
+    let mut g = Graph::<_, ()>::new();
+    let docs = g.add_node(("docs", 2));
+    let compile = g.add_node(("compile", 8));
+    g.add_node(("tests", 5));
+    let link = g.add_node(("link", 3));
+    let package = g.add_node(("package", 1));
+    g.add_node(("lint", 5));
+    g.extend_with_edges(&[(compile, link), (link, package), (docs, package)]);
+
+    let order = toposort_by_key(&g, |nx| g[nx].1).unwrap();
+    let names: Vec<_> = order.iter().map(|&nx| g[nx].0).collect();
+    // equal keys keep the node order: tests before lint
+    assert_eq!(
+        names,
+        vec!["compile", "tests", "lint", "link", "docs", "package"]
+    );
+    // still a valid topological order
+    for edge in g.edge_references() {
+        let pos = |nx| order.iter().position(|&x| x == nx).unwrap();
+        assert!(pos(edge.source()) < pos(edge.target()));
+    }
+
+    // a lower key for compile can not move link before it
+    let order = toposort_by_key(&g, |nx| if nx == compile { 0 } else { g[nx].1 }).unwrap();
+    let names: Vec<_> = order.iter().map(|&nx| g[nx].0).collect();
+    assert_eq!(
+        names,
+        vec!["tests", "lint", "docs", "compile", "link", "package"]
+    );
+
+    g.add_edge(package, compile, ());
+    let err = toposort_by_key(&g, |nx| g[nx].1).unwrap_err();
+    assert!([compile, link, package].contains(&err.node_id()));
+
+    let mut g = Graph::<(), ()>::new();
+    let a = g.add_node(());
+    g.add_edge(a, a, ());
+    assert_eq!(toposort_by_key(&g, |_| 0).unwrap_err().node_id(), a);
+}
+
+#[test]
+fn edge_coloring() {
+    use petgraph::algo::edge_coloring;
+
+    // the petersen graph has maximum degree 3
+    let g = Graph::<(), (), Undirected>::from_edges(&[
+        (0, 1),
+        (1, 2),
+        (2, 3),
+        (3, 4),
+        (4, 0),
+        (0, 5),
+        (1, 6),
+        (2, 7),
+        (3, 8),
+        (4, 9),
+        (5, 7),
+        (7, 9),
+        (9, 6),
+        (6, 8),
+        (8, 5),
+    ]);
+    let colors = edge_coloring(&g);
+    assert_eq!(colors.len(), g.edge_count());
+    for node in g.node_indices() {
+        let mut seen = HashSet::new();
+        for edge in g.edges(node) {
+            let key = (edge.source(), edge.target());
+            let color = colors.get(&key).or_else(|| colors.get(&(key.1, key.0)));
+            assert!(seen.insert(*color.unwrap()), "repeated color at {:?}", node);
+        }
+    }
+    assert!(colors.values().all(|&c| c < 2 * 3 - 1));
+
+    // directed and parallel edges are colored as undirected ones
+    let g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 0), (1, 2), (2, 0)]);
+    let colors = edge_coloring(&g);
+    assert_eq!(colors.len(), 3);
+    let mut distinct: Vec<_> = colors.values().collect();
+    distinct.sort();
+    distinct.dedup();
+    assert_eq!(distinct.len(), 3);
+}
+
+#[test]
+fn edge_betweenness() {
+    use petgraph::algo::edge_betweenness;
+
+    // two triangles joined by the bridge 2 - 3
+    let g = Graph::<(), (), Undirected>::from_edges(&[
+        (0, 1),
+        (1, 2),
+        (2, 0),
+        (2, 3),
+        (3, 4),
+        (4, 5),
+        (5, 3),
+    ]);
+    let betweenness = edge_betweenness(&g);
+    assert_eq!(betweenness.len(), g.edge_count());
+    let bridge = betweenness[&(n(2), n(3))];
+    // every path from one triangle to the other crosses the bridge
+    assert_eq!(bridge, 9.);
+    for (&pair, &value) in &betweenness {
+        if pair != (n(2), n(3)) {
+            assert!(value < bridge, "{:?} has betweenness {}", pair, value);
+        }
+    }
+    assert_eq!(betweenness[&(n(0), n(1))], 1.);
+    assert_eq!(betweenness[&(n(2), n(0))], 4.);
+    assert_eq!(betweenness[&(n(5), n(3))], 4.);
+
+    // two shortest paths from 0 to 3 share the pair between them
+    let g = Graph::<(), ()>::from_edges(&[(0, 1), (0, 2), (1, 3), (2, 3)]);
+    let betweenness = edge_betweenness(&g);
+    for &(a, b) in &[(0, 1), (0, 2), (1, 3), (2, 3)] {
+        assert_eq!(betweenness[&(n(a), n(b))], 1.5);
+    }
+}
+
+#[test]
+fn reversed_reachable_from() {
+    use petgraph::algo::{reachable_subgraph, reversed_reachable_from};
+
+    let mut g = Graph::new();
+    let a = g.add_node("A");
+    let b = g.add_node("B");
+    let c = g.add_node("C");
+    let d = g.add_node("D");
+    let e = g.add_node("E");
+    g.extend_with_edges(&[
+        (a, b, 1),
+        (b, c, 2),
+        (c, a, 3),
+        (d, a, 4),
+        (b, e, 5),
+        (d, e, 6),
+    ]);
+
+    let rev = reversed_reachable_from(&g, b);
+    let mut expected = reachable_subgraph(&g, b);
+    expected.reverse();
+
+    assert_eq!(rev.node_count(), 4);
+    assert_eq!(
+        rev.raw_nodes()
+            .iter()
+            .map(|node| node.weight)
+            .collect::<Vec<_>>(),
+        expected
+            .raw_nodes()
+            .iter()
+            .map(|node| node.weight)
+            .collect::<Vec<_>>()
+    );
+    let edges = |g: &Graph<&str, i32>| {
+        g.edge_references()
+            .map(|e| (e.source().index(), e.target().index(), *e.weight()))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(edges(&rev), edges(&expected));
+    // the nodes are A, B, C, E
+    assert_eq!(
+        edges(&rev),
+        vec![(1, 0, 1), (2, 1, 2), (0, 2, 3), (3, 1, 5)]
+    );
+}
+
+#[test]
+fn seeded_subgraph() {
+    use petgraph::algo::seeded_subgraph;
+
+    let mut g = Graph::new();
+    let a = g.add_node("A");
+    let b = g.add_node("B");
+    let c = g.add_node("C");
+    let d = g.add_node("D");
+    let e = g.add_node("E");
+    let f = g.add_node("F");
+    let h = g.add_node("H");
+    g.extend_with_edges(&[
+        (a, b, 0.9),
+        (b, c, 0.9),
+        // too far from any seed
+        (c, d, 0.9),
+        // too weak to extend the region
+        (a, e, 0.1),
+        (f, h, 0.7),
+        (h, b, 0.6),
+        // weak, but both ends are in the region
+        (c, a, 0.1),
+    ]);
+
+    let region = seeded_subgraph(&g, vec![a, f], 2, 0.5, |e| *e.weight());
+    let mut names: Vec<_> = region.node_indices().map(|nx| region[nx]).collect();
+    names.sort();
+    assert_eq!(names, vec!["A", "B", "C", "F", "H"]);
+    assert_eq!(region.edge_count(), 5);
+    let weak: Vec<_> = region
+        .edge_references()
+        .filter(|e| *e.weight() < 0.5)
+        .map(|e| (region[e.source()], region[e.target()]))
+        .collect();
+    assert_eq!(weak, vec![("C", "A")]);
+
+    // lowering the threshold lets the weak edge through
+    let region = seeded_subgraph(&g, vec![a], 1, 0.0, |e| *e.weight());
+    assert_eq!(region.node_count(), 3);
+    assert!(region.node_indices().any(|nx| region[nx] == "E"));
+
+    let region = seeded_subgraph(&g, vec![a, a], 0, 0.5, |e| *e.weight());
+    assert_eq!(region.node_count(), 1);
+}
+
+#[test]
+fn spanning_tree_from() {
+    use petgraph::algo::{spanning_tree_from, TreeStrategy};
+
+    // g -> a is not reachable from a
+    let mut g = Graph::<_, ()>::new();
+    let a = g.add_node("A");
+    let b = g.add_node("B");
+    let c = g.add_node("C");
+    let d = g.add_node("D");
+    let e = g.add_node("E");
+    let f = g.add_node("F");
+    let gx = g.add_node("G");
+    g.extend_with_edges(&[
+        (a, b),
+        (a, c),
+        (b, d),
+        (c, b),
+        (c, e),
+        (e, d),
+        (d, f),
+        (gx, a),
+    ]);
+
+    let tree = spanning_tree_from(&g, a, TreeStrategy::Bfs);
+    assert_eq!(tree.node_count(), 6);
+    assert_eq!(tree.edge_count(), tree.node_count() - 1);
+    let parent_of = |name: &str| {
+        let child = tree.node_indices().find(|&nx| tree[nx] == name).unwrap();
+        let parents: Vec<_> = tree.neighbors_directed(child, Incoming).collect();
+        match parents[..] {
+            [] => None,
+            [parent] => Some(tree[parent]),
+            _ => panic!("{} has several parents", name),
+        }
+    };
+    assert_eq!(parent_of("A"), None);
+    assert_eq!(parent_of("B"), Some("A"));
+    assert_eq!(parent_of("C"), Some("A"));
+    assert_eq!(parent_of("D"), Some("B"));
+    assert_eq!(parent_of("E"), Some("C"));
+    assert_eq!(parent_of("F"), Some("D"));
+    assert!(!tree.node_indices().any(|nx| tree[nx] == "G"));
+
+    let tree = spanning_tree_from(&g, a, TreeStrategy::Dfs);
+    assert_eq!(tree.node_count(), 6);
+    assert_eq!(tree.edge_count(), 5);
+    assert!(!petgraph::algo::is_cyclic_directed(&tree));
+    assert_eq!(Dfs::new(&tree, n(0)).iter(&tree).count(), 6);
+    // every tree edge is an edge of g
+    for edge in tree.edge_references() {
+        let (parent, child) = (tree[edge.source()], tree[edge.target()]);
+        assert!(g
+            .edge_references()
+            .any(|e| g[e.source()] == parent && g[e.target()] == child));
+    }
+    // the search goes deep first: every other node is found below one child of a
+    assert_eq!(tree.edges(n(0)).count(), 1);
+}
+
+#[test]
+fn random_spanning_tree() {
+    use petgraph::algo::{connected_components, random_spanning_tree};
+    use rand::{ChaChaRng, Rng, SeedableRng};
+
+    // 4 only has an edge into the component, 5 and 6 are apart
+    let g = Graph::<u32, u32>::from_edges(&[
+        (0, 1, 10),
+        (1, 2, 11),
+        (2, 3, 12),
+        (3, 0, 13),
+        (0, 2, 14),
+        (4, 1, 15),
+        (5, 6, 16),
+    ]);
+    let weights: HashSet<_> = [10, 11, 12, 13, 14, 15].iter().cloned().collect();
+    let mut trees = HashSet::new();
+    for seed in 0..20 {
+        let mut rng = ChaChaRng::from_seed([seed; 32]);
+        let mut rng = || rng.gen::<f64>();
+        let tree = random_spanning_tree(&g, &mut rng);
+        assert_eq!(tree.node_count(), 5);
+        assert_eq!(tree.edge_count(), 4);
+        assert_eq!(connected_components(&tree), 1);
+        for nx in tree.node_indices() {
+            let parents = tree.neighbors_directed(nx, Incoming).count();
+            assert_eq!(parents, if nx.index() == 0 { 0 } else { 1 });
+        }
+        let mut edges: Vec<_> = tree.raw_edges().iter().map(|e| e.weight).collect();
+        assert!(edges.iter().all(|w| weights.contains(w)));
+        // 4 hangs below 1
+        assert!(edges.contains(&15));
+        edges.sort();
+        trees.insert(edges);
+    }
+    assert!(trees.len() > 1, "{:?}", trees);
+
+    let mut rng = || 0.5;
+    let tree = random_spanning_tree(&Graph::<(), ()>::new(), &mut rng);
+    assert_eq!(tree.node_count(), 0);
+}
+
+#[test]
+fn concurrent_builder() {
+    use petgraph::graph::ConcurrentBuilder;
+    use std::sync::Arc;
+    use std::thread;
+
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Graph<String, u32>>();
+    assert_send_sync::<ConcurrentBuilder<u32>>();
+
+    let builder = Arc::new(ConcurrentBuilder::<(u32, u32)>::new(3));
+    let threads: Vec<_> = (0..4u32)
+        .map(|t| {
+            let builder = builder.clone();
+            thread::spawn(move || {
+                // every thread adds the edges from nodes 5 * t to 5 * t + 4
+                for i in 0..5 {
+                    let a = 5 * t + i;
+                    builder.add_edge(n(a as usize), n(((a + 7) % 20) as usize), (t, i));
+                    builder.add_edge(n(a as usize), n(((a + 1) % 20) as usize), (t, i + 5));
+                }
+            })
+        })
+        .collect();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+    assert_eq!(builder.edge_count(), 40);
+
+    let builder = Arc::try_unwrap(builder).ok().unwrap();
+    let g: DiGraph<(), (u32, u32)> = builder.build();
+    assert_eq!(g.node_count(), 20);
+    assert_eq!(g.edge_count(), 40);
+    for a in 0..20 {
+        let (t, i) = (a / 5, a % 5);
+        let b = g
+            .find_edge(n(a as usize), n(((a + 7) % 20) as usize))
+            .unwrap();
+        assert_eq!(g[b], (t, i));
+        let b = g
+            .find_edge(n(a as usize), n(((a + 1) % 20) as usize))
+            .unwrap();
+        assert_eq!(g[b], (t, i + 5));
+    }
+
+    let empty: DiGraph<(), ()> = ConcurrentBuilder::new(1).build();
+    assert_eq!(empty.node_count(), 0);
+}
+
+#[test]
+fn canonical_form() {
+    use petgraph::algo::canonical_form;
+
+    fn edges<E: Clone>(g: &DiGraph<usize, E>) -> Vec<(usize, usize, E)> {
+        g.raw_edges()
+            .iter()
+            .map(|e| (e.source().index(), e.target().index(), e.weight.clone()))
+            .collect()
+    }
+
+    // the same directed graph, with the nodes numbered differently
+    let a = Graph::<(), u8>::from_edges(&[(0, 1, 1), (1, 2, 2), (2, 0, 3), (2, 3, 4), (4, 3, 5)]);
+    let perm = [3, 0, 4, 1, 2];
+    let b = Graph::<(), u8>::from_edges(
+        a.raw_edges()
+            .iter()
+            .map(|e| (perm[e.source().index()], perm[e.target().index()], e.weight)),
+    );
+    let (ca, a_nodes) = canonical_form(&a);
+    let (cb, b_nodes) = canonical_form(&b);
+    assert_eq!(edges(&ca), edges(&cb));
+    assert!(pg::algo::is_isomorphic(&ca, &a));
+    // the node weights are the labels, the same for isomorphic graphs
+    for i in 0..5 {
+        assert_eq!(ca[n(i)], i);
+        assert_eq!(cb[n(i)], i);
+        assert_eq!(b_nodes[i].index() as u32, perm[a_nodes[i].index()]);
+    }
+    // the mapping leads back to the original nodes
+    for e in ca.raw_edges() {
+        let (s, t) = (a_nodes[e.source().index()], a_nodes[e.target().index()]);
+        assert_eq!(a[a.find_edge(s, t).unwrap()], e.weight);
+    }
+
+    // symmetric undirected graphs: a 6-cycle, and a 6-cycle with a chord
+    let cycle = |labels: [usize; 6]| {
+        let mut g = UnGraph::<(), ()>::new_undirected();
+        for _ in 0..6 {
+            g.add_node(());
+        }
+        for i in 0..6 {
+            g.add_edge(n(labels[i]), n(labels[(i + 1) % 6]), ());
+        }
+        g
+    };
+    let form = |g: &UnGraph<(), ()>| edges(&canonical_form(g).0);
+    let c1 = cycle([0, 1, 2, 3, 4, 5]);
+    let mut c2 = cycle([4, 2, 0, 5, 1, 3]);
+    assert_eq!(form(&c1), form(&c2));
+    c2.add_edge(n(4), n(5), ());
+    let mut c3 = c1.clone();
+    c3.add_edge(n(0), n(3), ());
+    assert_eq!(form(&c2), form(&c3));
+    assert_ne!(form(&c1), form(&c3));
+
+    let (empty, nodes) = canonical_form(&Graph::<(), ()>::new());
+    assert_eq!(empty.node_count(), 0);
+    assert!(nodes.is_empty());
+}
+
+#[test]
+fn center_and_periphery() {
+    use petgraph::algo::{center, eccentricity, periphery};
+
+    // a path of 5 nodes has one middle node, one of 6 has two
+    let path = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 4)]);
+    assert_eq!(center(&path), vec![n(2)]);
+    assert_eq!(periphery(&path), vec![n(0), n(4)]);
+    let mut path = path;
+    path.extend_with_edges(&[(4, 5)]);
+    assert_eq!(center(&path), vec![n(2), n(3)]);
+    assert_eq!(periphery(&path), vec![n(0), n(5)]);
+    assert_eq!(eccentricity(&path, n(0)), 5);
+    assert_eq!(eccentricity(&path, n(3)), 3);
+
+    // a directed path only reaches forward
+    let path = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2)]);
+    assert_eq!(center(&path), vec![n(2)]);
+    assert_eq!(periphery(&path), vec![n(0)]);
+
+    // an isolated node has eccentricity 0
+    let mut g = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (1, 2)]);
+    g.add_node(());
+    assert_eq!(center(&g), vec![n(3)]);
+    assert_eq!(periphery(&g), vec![n(0), n(2)]);
+
+    assert!(center(&Graph::<(), ()>::new()).is_empty());
+}
+
+#[test]
+fn min_mean_cycle() {
+    use petgraph::algo::min_mean_cycle;
+
+    // 0 -> 1 -> 2 -> 0 has mean 3, 3 -> 4 -> 5 -> 3 has mean 2 although it
+    // costs more in total than 6 -> 7 -> 6, which has mean 2.5
+    let g = Graph::<(), f64>::from_edges(&[
+        (0, 1, 3.),
+        (1, 2, 3.),
+        (2, 0, 3.),
+        (2, 3, -5.),
+        (3, 4, 1.),
+        (4, 5, 4.),
+        (5, 3, 1.),
+        (5, 6, 0.),
+        (6, 7, 1.),
+        (7, 6, 4.),
+    ]);
+    let (mean, mut cycle) = min_mean_cycle(&g, |e| *e.weight()).unwrap();
+    assert_eq!(mean, 2.);
+    cycle.sort();
+    assert_eq!(cycle, vec![n(3), n(4), n(5)]);
+
+    // the cycle is returned in order
+    let (mean, cycle) = min_mean_cycle(&g, |e| -*e.weight()).unwrap();
+    assert_eq!(mean, -3.);
+    let start = cycle.iter().position(|&v| v == n(0)).unwrap();
+    let rotated: Vec<_> = cycle[start..]
+        .iter()
+        .chain(&cycle[..start])
+        .cloned()
+        .collect();
+    assert_eq!(rotated, vec![n(0), n(1), n(2)]);
+
+    // a self loop, and an acyclic graph
+    let mut g = Graph::<(), f64>::from_edges(&[(0, 1, 1.), (1, 2, 1.), (0, 2, 5.)]);
+    assert_eq!(min_mean_cycle(&g, |e| *e.weight()), None);
+    g.add_edge(n(2), n(2), 7.);
+    assert_eq!(min_mean_cycle(&g, |e| *e.weight()), Some((7., vec![n(2)])));
+    assert_eq!(
+        min_mean_cycle(&Graph::<(), f64>::new(), |e| *e.weight()),
+        None
+    );
+}
+
+#[test]
+fn harmonic_centrality() {
+    use petgraph::algo::harmonic_centrality;
+
+    // a star with three leaves, a separate edge and an isolated node
+    let mut g = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (0, 2), (0, 3), (4, 5)]);
+    g.add_node(());
+    let centrality = harmonic_centrality(&g);
+    assert_eq!(centrality.len(), 7);
+    assert!(centrality.values().all(|c| c.is_finite()));
+    assert_eq!(centrality[&n(0)], 3.);
+    assert_eq!(centrality[&n(1)], 1. + 0.5 + 0.5);
+    assert_eq!(centrality[&n(4)], 1.);
+    assert_eq!(centrality[&n(6)], 0.);
+    // the hub is the most central node
+    assert!(g
+        .node_indices()
+        .all(|nx| centrality[&nx] <= centrality[&n(0)]));
+
+    // distances follow the edge directions
+    let g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3)]);
+    let centrality = harmonic_centrality(&g);
+    assert_eq!(centrality[&n(0)], 1. + 1. / 2. + 1. / 3.);
+    assert_eq!(centrality[&n(3)], 0.);
+}
+
+#[test]
+fn flood_fill() {
+    use petgraph::algo::flood_fill;
+
+    // a line of cells with a wall of height 9 between 2 and 3, and a way
+    // around it through 5 that is only blocked at its far end
+    let mut g = Graph::<u32, u32>::new();
+    let heights = [1, 2, 1, 3, 2, 4];
+    for &h in &heights {
+        g.add_node(h);
+    }
+    g.extend_with_edges(&[
+        (0, 1, 1),
+        (1, 2, 1),
+        (2, 3, 9),
+        (3, 4, 1),
+        (1, 5, 1),
+        (5, 4, 7),
+    ]);
+
+    let region = flood_fill(&g, n(0), |_, &cost| cost < 5);
+    let expected: HashSet<_> = vec![n(0), n(1), n(2), n(5)].into_iter().collect();
+    assert_eq!(region, expected);
+
+    // the target node is passed along to the gate
+    let region = flood_fill(&g, n(0), |&nx, _| g[nx] <= 2);
+    let expected: HashSet<_> = vec![n(0), n(1), n(2)].into_iter().collect();
+    assert_eq!(region, expected);
+
+    // a gate that lets everything through is plain reachability
+    let region = flood_fill(&g, n(1), |_, _| true);
+    assert_eq!(region.len(), 5);
+    assert!(!region.contains(&n(0)));
+    assert_eq!(flood_fill(&g, n(4), |_, _| true).len(), 1);
+}
+
+#[test]
+fn scc_membership() {
+    use petgraph::algo::scc_membership;
+
+    // the cycle 0 -> 1 -> 2 -> 0 leads into the cycle 3 -> 4 -> 3
+    let g = Graph::<(), ()>::from_edges(&[(3, 4), (4, 3), (2, 3), (0, 1), (1, 2), (2, 0)]);
+    let ids = scc_membership(&g);
+    assert_eq!(ids.len(), 5);
+    assert_eq!(ids[&n(0)], 0);
+    assert_eq!(ids[&n(1)], 0);
+    assert_eq!(ids[&n(2)], 0);
+    assert_eq!(ids[&n(3)], 1);
+    assert_eq!(ids[&n(4)], 1);
+
+    // every edge leads to an equal or larger id
+    let g = Graph::<(), ()>::from_edges(&[(5, 0), (0, 1), (1, 0), (1, 2), (3, 2), (4, 4), (2, 4)]);
+    let ids = scc_membership(&g);
+    for edge in g.raw_edges() {
+        assert!(ids[&edge.source()] <= ids[&edge.target()]);
+    }
+    let mut distinct: Vec<_> = ids.values().cloned().collect();
+    distinct.sort();
+    distinct.dedup();
+    assert_eq!(distinct, (0..5).collect::<Vec<_>>());
+}
+
+#[test]
+fn incremental_reachability() {
+    use petgraph::algo::{has_path_connecting, IncrementalReachability};
+    use rand::{ChaChaRng, Rng, SeedableRng};
+
+    let node_count = 12;
+    let mut rng = ChaChaRng::from_seed([9; 32]);
+    let mut reach = IncrementalReachability::new();
+    let mut g = Graph::<(), ()>::new();
+    for i in 0..node_count {
+        g.add_node(());
+        assert!(reach.add_node(n(i)));
+    }
+    assert!(!reach.add_node(n(0)));
+
+    let check = |g: &Graph<(), ()>, reach: &IncrementalReachability<NodeIndex>| {
+        for a in g.node_indices() {
+            for b in g.node_indices() {
+                assert_eq!(
+                    reach.can_reach(a, b),
+                    has_path_connecting(g, a, b, None),
+                    "{:?} -> {:?}",
+                    a,
+                    b
+                );
+            }
+        }
+    };
+    for step in 0..30 {
+        let a = n(rng.gen_range(0, node_count));
+        let b = n(rng.gen_range(0, node_count));
+        g.add_edge(a, b, ());
+        reach.add_edge(a, b);
+        check(&g, &reach);
+        if step % 10 == 9 {
+            let e = g
+                .edge_indices()
+                .nth(rng.gen_range(0, g.edge_count()))
+                .unwrap();
+            let (a, b) = g.edge_endpoints(e).unwrap();
+            g.remove_edge(e);
+            assert!(reach.remove_edge(a, b));
+            check(&g, &reach);
+        }
+    }
+    assert_eq!(reach.node_count(), node_count);
+    assert_eq!(reach.edge_count(), g.edge_count());
+    assert!(!reach.remove_edge(n(0), n(node_count)));
+    assert!(!reach.can_reach(n(0), n(node_count)));
+}
+
+#[test]
+fn feedback_arc_set() {
+    use petgraph::algo::feedback_arc_set;
+    use rand::{ChaChaRng, Rng, SeedableRng};
+
+    fn without<N: Clone, E: Clone>(
+        g: &Graph<N, E>,
+        edges: &[(NodeIndex, NodeIndex)],
+    ) -> Graph<N, E> {
+        let mut g = g.clone();
+        for &(a, b) in edges {
+            let e = g.find_edge(a, b).unwrap();
+            g.remove_edge(e);
+        }
+        g
+    }
+
+    // one cycle 1 -> 2 -> 3 -> 1, with a tail on each side
+    let g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 1), (3, 4)]);
+    let arcs = feedback_arc_set(&g);
+    assert_eq!(arcs.len(), 1);
+    assert!(!pg::algo::is_cyclic_directed(&without(&g, &arcs)));
+
+    // acyclic graphs need no removals
+    let g = Graph::<(), ()>::from_edges(&[(0, 1), (0, 2), (1, 3), (2, 3), (4, 0)]);
+    assert_eq!(feedback_arc_set(&g), vec![]);
+
+    // self loops are always included, parallel edges are listed once each
+    let g = Graph::<(), ()>::from_edges(&[(0, 0), (0, 1), (1, 0), (1, 0)]);
+    let arcs = feedback_arc_set(&g);
+    assert_eq!(arcs, vec![(n(0), n(0)), (n(0), n(1))]);
+    assert!(!pg::algo::is_cyclic_directed(&without(&g, &arcs)));
+
+    let mut rng = ChaChaRng::from_seed([3; 32]);
+    for _ in 0..20 {
+        let mut g = Graph::<(), ()>::new();
+        for _ in 0..15 {
+            g.add_node(());
+        }
+        for _ in 0..40 {
+            let a = n(rng.gen_range(0, 15));
+            let b = n(rng.gen_range(0, 15));
+            g.add_edge(a, b, ());
+        }
+        let arcs = feedback_arc_set(&g);
+        assert!(!pg::algo::is_cyclic_directed(&without(&g, &arcs)));
+    }
+}
+
+#[test]
+fn cuthill_mckee_order() {
+    use petgraph::algo::cuthill_mckee_order;
+
+    // the path 3 - 0 - 4 - 1 - 2, with the directions mixed up
+    let g = Graph::<(), ()>::from_edges(&[(0, 3), (4, 0), (4, 1), (2, 1)]);
+    let order = cuthill_mckee_order(&g);
+    let chain = vec![n(3), n(0), n(4), n(1), n(2)];
+    let reversed: Vec<_> = chain.iter().rev().cloned().collect();
+    assert!(order == chain || order == reversed, "{:?}", order);
+
+    // adjacent nodes end up close: a 2 x 4 ladder has bandwidth at most 2
+    let g = Graph::<(), (), Undirected>::from_edges(&[
+        (0, 1),
+        (1, 2),
+        (2, 3),
+        (4, 5),
+        (5, 6),
+        (6, 7),
+        (0, 4),
+        (1, 5),
+        (2, 6),
+        (3, 7),
+    ]);
+    let order = cuthill_mckee_order(&g);
+    assert_eq!(order.len(), 8);
+    let mut position = [0; 8];
+    for (i, nx) in order.iter().enumerate() {
+        position[nx.index()] = i as isize;
+    }
+    for edge in g.raw_edges() {
+        assert!((position[edge.source().index()] - position[edge.target().index()]).abs() <= 2);
+    }
+
+    // every component is ordered, isolated nodes included
+    let mut g = Graph::<(), ()>::from_edges(&[(0, 1), (2, 3), (3, 4)]);
+    g.add_node(());
+    let mut order = cuthill_mckee_order(&g);
+    order.sort();
+    assert_eq!(order, g.node_indices().collect::<Vec<_>>());
+}
+
+#[test]
+fn descendant_counts() {
+    use petgraph::algo::descendant_counts;
+
+    // a chain: the head reaches everyone, the tail no one
+    let g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 4)]);
+    let counts = descendant_counts(&g);
+    assert_eq!(counts.len(), 5);
+    for i in 0..5 {
+        assert_eq!(counts[&n(i)], 4 - i);
+    }
+
+    // a diamond: shared descendants are counted once
+    let g = Graph::<(), ()>::from_edges(&[(0, 1), (0, 2), (1, 3), (2, 3), (3, 4)]);
+    let counts = descendant_counts(&g);
+    assert_eq!(counts[&n(0)], 4);
+    assert_eq!(counts[&n(1)], 2);
+    assert_eq!(counts[&n(3)], 1);
+
+    // with a cycle, the nodes on it count themselves
+    let mut g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 1), (2, 3), (4, 4)]);
+    g.add_node(());
+    let counts = descendant_counts(&g);
+    assert_eq!(counts[&n(0)], 3);
+    assert_eq!(counts[&n(1)], 3);
+    assert_eq!(counts[&n(2)], 3);
+    assert_eq!(counts[&n(3)], 0);
+    assert_eq!(counts[&n(4)], 1);
+    assert_eq!(counts[&n(5)], 0);
+}
+
+#[test]
+fn rewire_edges() {
+    use rand::{ChaChaRng, Rng, SeedableRng};
+
+    fn degrees<Ty: EdgeType>(g: &Graph<(), u32, Ty>) -> Vec<(usize, usize)> {
+        g.node_indices()
+            .map(|a| {
+                (
+                    g.neighbors_directed(a, Outgoing).count(),
+                    g.neighbors_directed(a, Incoming).count(),
+                )
+            })
+            .collect()
+    }
+
+    let mut rng = ChaChaRng::from_seed([11; 32]);
+    let mut rng = || rng.gen::<f64>();
+    let mut g = Graph::<(), u32>::new();
+    for _ in 0..12 {
+        g.add_node(());
+    }
+    for i in 0..12 {
+        g.add_edge(n(i), n((i + 1) % 12), i as u32);
+        g.add_edge(n(i), n((i + 5) % 12), 100 + i as u32);
+    }
+    let before = degrees(&g);
+    let edges_before: HashSet<_> = g
+        .edge_references()
+        .map(|e| (e.source(), e.target()))
+        .collect();
+    let performed = g.rewire_edges(100, &mut rng);
+    assert!(performed > 0);
+    assert_eq!(degrees(&g), before);
+    assert_eq!(g.edge_count(), 24);
+    let edges_after: HashSet<_> = g
+        .edge_references()
+        .map(|e| (e.source(), e.target()))
+        .collect();
+    // no self loops and no parallel edges
+    assert_eq!(edges_after.len(), 24);
+    assert!(edges_after.iter().all(|&(a, b)| a != b));
+    assert_ne!(edges_after, edges_before);
+    // every edge is still reachable through the adjacency lists
+    for a in g.node_indices() {
+        for e in g.edges(a) {
+            assert_eq!(g.edge_endpoints(e.id()), Some((a, e.target())));
+        }
+    }
+    let mut weights: Vec<_> = g.raw_edges().iter().map(|e| e.weight).collect();
+    weights.sort();
+    assert_eq!(weights, (0..12).chain(100..112).collect::<Vec<_>>());
+
+    // undirected graphs keep their degrees too
+    let mut g = Graph::<(), u32, Undirected>::from_edges(&[
+        (0, 1, 0),
+        (2, 3, 1),
+        (4, 5, 2),
+        (6, 7, 3),
+        (0, 2, 4),
+        (4, 6, 5),
+    ]);
+    let before = degrees(&g);
+    g.rewire_edges(50, &mut rng);
+    assert_eq!(degrees(&g), before);
+
+    // too few edges to swap
+    let mut g = Graph::<(), u32>::from_edges(&[(0, 1, 0)]);
+    assert_eq!(g.rewire_edges(10, &mut rng), 0);
+}
+
+#[test]
+fn transitive_reduction_general() {
+    use petgraph::algo::{has_path_connecting, transitive_reduction_general};
+
+    // the cycle 0 -> 1 -> 2 -> 0 with a chord, the cycle 3 <-> 4, then 5 with
+    // a self loop; 0 -> 3, 4 -> 5 and the redundant 1 -> 5 and 2 -> 4
+    let g = Graph::<(), ()>::from_edges(&[
+        (0, 1),
+        (1, 2),
+        (2, 0),
+        (0, 2),
+        (3, 4),
+        (4, 3),
+        (5, 5),
+        (0, 3),
+        (4, 5),
+        (1, 5),
+        (2, 4),
+    ]);
+    let reduced = transitive_reduction_general(&g);
+    assert_eq!(reduced.node_count(), g.node_count());
+    for a in g.node_indices() {
+        for b in g.node_indices() {
+            // a path of at least one edge
+            let reaches = |g: &Graph<_, _>| {
+                g.neighbors(a)
+                    .any(|succ| has_path_connecting(g, succ, b, None))
+            };
+            assert_eq!(reaches(&g), reaches(&reduced), "{:?} -> {:?}", a, b);
+        }
+    }
+    // 3 cycle edges, 2 cycle edges, the self loop and 2 inter-component edges
+    assert_eq!(reduced.edge_count(), 8);
+    let edge = |a, b| reduced.contains_edge(n(a), n(b));
+    assert!(!edge(1, 5) && !edge(2, 4) && !edge(0, 5));
+    assert!(edge(5, 5));
+
+    // a DAG gets its plain transitive reduction
+    let g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (0, 2)]);
+    let reduced = transitive_reduction_general(&g);
+    assert_eq!(reduced.edge_count(), 2);
+    assert!(!reduced.contains_edge(n(0), n(2)));
+}
+
+#[test]
+fn simrank() {
+    use petgraph::algo::simrank;
+
+    // the complete bipartite graph between {0, 1} and {2, 3, 4}: the nodes
+    // on each side are structurally equivalent
+    let g =
+        Graph::<(), (), Undirected>::from_edges(&[(0, 2), (0, 3), (0, 4), (1, 2), (1, 3), (1, 4)]);
+    let similarity = simrank(&g, 0.8, 100);
+    assert_eq!(similarity.len(), 25);
+    for a in g.node_indices() {
+        assert_eq!(similarity[&(a, a)], 1.);
+        for b in g.node_indices() {
+            assert_eq!(similarity[&(a, b)], similarity[&(b, a)]);
+        }
+    }
+    let s01 = similarity[&(n(0), n(1))];
+    let s23 = similarity[&(n(2), n(3))];
+    assert!(s01 > 0.6, "{}", s01);
+    assert!(s23 > 0.6, "{}", s23);
+    assert!((similarity[&(n(2), n(4))] - s23).abs() < 1e-9);
+    assert!((similarity[&(n(3), n(4))] - s23).abs() < 1e-9);
+    // nodes on different sides never share a neighbor
+    assert_eq!(similarity[&(n(0), n(2))], 0.);
+
+    // no in-neighbors, no similarity
+    let g = Graph::<(), ()>::from_edges(&[(0, 2), (1, 3)]);
+    let similarity = simrank(&g, 0.8, 10);
+    assert_eq!(similarity[&(n(0), n(1))], 0.);
+    assert_eq!(similarity[&(n(2), n(3))], 0.);
+}
+
+#[test]
+fn gomory_hu_tree() {
+    use petgraph::algo::gomory_hu_tree;
+    use rand::{ChaChaRng, Rng, SeedableRng};
+
+    // the value of a minimum cut between a and b, by trying every cut
+    fn min_cut(g: &Graph<(), u32, Undirected>, a: usize, b: usize) -> u32 {
+        let n = g.node_count();
+        (0..1u32 << n)
+            .filter(|side| side & 1 << a != 0 && side & 1 << b == 0)
+            .map(|side| {
+                g.raw_edges()
+                    .iter()
+                    .filter(|e| {
+                        (side >> e.source().index() & 1) != (side >> e.target().index() & 1)
+                    })
+                    .map(|e| e.weight)
+                    .sum()
+            })
+            .min()
+            .unwrap()
+    }
+
+    for seed in 0..5 {
+        let mut rng = ChaChaRng::from_seed([seed; 32]);
+        let mut g = Graph::<(), u32, Undirected>::new_undirected();
+        for _ in 0..7 {
+            g.add_node(());
+        }
+        for _ in 0..12 {
+            let a = rng.gen_range(0, 7);
+            let b = rng.gen_range(0, 7);
+            g.add_edge(n(a), n(b), rng.gen_range(0, 10));
+        }
+        let tree = gomory_hu_tree(&g, |e| *e.weight());
+        assert_eq!(tree.node_count(), 7);
+        assert_eq!(tree.edge_count(), 6);
+        let tree = tree.into_edge_type::<Undirected>();
+        for a in 0..7 {
+            // the smallest weight on the tree path from a to every node
+            let mut bottleneck = [None; 7];
+            bottleneck[a] = Some(std::u32::MAX);
+            let mut stack = vec![n(a)];
+            while let Some(x) = stack.pop() {
+                for e in tree.edges(x) {
+                    let y = e.target();
+                    if bottleneck[y.index()].is_none() {
+                        bottleneck[y.index()] =
+                            Some(bottleneck[x.index()].unwrap().min(*e.weight()));
+                        stack.push(y);
+                    }
+                }
+            }
+            for b in 0..7 {
+                if a != b {
+                    assert_eq!(tree[n(b)], n(b));
+                    assert_eq!(bottleneck[b], Some(min_cut(&g, a, b)), "{} - {}", a, b);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn maximal_independent_set() {
+    use petgraph::algo::maximal_independent_set;
+    use rand::{ChaChaRng, Rng, SeedableRng};
+
+    // a 3 x 3 grid with a diagonal, an isolated node and one with a self loop
+    let mut g = Graph::<(), ()>::from_edges(&[
+        (0, 1),
+        (1, 2),
+        (3, 4),
+        (4, 5),
+        (6, 7),
+        (7, 8),
+        (0, 3),
+        (3, 6),
+        (1, 4),
+        (4, 7),
+        (2, 5),
+        (5, 8),
+        (0, 4),
+        (10, 10),
+        (10, 8),
+    ]);
+    g.add_node(());
+    for seed in 0..10 {
+        let mut rng = ChaChaRng::from_seed([seed; 32]);
+        let mut rng = || rng.gen::<f64>();
+        let set = maximal_independent_set(&g, &mut rng);
+        // independent
+        for edge in g.raw_edges() {
+            assert!(!(set.contains(&edge.source()) && set.contains(&edge.target())));
+        }
+        // maximal
+        for a in g.node_indices() {
+            if !set.contains(&a) {
+                assert!(
+                    g.contains_edge(a, a) || g.neighbors_undirected(a).any(|b| set.contains(&b)),
+                    "{:?} could be added to {:?}",
+                    a,
+                    set
+                );
+            }
+        }
+        assert!(set.contains(&n(9)));
+        assert!(!set.contains(&n(10)));
+    }
+}
+
+#[test]
+fn shortest_path_closure() {
+    use petgraph::algo::shortest_path_closure;
+
+    // a chain 0 -> 1 -> 2 -> 3, with a longer shortcut 0 -> 2
+    let g = Graph::<(), u32>::from_edges(&[(0, 1, 2), (1, 2, 3), (2, 3, 4), (0, 2, 9)]);
+    let closure = shortest_path_closure(&g, |e| *e.weight());
+    assert_eq!(closure.node_count(), 4);
+    let distance = |a, b| closure.find_edge(n(a), n(b)).map(|e| closure[e]);
+    assert_eq!(distance(0, 1), Some(2));
+    assert_eq!(distance(0, 2), Some(5));
+    assert_eq!(distance(0, 3), Some(9));
+    assert_eq!(distance(1, 3), Some(7));
+    assert_eq!(distance(2, 3), Some(4));
+    // nothing backwards, and no self loops
+    assert_eq!(distance(1, 0), None);
+    assert_eq!(distance(0, 0), None);
+    assert_eq!(closure.edge_count(), 6);
+    for nx in closure.node_indices() {
+        assert_eq!(closure[nx], nx);
+    }
+
+    // nodes on a cycle reach each other, but not themselves
+    let g = Graph::<(), u32>::from_edges(&[(0, 1, 1), (1, 0, 1)]);
+    let closure = shortest_path_closure(&g, |e| *e.weight());
+    assert_eq!(closure.edge_count(), 2);
+}
+
+#[test]
+fn bisect() {
+    use petgraph::algo::bisect;
+
+    // two complete graphs on the even and the odd nodes, joined by one edge,
+    // so that the initial split cuts both of them in half
+    let mut g = Graph::<(), (), Undirected>::new_undirected();
+    for _ in 0..10 {
+        g.add_node(());
+    }
+    for a in 0..10 {
+        for b in (a + 2..10).step_by(2) {
+            g.add_edge(n(a), n(b), ());
+        }
+    }
+    g.add_edge(n(8), n(9), ());
+    let (a, b) = bisect(&g);
+    assert_eq!(a.len(), 5);
+    assert_eq!(b.len(), 5);
+    let evens: HashSet<_> = (0..10).step_by(2).map(n).collect();
+    let odds: HashSet<_> = (1..10).step_by(2).map(n).collect();
+    assert!(
+        (a == evens && b == odds) || (a == odds && b == evens),
+        "{:?}",
+        a
+    );
+
+    // every node ends up in one half, also for an odd number of nodes
+    let g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 4)]);
+    let (a, b) = bisect(&g);
+    assert_eq!((a.len(), b.len()), (3, 2));
+    assert!(a.is_disjoint(&b));
+    let crossing = g
+        .raw_edges()
+        .iter()
+        .filter(|e| a.contains(&e.source()) != a.contains(&e.target()))
+        .count();
+    assert_eq!(crossing, 1);
+}
+
+#[test]
+fn approximate_diameter() {
+    use petgraph::algo::approximate_diameter;
+    use rand::{ChaChaRng, Rng, SeedableRng};
+
+    let mut rng = ChaChaRng::from_seed([13; 32]);
+    let mut rng = || rng.gen::<f64>();
+    // a cycle of 8 with a tail of 3: the diameter is from the tail end to
+    // the opposite side of the cycle
+    let mut edges: Vec<_> = (0..8).map(|i| (i, (i + 1) % 8)).collect();
+    edges.extend(&[(0, 8), (8, 9), (9, 10)]);
+    let g = Graph::<(), (), Undirected>::from_edges(&edges);
+    assert_eq!(approximate_diameter(&g, 11, &mut rng), 7);
+    assert_eq!(approximate_diameter(&g, 100, &mut rng), 7);
+    for samples in 1..11 {
+        let estimate = approximate_diameter(&g, samples, &mut rng);
+        assert!((4..=7).contains(&estimate), "{}", estimate);
+    }
+    assert_eq!(approximate_diameter(&g, 0, &mut rng), 0);
+    assert_eq!(
+        approximate_diameter(&Graph::<(), ()>::new(), 5, &mut rng),
+        0
+    );
+}
+
+#[test]
+fn degree_signature() {
+    use petgraph::algo::{degree_signature, group_by_signature};
+
+    // a star pointing out of 0, with one leaf pointing back
+    let g = Graph::<(), ()>::from_edges(&[(0, 1), (0, 2), (0, 3), (0, 4), (4, 0)]);
+    assert_eq!(degree_signature(&g, n(0)), (1, 4));
+    assert_eq!(degree_signature(&g, n(1)), (1, 0));
+    assert_eq!(degree_signature(&g, n(4)), (1, 1));
+    let groups = group_by_signature(&g);
+    assert_eq!(groups.len(), 3);
+    assert_eq!(groups[&(1, 4)], vec![n(0)]);
+    assert_eq!(groups[&(1, 0)], vec![n(1), n(2), n(3)]);
+    assert_eq!(groups[&(1, 1)], vec![n(4)]);
+
+    // undirected: the center and the leaves differ by degree only
+    let g = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (0, 2), (0, 3)]);
+    let groups = group_by_signature(&g);
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[&(3, 3)], vec![n(0)]);
+    assert_eq!(groups[&(1, 1)], vec![n(1), n(2), n(3)]);
+}
+
+#[test]
+fn metric_closure() {
+    use petgraph::algo::metric_closure;
+
+    //     1       4
+    // 0 ----- 1 ----- 2
+    //  \      |       |
+    //   \ 3   | 1     | 1
+    //    \    |       |
+    //     `-- 3 ----- 4
+    //             5
+    let g = Graph::<(), u32, Undirected>::from_edges(&[
+        (0, 1, 1),
+        (1, 2, 4),
+        (0, 3, 3),
+        (1, 3, 1),
+        (2, 4, 1),
+        (3, 4, 5),
+    ]);
+    let terminals = [n(0), n(2), n(3)];
+    let closure = metric_closure(&g, &terminals, |e| *e.weight());
+    assert_eq!(closure.node_count(), 3);
+    assert_eq!(closure.edge_count(), 6);
+    for (i, &t) in terminals.iter().enumerate() {
+        assert_eq!(closure[n(i)], t);
+    }
+    let distance = |a, b| closure[closure.find_edge(n(a), n(b)).unwrap()];
+    // 0 - 1 - 2
+    assert_eq!(distance(0, 1), 5);
+    assert_eq!(distance(1, 0), 5);
+    // 0 - 1 - 3, shorter than the direct edge
+    assert_eq!(distance(0, 2), 2);
+    // 3 - 1 - 2, as long as 3 - 4 - 2
+    assert_eq!(distance(2, 1), 5);
+
+    // unreachable pairs are omitted
+    let mut g = Graph::<(), u32>::from_edges(&[(0, 1, 1)]);
+    let c = g.add_node(());
+    let closure = metric_closure(&g, &[n(0), n(1), c], |e| *e.weight());
+    assert_eq!(closure.edge_count(), 1);
+    assert!(closure.contains_edge(n(0), n(1)));
+}
+
+#[test]
+fn steiner_tree() {
+    use petgraph::algo::steiner_tree;
+    use petgraph::unionfind::UnionFind;
+
+    fn check(g: &Graph<(), u32, Undirected>, terminals: &[NodeIndex], bound: u32) {
+        let tree = steiner_tree(g, terminals, |e| *e.weight());
+        // a tree of edges of `g`
+        assert_eq!(tree.edge_count() + 1, tree.node_count());
+        let mut sets = UnionFind::new(tree.node_count());
+        for edge in tree.raw_edges() {
+            assert!(sets.union(edge.source().index(), edge.target().index()));
+            let (a, b) = (tree[edge.source()], tree[edge.target()]);
+            assert!(g.edges_connecting(a, b).any(|e| *e.weight() == edge.weight));
+        }
+        // that connects the terminals, listed first
+        for (i, &t) in terminals.iter().enumerate() {
+            assert_eq!(tree[n(i)], t);
+            assert!(sets.equiv(0, i));
+        }
+        // whose leaves are terminals
+        for nx in tree.node_indices() {
+            if tree.neighbors_undirected(nx).count() <= 1 {
+                assert!(terminals.contains(&tree[nx]));
+            }
+        }
+        let cost: u32 = tree.raw_edges().iter().map(|e| e.weight).sum();
+        assert!(cost <= bound, "{} > {}", cost, bound);
+    }
+
+    // the terminals 0, 1 and 2 pairwise cost 5, or 3 each to the hub 3; the
+    // optimum of 9 goes through the hub
+    let g = Graph::<(), u32, Undirected>::from_edges(&[
+        (0, 1, 5),
+        (1, 2, 5),
+        (2, 0, 5),
+        (0, 3, 3),
+        (1, 3, 3),
+        (2, 3, 3),
+    ]);
+    check(&g, &[n(0), n(1), n(2)], 2 * 9);
+
+    // a path through non-terminals, with a dead end 4 that must be pruned
+    let g = Graph::<(), u32, Undirected>::from_edges(&[
+        (0, 1, 1),
+        (1, 2, 1),
+        (2, 3, 1),
+        (1, 4, 1),
+        (0, 3, 10),
+    ]);
+    let tree = steiner_tree(&g, &[n(0), n(3)], |e| *e.weight());
+    assert_eq!(tree.node_count(), 4);
+    assert_eq!(tree.edge_count(), 3);
+    check(&g, &[n(0), n(3)], 3);
+
+    // a single terminal is a tree on its own
+    let tree = steiner_tree(&g, &[n(2)], |e| *e.weight());
+    assert_eq!(tree.node_count(), 1);
+    assert_eq!(tree.edge_count(), 0);
+}
+
+#[test]
+fn critical_chain() {
+    use petgraph::algo::critical_chain;
+
+    // 0 -> 1 -> (2 -> 3 -> 4 -> 2) -> 5 -> 6, with a bypass 1 -> 7 -> 5 of
+    // longer edges that is still shorter than going through the cycle
+    let g = Graph::<(), u32>::from_edges(&[
+        (0, 1, 1),
+        (1, 2, 1),
+        (2, 3, 2),
+        (3, 4, 7),
+        (4, 2, 3),
+        (4, 5, 1),
+        (5, 6, 2),
+        (1, 7, 4),
+        (7, 5, 4),
+    ]);
+    let (length, chain) = critical_chain(&g, |e| *e.weight());
+    // 1 + 1 + 7 (the cycle) + 1 + 2
+    assert_eq!(length, 12);
+    let chain: Vec<Vec<_>> = chain
+        .into_iter()
+        .map(|mut nodes| {
+            nodes.sort();
+            nodes
+        })
+        .collect();
+    assert_eq!(
+        chain,
+        vec![
+            vec![n(0)],
+            vec![n(1)],
+            vec![n(2), n(3), n(4)],
+            vec![n(5)],
+            vec![n(6)],
+        ]
+    );
+
+    // without the cycle, the bypass is the longer way
+    let g = Graph::<(), u32>::from_edges(&[(0, 1, 1), (1, 2, 1), (2, 5, 1), (1, 7, 4), (7, 5, 4)]);
+    let (length, mut chain) = critical_chain(&g, |e| *e.weight());
+    assert_eq!(length, 9);
+    chain.pop();
+    assert_eq!(chain.pop(), Some(vec![n(7)]));
+
+    let (length, chain) = critical_chain(&Graph::<(), u32>::new(), |e| *e.weight());
+    assert_eq!((length, chain.len()), (0, 0));
+}
+
+#[test]
+fn modularity() {
+    use petgraph::algo::modularity;
+    use rand::{ChaChaRng, Rng, SeedableRng};
+    use std::collections::HashMap;
+
+    // two complete graphs on 5 nodes, joined by one light edge
+    let mut g = Graph::<(), f64, Undirected>::new_undirected();
+    for _ in 0..10 {
+        g.add_node(());
+    }
+    for c in 0..2 {
+        for a in 0..5 {
+            for b in a + 1..5 {
+                g.add_edge(n(5 * c + a), n(5 * c + b), 2.);
+            }
+        }
+    }
+    g.add_edge(n(4), n(5), 0.5);
+
+    let clusters: HashMap<_, _> = g.node_indices().map(|a| (a, a.index() / 5)).collect();
+    let q = modularity(&g, &clusters, |e| *e.weight());
+    assert!(q > 0.45 && q < 0.5, "{}", q);
+
+    let mut rng = ChaChaRng::from_seed([17; 32]);
+    for _ in 0..10 {
+        let random: HashMap<_, _> = g.node_indices().map(|a| (a, rng.gen_range(0, 2))).collect();
+        assert!(modularity(&g, &random, |e| *e.weight()) < q);
+    }
+
+    // a single community has modularity 0
+    let one: HashMap<_, _> = g.node_indices().map(|a| (a, 0)).collect();
+    assert!(modularity(&g, &one, |e| *e.weight()).abs() < 1e-9);
+    // no weight, no modularity
+    assert_eq!(modularity(&g, &clusters, |_| 0.), 0.);
+}
+
+#[test]
+fn louvain_communities() {
+    use petgraph::algo::{louvain_communities, modularity};
+    use rand::{ChaChaRng, Rng, SeedableRng};
+
+    // two complete graphs on 6 nodes, joined by a single edge
+    let mut g = Graph::<(), (), Undirected>::new_undirected();
+    for _ in 0..12 {
+        g.add_node(());
+    }
+    for c in 0..2 {
+        for a in 0..6 {
+            for b in a + 1..6 {
+                g.add_edge(n(6 * c + a), n(6 * c + b), ());
+            }
+        }
+    }
+    g.add_edge(n(5), n(6), ());
+
+    for seed in 0..5 {
+        let mut rng = ChaChaRng::from_seed([seed; 32]);
+        let mut rng = || rng.gen::<f64>();
+        let community = louvain_communities(&g, |_| 1., &mut rng);
+        assert_eq!(community.len(), 12);
+        for a in 0..12 {
+            assert_eq!(community[&n(a)], a / 6, "seed {}: {:?}", seed, community);
+        }
+    }
+
+    // a ring of 6 triangles, each joined to the next by one edge
+    let mut g = Graph::<(), f64, Undirected>::new_undirected();
+    for _ in 0..18 {
+        g.add_node(());
+    }
+    for t in 0..6 {
+        let (a, b, c) = (3 * t, 3 * t + 1, 3 * t + 2);
+        for &(x, y) in &[(a, b), (b, c), (c, a), (c, (c + 1) % 18)] {
+            g.add_edge(n(x), n(y), 1.);
+        }
+    }
+    let mut rng = ChaChaRng::from_seed([3; 32]);
+    let mut rng = || rng.gen::<f64>();
+    let community = louvain_communities(&g, |e| *e.weight(), &mut rng);
+    let triangles: std::collections::HashMap<_, _> =
+        g.node_indices().map(|a| (a, a.index() / 3)).collect();
+    let q = modularity(&g, &community, |e| *e.weight());
+    assert!(
+        q >= modularity(&g, &triangles, |e| *e.weight()) - 1e-9,
+        "{}",
+        q
+    );
+    // nodes of a triangle stay together
+    for t in 0..6 {
+        assert_eq!(community[&n(3 * t)], community[&n(3 * t + 1)]);
+        assert_eq!(community[&n(3 * t)], community[&n(3 * t + 2)]);
+    }
+
+    // no edges: every node is a community of its own
+    let mut g = Graph::<(), ()>::new();
+    g.add_node(());
+    g.add_node(());
+    let community = louvain_communities(&g, |_| 1., &mut rng);
+    assert_eq!(community[&n(0)], 0);
+    assert_eq!(community[&n(1)], 1);
+}
+
+#[test]
+fn graph_power() {
+    use petgraph::algo::graph_power;
+
+    let edges = |g: &Graph<NodeIndex, ()>| {
+        g.raw_edges()
+            .iter()
+            .map(|e| (e.source().index(), e.target().index()))
+            .collect::<Vec<_>>()
+    };
+
+    // the path 0 -> 1 -> 2 -> 3 -> 4
+    let g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 4)]);
+    assert_eq!(
+        edges(&graph_power(&g, 1)),
+        vec![(0, 1), (1, 2), (2, 3), (3, 4)]
+    );
+    assert_eq!(
+        edges(&graph_power(&g, 2)),
+        vec![(0, 1), (0, 2), (1, 2), (1, 3), (2, 3), (2, 4), (3, 4)]
+    );
+    // high powers are the transitive closure
+    assert_eq!(graph_power(&g, 4).edge_count(), 10);
+    assert_eq!(graph_power(&g, 10).edge_count(), 10);
+    assert_eq!(graph_power(&g, 0).edge_count(), 0);
+    let square = graph_power(&g, 2);
+    for nx in square.node_indices() {
+        assert_eq!(square[nx], nx);
+    }
+
+    // undirected: both directions, and walks back to the start make loops
+    let g = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (1, 2)]);
+    assert_eq!(
+        edges(&graph_power(&g, 1)),
+        vec![(0, 1), (1, 0), (1, 2), (2, 1)]
+    );
+    assert_eq!(
+        edges(&graph_power(&g, 2)),
+        vec![
+            (0, 0),
+            (0, 1),
+            (0, 2),
+            (1, 0),
+            (1, 1),
+            (1, 2),
+            (2, 0),
+            (2, 1),
+            (2, 2)
+        ]
+    );
+
+    // a directed cycle of 3 gets self loops from its third power on
+    let g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 0)]);
+    assert!(!graph_power(&g, 2).contains_edge(n(0), n(0)));
+    assert!(graph_power(&g, 3).contains_edge(n(0), n(0)));
+}
+
+#[test]
+fn remove_self_loops() {
+    let mut g = Graph::<&str, u32>::new();
+    let a = g.add_node("a");
+    let b = g.add_node("b");
+    g.add_edge(a, a, 1);
+    g.add_edge(a, b, 2);
+    g.add_edge(b, b, 3);
+    assert_eq!(g.remove_self_loops(), vec![(a, 1), (b, 3)]);
+    assert_eq!(g.node_count(), 2);
+    assert_eq!(g.edge_count(), 1);
+    let e = g.find_edge(a, b).unwrap();
+    assert_eq!(g[e], 2);
+    assert_eq!(g.neighbors(a).collect::<Vec<_>>(), vec![b]);
+    assert_eq!(g.neighbors_directed(a, Incoming).count(), 0);
+    assert_eq!(g.neighbors(b).count(), 0);
+    assert_eq!(g.remove_self_loops(), vec![]);
+
+    // undirected, with a loop last
+    let mut g = Graph::<(), u32, Undirected>::from_edges(&[(0, 1, 1), (1, 2, 2), (2, 2, 3)]);
+    assert_eq!(g.remove_self_loops(), vec![(n(2), 3)]);
+    assert_eq!(g.neighbors(n(2)).collect::<Vec<_>>(), vec![n(1)]);
+}
+
+#[test]
+fn weighted_betweenness_centrality() {
+    use petgraph::algo::weighted_betweenness_centrality;
+
+    // the unweighted betweenness, by counting shortest paths between all
+    // pairs with breadth-first searches
+    fn unweighted<Ty: EdgeType>(g: &Graph<(), u32, Ty>) -> Vec<f64> {
+        let node_count = g.node_count();
+        let mut dist = vec![vec![None; node_count]; node_count];
+        let mut count = vec![vec![0.; node_count]; node_count];
+        for s in 0..node_count {
+            dist[s][s] = Some(0);
+            count[s][s] = 1.;
+            let mut queue = std::collections::VecDeque::new();
+            queue.push_back(s);
+            while let Some(v) = queue.pop_front() {
+                let d = dist[s][v].unwrap();
+                for w in g.neighbors(n(v)) {
+                    let w = w.index();
+                    if dist[s][w].is_none() {
+                        dist[s][w] = Some(d + 1);
+                        queue.push_back(w);
+                    }
+                    if dist[s][w] == Some(d + 1) {
+                        count[s][w] += count[s][v];
+                    }
+                }
+            }
+        }
+        let mut betweenness = vec![0.; node_count];
+        for s in 0..node_count {
+            for t in 0..node_count {
+                for v in 0..node_count {
+                    if s == t || v == s || v == t {
+                        continue;
+                    }
+                    if let (Some(a), Some(b), Some(c)) = (dist[s][v], dist[v][t], dist[s][t]) {
+                        if a + b == c {
+                            betweenness[v] += count[s][v] * count[v][t] / count[s][t];
+                        }
+                    }
+                }
+            }
+        }
+        if !g.is_directed() {
+            for value in &mut betweenness {
+                *value /= 2.;
+            }
+        }
+        betweenness
+    }
+
+    // unit weights, with several equally short paths
+    let edges = [
+        (0, 1, 1),
+        (0, 2, 1),
+        (1, 3, 1),
+        (2, 3, 1),
+        (3, 4, 1),
+        (4, 5, 1),
+        (4, 6, 1),
+        (6, 5, 1),
+        (5, 0, 1),
+    ];
+    let directed = Graph::<(), u32>::from_edges(&edges);
+    let undirected = Graph::<(), u32, Undirected>::from_edges(&edges);
+    let centrality = weighted_betweenness_centrality(&directed, |e| *e.weight());
+    for (i, expected) in unweighted(&directed).into_iter().enumerate() {
+        assert!((centrality[&n(i)] - expected).abs() < 1e-9, "{}", i);
+    }
+    let centrality = weighted_betweenness_centrality(&undirected, |e| *e.weight());
+    for (i, expected) in unweighted(&undirected).into_iter().enumerate() {
+        assert!((centrality[&n(i)] - expected).abs() < 1e-9, "{}", i);
+    }
+
+    // weighted: the path 0 - 1 - 2 - 3 costs 3, the direct 0 - 3 costs 4,
+    // and 0 - 4 - 3 costs 3 too
+    let g = Graph::<(), u32, Undirected>::from_edges(&[
+        (0, 1, 1),
+        (1, 2, 1),
+        (2, 3, 1),
+        (0, 3, 4),
+        (0, 4, 1),
+        (4, 3, 2),
+    ]);
+    let centrality = weighted_betweenness_centrality(&g, |e| *e.weight());
+    // 0 - 3 and 2 - 4 (by 2 - 1 - 0 - 4 or 2 - 3 - 4) have two shortest
+    // paths each, the other pairs one
+    assert_eq!(centrality[&n(0)], 1.5); // 1 - 4, half of 2 - 4
+    assert_eq!(centrality[&n(1)], 2.); // 0 - 2, halves of 0 - 3 and 2 - 4
+    assert_eq!(centrality[&n(2)], 1.5); // 1 - 3, half of 0 - 3
+    assert_eq!(centrality[&n(3)], 0.5); // half of 2 - 4
+    assert_eq!(centrality[&n(4)], 0.5);
+}
+
+#[test]
+fn ordered_layout() {
+    use petgraph::algo::ordered_layout;
+
+    let span = |g: &Graph<(), ()>, layers: &std::collections::HashMap<NodeIndex, usize>| {
+        g.raw_edges()
+            .iter()
+            .map(|e| {
+                let (a, b) = (layers[&e.source()], layers[&e.target()]);
+                assert!(a < b);
+                b - a
+            })
+            .sum::<usize>()
+    };
+
+    // a diamond 0 -> {1, 2} -> 3 with a longer left side 1 -> 4 -> 3, and a
+    // second source 5 feeding the sink 3
+    let g = Graph::<(), ()>::from_edges(&[(0, 1), (0, 2), (1, 4), (4, 3), (2, 3), (5, 3)]);
+    let layers = ordered_layout(&g).unwrap();
+    assert_eq!(layers[&n(0)], 0);
+    assert_eq!(layers[&n(1)], 1);
+    assert_eq!(layers[&n(4)], 2);
+    assert_eq!(layers[&n(3)], 3);
+    // 2 has as many incoming as outgoing edges, so either layer is fine
+    assert!(layers[&n(2)] == 1 || layers[&n(2)] == 2);
+    // 5 is pulled up from layer 0
+    assert_eq!(layers[&n(5)], 2);
+    // 1 + 1 + 1 + 1 + 2 + 1, the smallest possible
+    assert_eq!(span(&g, &layers), 7);
+
+    // all sources pulled up: layers still start at 0
+    let g = Graph::<(), ()>::from_edges(&[(0, 2), (0, 3), (1, 2), (1, 3)]);
+    let layers = ordered_layout(&g).unwrap();
+    assert_eq!(layers.values().min(), Some(&0));
+    assert_eq!(span(&g, &layers), 4);
+
+    let g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 1)]);
+    let err = ordered_layout(&g).unwrap_err();
+    assert!(err == n(1) || err == n(2));
+    let g = Graph::<(), ()>::from_edges(&[(0, 0)]);
+    assert_eq!(ordered_layout(&g), Err(n(0)));
+}
+
+#[test]
+fn weak_component_sizes() {
+    use petgraph::algo::weak_component_sizes;
+
+    // two disjoint triangles, one of them directed, and an isolated node
+    let mut g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 0), (3, 4), (5, 4), (3, 5)]);
+    g.add_node(());
+    assert_eq!(weak_component_sizes(&g), vec![3, 3, 1]);
+
+    g.add_edge(n(2), n(6), ());
+    assert_eq!(weak_component_sizes(&g), vec![4, 3]);
+
+    assert_eq!(weak_component_sizes(&Graph::<(), ()>::new()), vec![]);
+}
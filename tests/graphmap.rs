@@ -335,3 +335,1041 @@ fn self_loops_can_be_removed() {
     assert_eq!(graph.neighbors_directed((), Outgoing).next(), None);
     assert_eq!(graph.neighbors_directed((), Incoming).next(), None);
 }
+
+#[test]
+fn neighbors_owned() {
+    let mut graph = DiGraphMap::new();
+    graph.add_edge("a", "b", 1);
+    graph.add_edge("a", "c", 2);
+    graph.add_edge("c", "a", 3);
+
+    let owned = graph.neighbors_owned("a");
+    let borrowed: Vec<_> = graph.edges("a").map(|(_, b, &w)| (b, w)).collect();
+    assert_eq!(owned, borrowed);
+    assert_eq!(owned, vec![("b", 1), ("c", 2)]);
+
+    // the snapshot is not affected by later mutations
+    graph.remove_edge("a", "b");
+    graph[("a", "c")] = 10;
+    assert_eq!(owned, vec![("b", 1), ("c", 2)]);
+    assert_eq!(graph.neighbors_owned("a"), vec![("c", 10)]);
+    assert_eq!(graph.neighbors_owned("z"), vec![]);
+}
+
+#[test]
+fn neighbors_except() {
+    let mut graph = UnGraphMap::new();
+    graph.add_edge("root", "a", ());
+    graph.add_edge("a", "b", ());
+    graph.add_edge("a", "c", ());
+    graph.add_edge("a", "a", ());
+
+    let children: Vec<_> = graph.neighbors_except("a", "root").collect();
+    assert!(!children.contains(&"root"));
+    assert_eq!(children, vec!["b", "c", "a"]);
+    // excluding a node that is no neighbor changes nothing
+    assert_eq!(
+        graph.neighbors_except("a", "z").collect::<Vec<_>>(),
+        graph.neighbors("a").collect::<Vec<_>>()
+    );
+    // excluding the node itself leaves out its self loop
+    assert_eq!(
+        graph.neighbors_except("a", "a").collect::<Vec<_>>(),
+        vec!["root", "b", "c"]
+    );
+    assert_eq!(graph.neighbors_except("z", "a").next(), None);
+}
+
+#[test]
+fn edge_neighbors() {
+    let graph = DiGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2), (1, 3), (1, 0), (2, 1), (3, 3)]);
+    let adjacent: Vec<_> = graph.edge_neighbors(0, 1).collect();
+    let out_edges: Vec<_> = graph.edges(1).map(|(a, b, _)| (a, b)).collect();
+    assert_eq!(adjacent, out_edges);
+    assert_eq!(adjacent, vec![(1, 2), (1, 3), (1, 0)]);
+    assert_eq!(graph.edge_neighbors(2, 1).collect::<Vec<_>>(), out_edges);
+    assert_eq!(graph.edge_neighbors(3, 3).collect::<Vec<_>>(), vec![(3, 3)]);
+    // no such edge
+    assert_eq!(graph.edge_neighbors(0, 2).next(), None);
+
+    // an undirected edge is not adjacent to itself
+    let graph = UnGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2), (1, 3), (3, 3)]);
+    assert_eq!(
+        graph.edge_neighbors(0, 1).collect::<Vec<_>>(),
+        vec![(1, 2), (1, 3)]
+    );
+    assert_eq!(graph.edge_neighbors(1, 0).collect::<Vec<_>>(), vec![]);
+    assert_eq!(graph.edge_neighbors(1, 3).collect::<Vec<_>>(), vec![(3, 3)]);
+    assert_eq!(graph.edge_neighbors(3, 3).collect::<Vec<_>>(), vec![(3, 1)]);
+}
+
+#[test]
+fn retain_node_edges() {
+    let mut graph = DiGraphMap::new();
+    graph.add_edge(1, 2, 1.);
+    graph.add_edge(1, 3, 4.);
+    graph.add_edge(1, 4, 6.);
+    graph.add_edge(2, 3, 8.);
+    graph.add_edge(3, 1, 9.);
+
+    assert_eq!(graph.retain_node_edges(1, |_, &w| w < 5.), 1);
+    assert_eq!(graph.edge_count(), 4);
+    assert!(graph.contains_edge(1, 2));
+    assert!(graph.contains_edge(1, 3));
+    assert!(!graph.contains_edge(1, 4));
+    // other nodes are untouched
+    assert!(graph.contains_edge(2, 3));
+    assert!(graph.contains_edge(3, 1));
+    assert_eq!(graph.neighbors_directed(4, Incoming).count(), 0);
+
+    assert_eq!(graph.retain_node_edges(1, |&n, _| n != 3), 1);
+    assert_eq!(graph.neighbors(1).collect::<Vec<_>>(), vec![2]);
+    assert_eq!(graph.retain_node_edges(7, |_, _| false), 0);
+}
+
+#[test]
+fn connect_disconnect() {
+    let mut graph = DiGraphMap::new();
+    assert!(graph.connect(1, 2));
+    assert!(graph.connect(2, 3));
+    assert!(graph.connect(3, 1));
+    assert!(!graph.connect(1, 2));
+    assert_eq!(graph.node_count(), 3);
+    assert_eq!(graph.edge_count(), 3);
+    assert_eq!(graph.edge_weight(1, 2), Some(&()));
+
+    assert!(graph.disconnect(2, 3));
+    assert!(!graph.disconnect(2, 3));
+    assert!(!graph.disconnect(2, 1));
+    assert_eq!(graph.edge_count(), 2);
+    assert!(!graph.contains_edge(2, 3));
+    assert!(graph.contains_node(2));
+}
+
+#[test]
+fn stable_removal_keeps_order() {
+    let edges = [(0, 1), (0, 2), (0, 3), (0, 4), (2, 4), (3, 4)];
+    let mut graph = DiGraphMap::<_, ()>::from_edges(&edges);
+    assert!(!graph.stable_removal());
+    graph.set_stable_removal(true);
+    assert!(graph.stable_removal());
+
+    graph.remove_edge(0, 1);
+    assert_eq!(graph.neighbors(0).collect::<Vec<_>>(), vec![2, 3, 4]);
+    assert_eq!(
+        graph
+            .all_edges()
+            .map(|(a, b, _)| (a, b))
+            .collect::<Vec<_>>(),
+        vec![(0, 2), (0, 3), (0, 4), (2, 4), (3, 4)]
+    );
+    assert_eq!(
+        graph.neighbors_directed(4, Incoming).collect::<Vec<_>>(),
+        vec![0, 2, 3]
+    );
+
+    graph.remove_node(0);
+    assert_eq!(graph.nodes().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+    assert_eq!(
+        graph
+            .all_edges()
+            .map(|(a, b, _)| (a, b))
+            .collect::<Vec<_>>(),
+        vec![(2, 4), (3, 4)]
+    );
+    assert_eq!(
+        graph.neighbors_directed(4, Incoming).collect::<Vec<_>>(),
+        vec![2, 3]
+    );
+
+    // the default removal moves the last neighbor into the hole
+    let mut graph = DiGraphMap::<_, ()>::from_edges(&edges);
+    graph.remove_edge(0, 1);
+    assert_eq!(graph.neighbors(0).collect::<Vec<_>>(), vec![4, 2, 3]);
+}
+
+#[test]
+fn shares_neighbor() {
+    let mut graph = DiGraphMap::<_, ()>::new();
+    graph.add_edge("a", "x", ());
+    graph.add_edge("b", "x", ());
+    graph.add_edge("c", "a", ());
+    graph.add_edge("x", "c", ());
+
+    assert!(graph.shares_neighbor("a", "b"));
+    assert!(graph.shares_neighbor("b", "a"));
+    assert!(!graph.shares_neighbor("a", "c"));
+    assert!(!graph.shares_neighbor("c", "x"));
+    assert!(!graph.shares_neighbor("a", "z"));
+
+    // in an undirected graph, both edge directions count
+    let graph = UnGraphMap::<_, ()>::from_edges(&[(1, 2), (3, 2), (4, 4)]);
+    assert!(graph.shares_neighbor(1, 3));
+    assert!(!graph.shares_neighbor(1, 2));
+    assert!(graph.shares_neighbor(4, 4));
+}
+
+#[test]
+fn boundary_edges() {
+    // two triangles joined by 2 -> 3 and 4 -> 0
+    let mut graph = DiGraphMap::new();
+    for &(a, b, w) in &[
+        (0, 1, 'a'),
+        (1, 2, 'b'),
+        (2, 0, 'c'),
+        (3, 4, 'd'),
+        (4, 5, 'e'),
+        (5, 3, 'f'),
+        (2, 3, 'x'),
+        (4, 0, 'y'),
+    ] {
+        graph.add_edge(a, b, w);
+    }
+    let left: HashSet<_> = vec![0, 1, 2].into_iter().collect();
+    let right: HashSet<_> = vec![3, 4, 5].into_iter().collect();
+
+    assert_eq!(graph.boundary_edges(&left, &right), vec![(2, 3, &'x')]);
+    assert_eq!(graph.boundary_edges(&right, &left), vec![(4, 0, &'y')]);
+    assert_eq!(graph.boundary_edges(&left, &left).len(), 3);
+
+    let graph: UnGraphMap<_, _> = graph.all_edges().map(|(a, b, &w)| (a, b, w)).collect();
+    let mut cut = graph.boundary_edges(&left, &right);
+    cut.sort();
+    assert_eq!(cut, vec![(0, 4, &'y'), (2, 3, &'x')]);
+    assert!(graph.boundary_edges(&left, &HashSet::new()).is_empty());
+}
+
+#[test]
+fn incremental_components() {
+    use petgraph::graphmap::{EdgeEffect, IncrementalComponents};
+
+    let mut graph = DiGraphMap::<_, u32>::new();
+    graph.add_edge("a", "b", 1);
+    graph.add_edge("c", "b", 2);
+    graph.add_edge("x", "y", 3);
+    graph.add_node("z");
+
+    let mut comps = IncrementalComponents::new(graph);
+    assert_eq!(comps.component_count(), 3);
+    assert!(comps.same_component("a", "c"));
+    assert!(!comps.same_component("a", "x"));
+
+    // joining the two isolated subgraphs
+    assert_eq!(
+        comps.add_edge_tracked("y", "a", 4),
+        EdgeEffect::MergedComponents
+    );
+    assert_eq!(comps.component_count(), 2);
+    assert!(comps.same_component("x", "c"));
+    assert_eq!(comps.add_edge_tracked("c", "x", 5), EdgeEffect::NewEdge);
+    assert_eq!(
+        comps.add_edge_tracked("a", "b", 6),
+        EdgeEffect::DuplicateIgnored
+    );
+    assert_eq!(comps.graph()[("a", "b")], 1);
+
+    // new nodes start out as their own component
+    assert_eq!(comps.add_node("w"), "w");
+    assert_eq!(comps.component_count(), 3);
+    assert_eq!(
+        comps.add_edge_tracked("v", "z", 7),
+        EdgeEffect::MergedComponents
+    );
+    assert_eq!(comps.component_count(), 3);
+    assert!(!comps.same_component("v", "q"));
+
+    let graph = comps.into_graph();
+    assert_eq!(graph.node_count(), 8);
+    assert_eq!(graph.edge_count(), 6);
+}
+
+#[test]
+fn degree_peeling() {
+    use petgraph::graphmap::DegreePeeling;
+
+    // a star around 0, with an extra edge between the leaves 3 and 4
+    let graph = UnGraphMap::<_, ()>::from_edges(&[(0, 1), (0, 2), (0, 3), (0, 4), (3, 4)]);
+    let mut peeling = DegreePeeling::new(graph);
+    assert_eq!(peeling.degree(0), Some(4));
+    let mut order = Vec::new();
+    loop {
+        let graph = peeling.graph();
+        let min_degree = graph.nodes().map(|m| graph.neighbors(m).count()).min();
+        for m in graph.nodes() {
+            assert_eq!(peeling.degree(m), Some(graph.neighbors(m).count()));
+        }
+        match peeling.remove_min_degree_node() {
+            Some((n, degree)) => {
+                assert_eq!(Some(degree), min_degree);
+                assert!(!peeling.graph().contains_node(n));
+                order.push((n, degree));
+            }
+            None => break,
+        }
+    }
+    assert_eq!(order, vec![(2, 1), (1, 1), (0, 2), (4, 1), (3, 0)]);
+    assert_eq!(peeling.degree(0), None);
+    assert_eq!(peeling.into_graph().edge_count(), 0);
+
+    // directed: edges in both directions count twice, self loops once
+    let graph = DiGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 0), (1, 2), (2, 2), (3, 2)]);
+    let mut peeling = DegreePeeling::new(graph);
+    assert_eq!(peeling.degree(1), Some(3));
+    assert_eq!(peeling.degree(2), Some(3));
+    assert_eq!(peeling.remove_min_degree_node(), Some((3, 1)));
+    assert_eq!(peeling.remove_min_degree_node(), Some((2, 2)));
+    assert_eq!(peeling.degree(0), Some(2));
+    assert_eq!(peeling.remove_min_degree_node(), Some((1, 2)));
+    assert_eq!(peeling.remove_min_degree_node(), Some((0, 0)));
+    assert_eq!(peeling.remove_min_degree_node(), None);
+    assert_eq!(peeling.graph().validate(), Ok(()));
+}
+
+#[test]
+fn to_adjacency_string() {
+    let mut graph = DiGraphMap::new();
+    graph.add_edge('c', 'a', 1);
+    graph.add_edge('a', 'c', 5);
+    graph.add_edge('a', 'b', 2);
+    graph.add_node('d');
+    assert_eq!(
+        graph.to_adjacency_string(),
+        "'a' -> ['b' (2), 'c' (5)]\n\
+         'b' -> []\n\
+         'c' -> ['a' (1)]\n\
+         'd' -> []\n"
+    );
+
+    // undirected edges are listed at both endpoints
+    let graph = UnGraphMap::<_, f32>::from_edges(&[(1, 2, 0.5), (3, 1, 1.)]);
+    assert_eq!(
+        graph.to_adjacency_string(),
+        "1 -> [2 (0.5), 3 (1.0)]\n\
+         2 -> [1 (0.5)]\n\
+         3 -> [1 (1.0)]\n"
+    );
+}
+
+#[test]
+fn merge_with() {
+    // two shards of edge counts
+    let mut graph = DiGraphMap::new();
+    graph.add_edge("a", "b", 1);
+    graph.add_edge("b", "c", 2);
+    let mut shard = DiGraphMap::new();
+    shard.add_edge("a", "b", 1);
+    shard.add_edge("c", "b", 1);
+    shard.add_node("d");
+
+    graph.merge_with(&shard, |x, y| x + y);
+    assert_eq!(graph[("a", "b")], 2);
+    assert_eq!(graph[("b", "c")], 2);
+    assert_eq!(graph[("c", "b")], 1);
+    assert!(graph.contains_node("d"));
+    assert_eq!(graph.node_count(), 4);
+    assert_eq!(graph.edge_count(), 3);
+
+    // undirected edges match in either orientation
+    let mut graph = UnGraphMap::<_, i32>::from_edges(&[(1, 2, 5)]);
+    let other = UnGraphMap::<_, i32>::from_edges(&[(2, 1, 3)]);
+    graph.merge_with(&other, |x, y| x - y);
+    assert_eq!(graph.edge_weight(1, 2), Some(&2));
+    assert_eq!(graph.edge_count(), 1);
+}
+
+#[test]
+fn try_neighbors() {
+    let mut graph = DiGraphMap::<_, ()>::new();
+    graph.add_edge(0, 1, ());
+    graph.add_edge(2, 0, ());
+    graph.add_node(3);
+
+    assert_eq!(
+        graph.try_neighbors(0).map(|it| it.collect::<Vec<_>>()),
+        Some(vec![1])
+    );
+    // present but isolated
+    let mut isolated = graph.try_neighbors(3).unwrap();
+    assert_eq!(isolated.next(), None);
+    // only incoming edges
+    assert_eq!(graph.try_neighbors(1).unwrap().count(), 0);
+    // absent
+    assert!(graph.try_neighbors(4).is_none());
+    assert_eq!(graph.neighbors(4).count(), 0);
+}
+
+#[test]
+fn contract_edges_where() {
+    let mut graph = DiGraphMap::new();
+    graph.add_edge('a', 'b', 0);
+    graph.add_edge('c', 'b', 0);
+    graph.add_edge('b', 'd', 3);
+    graph.add_edge('d', 'e', 0);
+    graph.add_edge('e', 'a', 2);
+    graph.add_edge('a', 'c', 1);
+    graph.add_node('f');
+
+    let survivors = graph.contract_edges_where(|_, _, &w| w == 0);
+    let mut partition: Vec<Vec<char>> = Vec::new();
+    for survivor in graph.nodes() {
+        let mut class: Vec<char> = survivors
+            .iter()
+            .filter(|&(_, s)| *s == survivor)
+            .map(|(&n, _)| n)
+            .collect();
+        class.sort();
+        partition.push(class);
+    }
+    partition.sort();
+    assert_eq!(
+        partition,
+        vec![vec!['a', 'b', 'c'], vec!['d', 'e'], vec!['f']]
+    );
+    assert_eq!(survivors.len(), 6);
+
+    // the remaining edges are redirected to the survivors
+    assert_eq!(graph.node_count(), 3);
+    assert_eq!(graph.edge_count(), 3);
+    assert_eq!(graph[('a', 'd')], 3);
+    assert_eq!(graph[('d', 'a')], 2);
+    assert_eq!(graph[('a', 'a')], 1);
+    assert_eq!(graph.validate(), Ok(()));
+}
+
+#[test]
+fn neighbors_rev() {
+    let mut graph = DiGraphMap::<_, ()>::new();
+    graph.add_edge(0, 1, ());
+    graph.add_edge(2, 0, ());
+    graph.add_edge(0, 3, ());
+    graph.add_edge(0, 0, ());
+    graph.add_edge(4, 0, ());
+    graph.add_edge(0, 5, ());
+
+    let forward: Vec<_> = graph.neighbors(0).collect();
+    let mut backward: Vec<_> = graph.neighbors_rev(0).collect();
+    assert_eq!(backward, vec![5, 0, 3, 1]);
+    backward.reverse();
+    assert_eq!(forward, backward);
+    assert_eq!(graph.neighbors_rev(6).next(), None);
+
+    let graph = UnGraphMap::<_, ()>::from_edges(&[(1, 0), (0, 2), (3, 0)]);
+    assert_eq!(graph.neighbors_rev(0).collect::<Vec<_>>(), vec![3, 2, 1]);
+
+    // both ends can be consumed from the same iterator
+    let mut neighbors = graph.neighbors(0);
+    assert_eq!(neighbors.next_back(), Some(3));
+    assert_eq!(neighbors.next(), Some(1));
+    assert_eq!(neighbors.next_back(), Some(2));
+    assert_eq!(neighbors.next(), None);
+}
+
+#[test]
+fn gc() {
+    let mut graph = DiGraphMap::new();
+    graph.add_edge("a", "b", 1);
+    graph.add_edge("b", "c", 8);
+    graph.add_edge("c", "a", 2);
+    graph.add_edge("c", "x", 3);
+    graph.add_edge("x", "a", 9);
+    graph.add_node("y");
+    graph.add_node("z");
+
+    let mut rejected_edges = Vec::new();
+    let (nodes, edges) = graph.gc(
+        |&n| n < "x" || n == "z",
+        |&a, &b, &w| {
+            if w > 5 {
+                rejected_edges.push((a, b));
+            }
+            w <= 5
+        },
+    );
+    assert_eq!(nodes, vec!["x", "y"]);
+    assert_eq!(rejected_edges, vec![("b", "c")]);
+    assert_eq!(edges, vec![("b", "c", 8), ("c", "x", 3), ("x", "a", 9)]);
+
+    assert_eq!(graph.nodes().collect::<Vec<_>>(), vec!["a", "b", "c", "z"]);
+    assert_eq!(
+        graph.all_edges().collect::<Vec<_>>(),
+        vec![("a", "b", &1), ("c", "a", &2)]
+    );
+    assert_eq!(graph.validate(), Ok(()));
+
+    // nothing rejected
+    let (nodes, edges) = graph.gc(|_| true, |_, _, _| true);
+    assert!(nodes.is_empty() && edges.is_empty());
+    assert_eq!(graph.edge_count(), 2);
+}
+
+#[test]
+fn path_neighborhood() {
+    use petgraph::algo::path_neighborhood;
+
+    // a 5 x 5 grid of cells
+    let mut grid = UnGraphMap::<(i32, i32), ()>::new();
+    for x in 0..5 {
+        for y in 0..5 {
+            if x + 1 < 5 {
+                grid.add_edge((x, y), (x + 1, y), ());
+            }
+            if y + 1 < 5 {
+                grid.add_edge((x, y), (x, y + 1), ());
+            }
+        }
+    }
+    // a straight path along row 2
+    let path: Vec<_> = (1..4).map(|x| (x, 2)).collect();
+
+    let corridor = path_neighborhood(&grid, &path, 0);
+    assert_eq!(corridor, path.iter().cloned().collect::<HashSet<_>>());
+
+    let corridor = path_neighborhood(&grid, &path, 1);
+    let mut expected: HashSet<_> = (1..4).flat_map(|x| vec![(x, 1), (x, 2), (x, 3)]).collect();
+    expected.insert((0, 2));
+    expected.insert((4, 2));
+    assert_eq!(corridor, expected);
+
+    // radius 2 covers everything except the four corners
+    let corridor = path_neighborhood(&grid, &path, 2);
+    assert_eq!(corridor.len(), 21);
+    assert!(!corridor.contains(&(0, 0)));
+    assert!(corridor.contains(&(0, 1)));
+    assert!(corridor.contains(&(2, 0)));
+
+    assert_eq!(path_neighborhood(&grid, &path, 10).len(), 25);
+}
+
+#[test]
+fn deterministic_iteration_order() {
+    let order = [42, 7, 1000, -3, 0, 15];
+    let mut graph = DiGraphMap::<_, ()>::new();
+    for &node in &order {
+        graph.add_node(node);
+    }
+    graph.add_edge(-3, 999, ());
+    graph.add_edge(7, 7, ());
+    assert_eq!(
+        graph.nodes().collect::<Vec<_>>(),
+        vec![42, 7, 1000, -3, 0, 15, 999]
+    );
+    assert_eq!(
+        graph
+            .all_edges()
+            .map(|(a, b, _)| (a, b))
+            .collect::<Vec<_>>(),
+        vec![(-3, 999), (7, 7)]
+    );
+}
+
+#[test]
+fn eq_with_weights() {
+    let close = |x: &f32, y: &f32| (x - y).abs() < 1e-3;
+
+    let mut a = DiGraphMap::new();
+    a.add_edge("x", "y", 1.0);
+    a.add_edge("y", "z", 2.5);
+    a.add_node("w");
+    let mut b = DiGraphMap::new();
+    b.add_node("w");
+    b.add_edge("y", "z", 2.5004);
+    b.add_edge("x", "y", 0.9998);
+
+    assert!(a.eq_with_weights(&b, close));
+    assert!(b.eq_with_weights(&a, close));
+    assert!(!a.eq_with_weights(&b, |x, y| x == y));
+
+    // the direction of an edge matters
+    let mut c = b.clone();
+    c.remove_edge("x", "y");
+    c.add_edge("y", "x", 1.0);
+    assert!(!a.eq_with_weights(&c, close));
+
+    // so does an extra isolated node
+    let mut d = b.clone();
+    d.add_node("v");
+    assert!(!a.eq_with_weights(&d, close));
+    d.remove_node("w");
+    assert!(!a.eq_with_weights(&d, close));
+}
+
+#[test]
+fn remove_edges() {
+    let mut graph = UnGraphMap::new();
+    graph.add_edge("a", "b", 1);
+    graph.add_edge("b", "c", 2);
+    graph.add_edge("c", "d", 3);
+    graph.add_edge("d", "a", 4);
+
+    let removed = graph.remove_edges(vec![("c", "b"), ("a", "c"), ("a", "d")]);
+    assert_eq!(removed, vec![Some(2), None, Some(4)]);
+
+    assert_eq!(graph.node_count(), 4);
+    assert_eq!(graph.edge_count(), 2);
+    assert!(graph.contains_edge("a", "b"));
+    assert!(graph.contains_edge("d", "c"));
+    assert!(!graph.contains_edge("b", "c"));
+    assert_eq!(graph.neighbors("a").collect::<Vec<_>>(), vec!["b"]);
+    assert_eq!(graph.validate(), Ok(()));
+}
+
+#[test]
+fn capacity() {
+    let mut graph = DiGraphMap::<_, ()>::with_capacity(4, 8);
+    let (nodes, edges) = graph.capacity();
+    assert!(nodes >= 4 && edges >= 8);
+
+    graph.add_edge(0, 1, ());
+    graph.reserve_nodes(100);
+    graph.reserve_edges(50);
+    let (nodes, edges) = graph.capacity();
+    assert!(nodes >= graph.node_count() + 100);
+    assert!(edges >= graph.edge_count() + 50);
+
+    graph.add_edge(0, 2, ());
+    graph.add_edge(3, 0, ());
+    assert!(graph.edge_capacity(0).unwrap() >= 3);
+    assert!(graph.edge_capacity(1).unwrap() >= 1);
+    assert_eq!(graph.edge_capacity(4), None);
+}
+
+#[test]
+fn neighbors_by_degree() {
+    use petgraph::graphmap::DegreeOrder;
+
+    // out-degrees: b = 2, c = 0, d = 3, e = 1
+    let graph = DiGraphMap::<_, ()>::from_edges(&[
+        ("a", "b"),
+        ("a", "c"),
+        ("a", "d"),
+        ("a", "e"),
+        ("b", "x"),
+        ("b", "y"),
+        ("d", "x"),
+        ("d", "y"),
+        ("d", "z"),
+        ("e", "x"),
+    ]);
+    let ascending = graph.neighbors_by_degree("a", DegreeOrder::Ascending);
+    assert_eq!(ascending, vec!["c", "e", "b", "d"]);
+    let descending = graph.neighbors_by_degree("a", DegreeOrder::Descending);
+    assert_eq!(descending, vec!["d", "b", "e", "c"]);
+    let degrees: Vec<_> = ascending
+        .iter()
+        .map(|&n| graph.neighbors(n).count())
+        .collect();
+    assert_eq!(degrees, vec![0, 1, 2, 3]);
+
+    assert!(graph
+        .neighbors_by_degree("q", DegreeOrder::Ascending)
+        .is_empty());
+
+    // ties keep the neighbor order, undirected degree counts all edges
+    let graph = UnGraphMap::<_, ()>::from_edges(&[(0, 1), (0, 2), (0, 3), (3, 4)]);
+    assert_eq!(
+        graph.neighbors_by_degree(0, DegreeOrder::Ascending),
+        vec![1, 2, 3]
+    );
+    assert_eq!(
+        graph.neighbors_by_degree(0, DegreeOrder::Descending),
+        vec![3, 1, 2]
+    );
+}
+
+#[test]
+fn add_edge_default() {
+    let mut graph = DiGraphMap::<_, f32>::new();
+    graph.add_edge("a", "b", 2.5);
+
+    assert!(graph.add_edge_default("b", "c"));
+    assert_eq!(graph.edge_weight("b", "c"), Some(&0.));
+    assert_eq!(graph[("b", "c")], 0.);
+    assert!(graph.contains_node("c"));
+    assert!(!graph.contains_edge("c", "b"));
+
+    // an existing weight is kept
+    assert!(!graph.add_edge_default("a", "b"));
+    assert_eq!(graph.edge_weight("a", "b"), Some(&2.5));
+    assert_eq!(graph.edge_count(), 2);
+
+    let mut graph = UnGraphMap::<_, String>::new();
+    assert!(graph.add_edge_default(1, 2));
+    assert!(!graph.add_edge_default(2, 1));
+    assert_eq!(graph.edge_weight(2, 1).map(|w| w.as_str()), Some(""));
+}
+
+#[test]
+fn eq_edges() {
+    let a = DiGraphMap::<_, i32>::from_edges(&[(1, 2, 5), (2, 3, 6), (3, 3, 7)]);
+    let mut b = DiGraphMap::new();
+    b.add_node(9);
+    b.add_edge(3, 3, 7);
+    b.add_edge(2, 3, 6);
+    b.add_edge(1, 2, 5);
+
+    // an extra isolated node only matters for full equality
+    assert!(a.eq_edges(&b));
+    assert!(b.eq_edges(&a));
+    assert!(!a.eq_with_weights(&b, |x, y| x == y));
+    b.remove_node(9);
+    assert!(a.eq_with_weights(&b, |x, y| x == y));
+
+    b[(2, 3)] = 0;
+    assert!(!a.eq_edges(&b));
+    b[(2, 3)] = 6;
+    b.add_edge(3, 2, 6);
+    assert!(!a.eq_edges(&b));
+    assert!(!b.eq_edges(&a));
+
+    let a = UnGraphMap::<_, ()>::from_edges(&[(1, 2)]);
+    let b = UnGraphMap::<_, ()>::from_edges(&[(2, 1)]);
+    assert!(a.eq_edges(&b));
+}
+
+#[test]
+fn nested_map() {
+    use std::collections::HashMap;
+
+    let mut graph = DiGraphMap::from_edges(&[(1, 2, 'a'), (2, 3, 'b'), (3, 1, 'c'), (3, 3, 'd')]);
+    graph.add_node(4);
+
+    let map = graph.to_nested_map();
+    assert_eq!(map.len(), 4);
+    assert_eq!(map[&1].len(), 1);
+    assert_eq!(map[&3][&1], 'c');
+    assert_eq!(map[&3][&3], 'd');
+    assert!(!map[&2].contains_key(&1));
+    assert!(map[&4].is_empty());
+
+    let back = DiGraphMap::from_nested_map(&map);
+    assert!(back.eq_with_weights(&graph, |x, y| x == y));
+
+    // undirected edges are listed from both ends
+    let graph = UnGraphMap::from_edges(&[(1, 2, 0.5), (2, 3, 1.5)]);
+    let map = graph.to_nested_map();
+    assert_eq!(map[&2][&1], 0.5);
+    assert_eq!(map[&1][&2], 0.5);
+    assert_eq!(map[&2].len(), 2);
+    let back = UnGraphMap::from_nested_map(&map);
+    assert_eq!(back.edge_count(), 2);
+    assert!(back.eq_with_weights(&graph, |x, y| x == y));
+
+    // targets without an entry of their own still become nodes
+    let mut map = HashMap::new();
+    map.insert("x", vec![("y", ())].into_iter().collect::<HashMap<_, _>>());
+    let graph = DiGraphMap::from_nested_map(&map);
+    assert!(graph.contains_node("y"));
+    assert_eq!(graph.edge_count(), 1);
+}
+
+#[test]
+fn neighbors_ordered() {
+    let mut graph = DiGraphMap::new();
+    assert!(!graph.edge_order_tracking());
+    graph.set_edge_order_tracking(true);
+    for (i, &b) in [5, 1, 4, 2, 3].iter().enumerate() {
+        graph.add_edge(0, b, i);
+    }
+    assert!(!graph.stable_removal());
+
+    graph.remove_edge(0, 1);
+    // swap removal moved the last neighbor into the hole
+    assert_eq!(graph.neighbors(0).collect::<Vec<_>>(), vec![5, 3, 4, 2]);
+    assert_eq!(
+        graph.neighbors_ordered(0).collect::<Vec<_>>(),
+        vec![5, 4, 2, 3]
+    );
+    let edges: Vec<_> = graph.edges_ordered(0).map(|(_, b, &w)| (b, w)).collect();
+    assert_eq!(edges, vec![(5, 0), (4, 2), (2, 3), (3, 4)]);
+
+    // updating a weight keeps the place, re-adding goes last
+    graph.add_edge(0, 5, 10);
+    graph.remove_edge(0, 4);
+    graph.add_edge(0, 4, 11);
+    assert_eq!(
+        graph.neighbors_ordered(0).collect::<Vec<_>>(),
+        vec![5, 2, 3, 4]
+    );
+
+    // gc rebuilds the graph but keeps the sequence
+    graph.remove_edge(0, 2);
+    graph.gc(|_| true, |_, &b, _| b != 3);
+    assert_eq!(graph.neighbors_ordered(0).collect::<Vec<_>>(), vec![5, 4]);
+    assert_eq!(graph.validate(), Ok(()));
+
+    assert_eq!(graph.neighbors_ordered(42).count(), 0);
+
+    // enabling the tracking numbers the existing edges
+    let mut graph = UnGraphMap::<_, ()>::from_edges(&[(0, 1), (2, 0), (0, 3)]);
+    graph.set_edge_order_tracking(true);
+    graph.remove_edge(1, 0);
+    assert_eq!(graph.neighbors_ordered(0).collect::<Vec<_>>(), vec![2, 3]);
+    assert_eq!(graph.validate(), Ok(()));
+
+    graph.set_edge_order_tracking(false);
+    graph.add_edge(0, 4, ());
+    assert_eq!(graph.validate(), Ok(()));
+}
+
+#[test]
+#[should_panic]
+fn neighbors_ordered_untracked() {
+    let graph = DiGraphMap::<_, ()>::from_edges(&[(0, 1)]);
+    graph.neighbors_ordered(0);
+}
+
+#[test]
+fn edge_ids() {
+    let mut graph = DiGraphMap::new();
+    graph.set_edge_order_tracking(true);
+    let (ab, old) = graph.add_edge_with_id("a", "b", 1);
+    assert_eq!(old, None);
+    let (bc, _) = graph.add_edge_with_id("b", "c", 2);
+    let (ca, _) = graph.add_edge_with_id("c", "a", 3);
+    assert_eq!(graph.edge_id("b", "c"), Some(bc));
+    assert_eq!(graph.edge_id("c", "b"), None);
+
+    // replacing a weight keeps the id
+    assert_eq!(graph.add_edge_with_id("b", "c", 20), (bc, Some(2)));
+
+    assert_eq!(graph.remove_edge_by_id(ab), Some(1));
+    assert_eq!(graph.edge_by_id(ab), None);
+    assert_eq!(graph.remove_edge_by_id(ab), None);
+    assert_eq!(graph.edge_by_id(bc), Some(("b", "c", &20)));
+    assert_eq!(graph.edge_by_id(ca), Some(("c", "a", &3)));
+
+    // unrelated mutations don't affect the ids
+    graph.add_edge("c", "d", 4);
+    graph.remove_node("d");
+    graph.remove_edge("c", "a");
+    assert_eq!(graph.edge_by_id(ca), None);
+    assert_eq!(graph.edge_by_id(bc), Some(("b", "c", &20)));
+
+    // ids are not reused for a new edge between the same nodes
+    let (ab2, _) = graph.add_edge_with_id("a", "b", 5);
+    assert_ne!(ab, ab2);
+    assert_eq!(graph.edge_by_id(ab), None);
+    assert_eq!(graph.edge_by_id(ab2), Some(("a", "b", &5)));
+
+    graph.gc(|_| true, |_, _, &w| w != 5);
+    assert_eq!(graph.edge_by_id(ab2), None);
+    assert_eq!(graph.edge_by_id(bc), Some(("b", "c", &20)));
+    assert_eq!(graph.validate(), Ok(()));
+    // the ids are forgotten with the edge order
+    graph.set_edge_order_tracking(false);
+    assert_eq!(graph.edge_by_id(bc), None);
+    assert_eq!(graph.edge_id("b", "c"), None);
+}
+
+#[test]
+fn mutual_edges() {
+    use petgraph::algo::reciprocity;
+
+    // one mutual pair and one one-way edge
+    let mut graph = DiGraphMap::<_, ()>::from_edges(&[("a", "b"), ("b", "c"), ("b", "a")]);
+    assert_eq!(graph.mutual_edges().collect::<Vec<_>>(), vec![("a", "b")]);
+    assert!((reciprocity(&graph) - 2. / 3.).abs() < 1e-9);
+
+    // self loops are neither mutual nor counted
+    graph.add_edge("c", "c", ());
+    assert_eq!(graph.mutual_edges().count(), 1);
+    assert!((reciprocity(&graph) - 2. / 3.).abs() < 1e-9);
+
+    graph.add_edge("c", "b", ());
+    let mut mutual: Vec<_> = graph.mutual_edges().collect();
+    mutual.sort();
+    assert_eq!(mutual, vec![("a", "b"), ("b", "c")]);
+    assert_eq!(reciprocity(&graph), 1.);
+
+    assert_eq!(reciprocity(&DiGraphMap::<u8, ()>::new()), 0.);
+    let graph = UnGraphMap::<_, ()>::from_edges(&[(1, 2), (3, 2)]);
+    assert_eq!(
+        graph.mutual_edges().collect::<Vec<_>>(),
+        vec![(1, 2), (2, 3)]
+    );
+    assert_eq!(reciprocity(&graph), 1.);
+}
+
+#[test]
+fn relabel_with_map() {
+    use std::collections::HashMap;
+
+    let graph = DiGraphMap::from_edges(&[(1, 2, 'x'), (2, 3, 'y'), (3, 1, 'z')]);
+    let mut names = HashMap::new();
+    names.insert(1, "one");
+    names.insert(2, "two");
+    names.insert(3, "three");
+    names.insert(4, "four");
+
+    let renamed = graph.relabel_with_map(&names).unwrap();
+    assert_eq!(
+        renamed.nodes().collect::<Vec<_>>(),
+        vec!["one", "two", "three"]
+    );
+    assert_eq!(renamed.edge_count(), 3);
+    assert_eq!(renamed.edge_weight("two", "three"), Some(&'y'));
+    assert_eq!(renamed.edge_weight("three", "one"), Some(&'z'));
+    assert!(!renamed.contains_edge("two", "one"));
+
+    names.remove(&2);
+    assert_eq!(graph.relabel_with_map(&names).err(), Some(2));
+
+    // renaming two nodes to the same value merges them
+    let mut names = HashMap::new();
+    names.insert(1, 'a');
+    names.insert(2, 'a');
+    names.insert(3, 'b');
+    let merged = graph.relabel_with_map(&names).unwrap();
+    assert_eq!(merged.node_count(), 2);
+    assert!(merged.contains_edge('a', 'a'));
+    assert!(merged.contains_edge('a', 'b'));
+    assert!(merged.contains_edge('b', 'a'));
+}
+
+#[test]
+fn adjacency_round_trip() {
+    let mut graph = DiGraphMap::<u32, ()>::from_edges(&[(1, 2), (2, 3), (3, 1), (3, 3)]);
+    graph.add_node(7);
+
+    let mut out = Vec::new();
+    graph.write_adjacency(&mut out).unwrap();
+    let text = String::from_utf8(out).unwrap();
+    assert_eq!(text, "1: 2\n2: 3\n3: 1 3\n7:\n");
+
+    let back = DiGraphMap::<u32, ()>::read_adjacency(text.as_bytes()).unwrap();
+    // the isolated node reappears
+    assert!(back.contains_node(7));
+    assert_eq!(back.neighbors(7).count(), 0);
+    assert!(back.eq_with_weights(&graph, |_, _| true));
+
+    let graph = UnGraphMap::<char, f32>::from_edges(&[('a', 'b', 1.), ('b', 'c', 2.)]);
+    let mut out = Vec::new();
+    graph.write_adjacency(&mut out).unwrap();
+    let back = UnGraphMap::<char, f32>::read_adjacency(&out[..]).unwrap();
+    assert_eq!(back.edge_count(), 2);
+    assert_eq!(back.edge_weight('c', 'b'), Some(&0.));
+
+    // blank lines are skipped, bad lines are errors
+    let graph = DiGraphMap::<u8, ()>::read_adjacency("\n1: 2\n\n".as_bytes()).unwrap();
+    assert_eq!(graph.edge_count(), 1);
+    let err = DiGraphMap::<u8, ()>::read_adjacency("1 2\n".as_bytes()).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    let err = DiGraphMap::<u8, ()>::read_adjacency("1: x\n".as_bytes()).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn double_ended_edges() {
+    let mut graph = DiGraphMap::new();
+    graph.add_edge(0, 1, 'a');
+    graph.add_edge(2, 0, 'x');
+    graph.add_edge(0, 2, 'b');
+    graph.add_edge(0, 3, 'c');
+
+    let mut edges = graph.edges(0);
+    assert_eq!(edges.size_hint(), (0, Some(4)));
+    assert_eq!(edges.next_back(), Some((0, 3, &'c')));
+    assert_eq!(edges.next(), Some((0, 1, &'a')));
+    assert_eq!(edges.next_back(), Some((0, 2, &'b')));
+    assert_eq!(edges.next_back(), None);
+
+    let reversed: Vec<_> = graph.edges(0).rev().map(|(_, b, _)| b).collect();
+    assert_eq!(reversed, vec![3, 2, 1]);
+    assert_eq!(graph.neighbors(0).count(), 3);
+
+    let mut incoming = graph.neighbors_directed(0, Incoming);
+    assert_eq!(incoming.next_back(), Some(2));
+    assert_eq!(incoming.next(), None);
+    assert_eq!(
+        graph
+            .neighbors_directed(0, Outgoing)
+            .rev()
+            .collect::<Vec<_>>(),
+        vec![3, 2, 1]
+    );
+
+    let graph = UnGraphMap::<_, ()>::from_edges(&[(0, 1), (2, 0)]);
+    let mut edges = graph.edges(0);
+    assert_eq!(edges.len(), 2);
+    assert_eq!(edges.next_back(), Some((0, 2, &())));
+    assert_eq!(edges.len(), 1);
+    assert_eq!(graph.neighbors(0).len(), 2);
+    assert_eq!(graph.neighbors_directed(0, Incoming).len(), 2);
+}
+
+#[test]
+fn degree_histogram() {
+    use petgraph::graphmap::{DegreeHistogram, GraphMap, NodeTrait};
+    use petgraph::EdgeType;
+    use std::collections::HashMap;
+
+    fn check<N: NodeTrait, E, Ty: EdgeType>(g: &DegreeHistogram<N, E, Ty>) {
+        let graph = g.graph();
+        let mut expected = HashMap::new();
+        for n in graph.nodes() {
+            let degree = if graph.is_directed() {
+                let mut degree = graph.neighbors_directed(n, Outgoing).count()
+                    + graph.neighbors_directed(n, Incoming).count();
+                if graph.contains_edge(n, n) {
+                    degree -= 1;
+                }
+                degree
+            } else {
+                graph.neighbors(n).count()
+            };
+            *expected.entry(degree).or_insert(0) += 1;
+        }
+        assert_eq!(g.live_degree_histogram(), &expected);
+    }
+
+    let mut g = DegreeHistogram::new(UnGraphMap::from_edges(&[(0, 1), (1, 2)]));
+    check(&g);
+    g.add_edge(2, 3, ());
+    g.add_edge(3, 0, ());
+    g.add_node(7);
+    check(&g);
+    // updating an existing edge changes no degree
+    g.add_edge(1, 0, ());
+    g.add_edge(4, 4, ());
+    check(&g);
+    assert_eq!(g.remove_edge(1, 2), Some(()));
+    assert_eq!(g.remove_edge(1, 2), None);
+    check(&g);
+    assert!(g.remove_node(0));
+    assert!(!g.remove_node(0));
+    check(&g);
+    g.remove_node(4);
+    g.add_edge(7, 1, ());
+    check(&g);
+
+    let graph: GraphMap<u32, (), Directed> = DiGraphMap::from_edges(&[(0, 1), (1, 0), (1, 2)]);
+    let mut g = DegreeHistogram::new(graph);
+    check(&g);
+    g.add_edge(2, 2, ());
+    g.add_edge(3, 2, ());
+    check(&g);
+    g.remove_edge(1, 0);
+    check(&g);
+    g.remove_node(2);
+    check(&g);
+    assert_eq!(g.live_degree_histogram()[&0], 1);
+    assert_eq!(g.into_graph().edge_count(), 1);
+}
+
+#[test]
+fn node_metrics_csv_labels() {
+    use petgraph::csv::write_node_metrics_csv_with_labels;
+    use std::collections::HashMap;
+
+    let g = UnGraphMap::<_, ()>::from_edges(&[("ada", "bob"), ("bob", "cy")]);
+    let degree: HashMap<_, _> = g
+        .nodes()
+        .map(|n| (n, g.neighbors(n).count() as f64))
+        .collect();
+    let mut out = Vec::new();
+    write_node_metrics_csv_with_labels(&g, &[("degree", degree)], |n| n, &mut out).unwrap();
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "node,degree\nada,1\nbob,2\ncy,1\n"
+    );
+}
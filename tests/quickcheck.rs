@@ -1108,3 +1108,36 @@ quickcheck! {
         true
     }
 }
+
+quickcheck! {
+    fn reachability_oracle(g: Graph<(), ()>, pairs: Vec<(usize, usize)>) -> bool {
+        use petgraph::algo::{has_path_connecting, ReachabilityOracle};
+        if g.node_count() == 0 {
+            return true;
+        }
+        let oracle = ReachabilityOracle::new(&g);
+        for (a, b) in pairs {
+            let a = node_index(a % g.node_count());
+            let b = node_index(b % g.node_count());
+            assert_eq!(
+                oracle.can_reach(a, b),
+                has_path_connecting(&g, a, b, None),
+                "disagreement for {:?} -> {:?}",
+                a,
+                b
+            );
+        }
+        true
+    }
+}
+
+quickcheck! {
+    fn transitive_closure_fast_matches_traversal(g: Graph<(), ()>) -> bool {
+        use petgraph::algo::{reachability_matrix, transitive_closure_fast};
+        let (nodes, matrix) = reachability_matrix(&g);
+        let (fast_nodes, closure) = transitive_closure_fast(&g);
+        nodes == fast_nodes && matrix.iter().zip(&closure).all(|(row, bits)| {
+            row.iter().enumerate().all(|(j, &reachable)| bits.contains(j) == reachable)
+        })
+    }
+}
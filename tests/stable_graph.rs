@@ -407,3 +407,13 @@ fn weights_mut_iterator() {
     assert_eq!(gr.node_weights_mut().count(), gr.node_count());
     assert_eq!(gr.edge_weights_mut().count(), gr.edge_count());
 }
+
+#[test]
+fn weak_component_sizes() {
+    use petgraph::algo::weak_component_sizes;
+
+    // removed nodes are not counted
+    let mut g = StableGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (3, 4)]);
+    g.remove_node(n(1));
+    assert_eq!(weak_component_sizes(&g), vec![2, 1, 1]);
+}